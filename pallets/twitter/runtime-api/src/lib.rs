@@ -0,0 +1,63 @@
+//! Runtime API definition for pallet_twitter.
+//!
+//! Lets front-ends read timelines and comment threads page by page instead of dumping the
+//! whole `Tweets` map over state RPC.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The code generated by `decl_runtime_apis!` trips these lints.
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_twitter::TweetId;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to tweets.
+	pub trait TwitterApi<AccountId, Tweet, AccountExport> where
+		AccountId: Codec,
+		Tweet: Codec,
+		AccountExport: Codec,
+	{
+		/// Tweets of `account` that still exist, oldest first, skipping the first `offset` of
+		/// them and returning at most `limit`.
+		fn tweets_of(account: AccountId, offset: u32, limit: u32) -> Vec<Tweet>;
+
+		/// The tweet with id `id`, if it exists.
+		fn tweet(id: TweetId) -> Option<Tweet>;
+
+		/// Comments on the tweet `id` that still exist, oldest first, skipping the first
+		/// `offset` of them and returning at most `limit`.
+		fn comments_of(id: TweetId, offset: u32, limit: u32) -> Vec<Tweet>;
+
+		/// Tweets of `account` that still exist, newest first, skipping the first `offset` of
+		/// them and returning at most `limit`.
+		fn timeline_of(account: AccountId, offset: u32, limit: u32) -> Vec<Tweet>;
+
+		/// The tweets with the given ids, in the same order, `None` for missing or deleted ones.
+		fn tweets(ids: Vec<TweetId>) -> Vec<Option<Tweet>>;
+
+		/// The most recent tweets that still exist, newest first, at most `limit`.
+		fn recent(limit: u32) -> Vec<Tweet>;
+
+		/// Tweets of the conversation started by tweet `id` that still exist, oldest first,
+		/// skipping the first `offset` of them and returning at most `limit`.
+		fn conversation(id: TweetId, offset: u32, limit: u32) -> Vec<Tweet>;
+
+		/// Ids of the comments on the tweet `id` at the comment indices `start` to
+		/// `start + len`, skipping removed ones.
+		fn comments_page(id: TweetId, start: u32, len: u32) -> Vec<TweetId>;
+
+		/// Everything owned by `account`, its tweets paged from `cursor`, `0` for the first page,
+		/// at most `limit` at a time. The export holds the cursor of the next page.
+		fn export_account(account: AccountId, cursor: u64, limit: u32) -> AccountExport;
+
+		/// Whether the tweet quoted by retweet `id` was edited or deleted since it was quoted,
+		/// `None` if either is missing or the retweet has no snapshot of the quoted text.
+		fn quote_is_stale(id: TweetId) -> Option<bool>;
+
+		/// Followers of `account` after `start`, `None` for the first page, at most `limit` of
+		/// them. The next page starts at the last follower returned.
+		fn followers_page(account: AccountId, start: Option<AccountId>, limit: u32) -> Vec<AccountId>;
+	}
+}
@@ -0,0 +1,1529 @@
+//! Benchmarks for pallet_twitter.
+
+#![cfg(feature = "runtime-benchmarks")]
+// The test functions generated by `benchmarks!` iterate over a `vec!` of component values.
+#![cfg_attr(test, allow(clippy::useless_vec))]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Twitter;
+
+const SEED: u32 = 0;
+
+/// An account named `name` that can afford any number of tweet deposits.
+fn funded_account<T: Trait>(name: &'static str) -> T::AccountId {
+	let who = account(name, 0, SEED);
+	fund::<T>(&who);
+	who
+}
+
+/// The whitelisted caller, funded like `funded_account`.
+fn funded_caller<T: Trait>() -> T::AccountId {
+	let who = whitelisted_caller();
+	fund::<T>(&who);
+	who
+}
+
+fn fund<T: Trait>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// Text of `len` bytes, or longer if needed to hold `h` distinct hashtags, and never empty.
+fn text_with_hashtags(len: u32, h: u32) -> Vec<u8> {
+	let mut text = Vec::new();
+	for i in 0..h {
+		text.extend_from_slice(&[b'#', b'a' + (i / 26 % 26) as u8, b'a' + (i % 26) as u8, b' ']);
+	}
+	if text.len() < len.max(1) as usize {
+		text.resize(len.max(1) as usize, b'a');
+	}
+	text
+}
+
+/// `a` distinct content identifiers of `MaxCidLen` bytes.
+fn media(a: u32) -> Vec<Vec<u8>> {
+	(0..a).map(|i| {
+		let mut cid = i.encode();
+		cid.resize(MaxCidLen::get() as usize, b'a');
+		cid
+	}).collect()
+}
+
+/// A notification list holding `MaxNotifications` entries.
+fn full_inbox<T: Trait>() -> NotificationsOf<T> {
+	let from: T::AccountId = account("notifier", 0, SEED);
+	let notifications = (0..T::MaxNotifications::get() as TweetId)
+		.map(|i| Notification::Mentioned(from.clone(), TweetId::max_value() - i))
+		.collect();
+	NotificationsOf::<T>::truncate_from(notifications)
+}
+
+/// A full `TopTweets` of made up tweets that all have more than one like.
+fn full_leaderboard<T: Trait>() -> LeaderboardOf<T> {
+	let size = LeaderboardOf::<T>::bound();
+	let board = (0..size)
+		.map(|i| (TweetId::max_value() - i as TweetId, (size - i) as u32 + 1))
+		.collect();
+	LeaderboardOf::<T>::truncate_from(board)
+}
+
+/// `n` distinct muted words of `MaxMutedWordLen` bytes, none found in `text_with_hashtags`.
+fn muted_words<T: Trait>(n: u32) -> MutedWordsOf<T> {
+	let words = (0..n)
+		.map(|i| {
+			let mut word = i.encode();
+			word.resize(MaxMutedWordLen::get() as usize, b'z');
+			MutedWord::truncate_from(word)
+		})
+		.collect();
+	MutedWordsOf::<T>::truncate_from(words)
+}
+
+/// Post a tweet of maximal length with `h` hashtags from `author` and return its id.
+fn create_tweet<T: Trait>(author: &T::AccountId, h: u32) -> TweetId {
+	let id = Twitter::<T>::next_tweet_id();
+	let text = text_with_hashtags(T::MaxTextLen::get(), h);
+	Twitter::<T>::new_tweet(RawOrigin::Signed(author.clone()).into(), text, vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone)
+		.expect("tweet can be posted");
+	id
+}
+
+/// Make the reports of `who` count, as if it first tweeted `MinReporterAge` blocks ago.
+fn make_reports_count<T: Trait>(who: &T::AccountId) {
+	let now = frame_system::Module::<T>::block_number();
+	FirstTweetAt::<T>::insert(who, now);
+	frame_system::Module::<T>::set_block_number(now + T::MinReporterAge::get());
+}
+
+/// Post a tweet from `author` and quote it `d - 1` times in a chain, returning the id of the
+/// last tweet, which a new quote would make a chain of `d` quotes.
+fn create_quote_chain<T: Trait>(author: &T::AccountId, d: u32) -> TweetId {
+	let mut id = create_tweet::<T>(author, 0);
+	for _ in 1 .. d {
+		Twitter::<T>::retweet(RawOrigin::Signed(author.clone()).into(), id, b"quote".to_vec(), vec![], None)
+			.expect("tweet can be quoted");
+		id = Twitter::<T>::next_tweet_id() - 1;
+	}
+	id
+}
+
+benchmarks! {
+	_ { }
+
+	new_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let m in 0 .. T::MaxMentions::get();
+		let a in 0 .. T::MaxAttachments::get();
+		let caller = funded_caller::<T>();
+		let id = Twitter::<T>::next_tweet_id();
+		let text = text_with_hashtags(t, h);
+		// Full inboxes, so that every mention drops the oldest notification, and mentions that
+		// are recorded for the reply policy.
+		let mentions: Vec<T::AccountId> = (0..m).map(|i| account("mentioned", i, SEED)).collect();
+		for who in &mentions {
+			<Notifications<T>>::insert(who, full_inbox::<T>());
+		}
+	}: _(RawOrigin::Signed(caller.clone()), text, mentions.clone(), true, Visibility::Public, media(a), Some(*b"en"), ReplyPolicy::Followers)
+	verify {
+		let tweet = Twitter::<T>::tweets(id).unwrap();
+		assert_eq!(tweet.author, caller);
+		assert_eq!(tweet.media.len(), a as usize);
+		for who in &mentions {
+			assert_eq!(Twitter::<T>::notifications(who).last(), Some(&Notification::Mentioned(caller.clone(), id)));
+		}
+	}
+
+	tweet_as {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let owner = funded_account::<T>("owner");
+		let caller = funded_caller::<T>();
+		Twitter::<T>::add_delegate(RawOrigin::Signed(owner.clone()).into(), caller.clone())?;
+		let id = Twitter::<T>::next_tweet_id();
+		let text = text_with_hashtags(t, h);
+	}: _(RawOrigin::Signed(caller.clone()), owner.clone(), text)
+	verify {
+		assert_eq!(Twitter::<T>::tweets(id).unwrap().author, owner);
+	}
+
+	new_thread {
+		let s in 1 .. T::MaxThreadLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let id = Twitter::<T>::next_tweet_id();
+		let texts = (0..s).map(|_| text_with_hashtags(T::MaxTextLen::get(), h)).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller.clone()), texts)
+	verify {
+		let last = id + s as TweetId - 1;
+		let expected = if s > 1 { Some(last - 1) } else { None };
+		assert_eq!(Twitter::<T>::tweets(last).unwrap().thread_prev, expected);
+	}
+
+	new_ephemeral_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let id = Twitter::<T>::next_tweet_id();
+		let text = text_with_hashtags(t, h);
+	}: _(RawOrigin::Signed(caller.clone()), text, 10u32.into())
+	verify {
+		assert!(Twitter::<T>::tweet_expiry(id).is_some());
+	}
+
+	expire_tweets {
+		let e in 0 .. T::MaxExpirationsPerBlock::get();
+		let author = funded_account::<T>("author");
+		let text = text_with_hashtags(T::MaxTextLen::get(), T::MaxHashtagsPerTweet::get());
+		for _ in 0..e {
+			TweetRate::<T>::remove(&author);
+			Twitter::<T>::new_ephemeral_tweet(RawOrigin::Signed(author.clone()).into(), text.clone(), 1u32.into())?;
+		}
+		let now = frame_system::Module::<T>::block_number() + 1u32.into();
+		ExpiryCursor::<T>::put(now);
+	}: {
+		Twitter::<T>::expire_tweets(now);
+	}
+	verify {
+		assert_eq!(Twitter::<T>::accounts(&author), Vec::<TweetId>::new());
+	}
+
+	prune_hashtag_usage {
+		let p in 0 .. T::MaxHashtagPrunesPerBlock::get();
+		let window = T::BlockNumber::zero();
+		for i in 0..p {
+			HashtagUsage::<T>::insert(window, i.to_le_bytes().to_vec(), 1);
+		}
+		// The first window is too old to be kept.
+		let now = T::TrendingWindow::get() * (T::KeepWindows::get() + 1).into();
+	}: {
+		Twitter::<T>::prune_hashtag_usage(now);
+	}
+	verify {
+		assert_eq!(HashtagUsage::<T>::iter_prefix(window).count(), 0);
+	}
+
+	prune_recent_content {
+		let p in 0 .. T::MaxContentPrunesPerBlock::get();
+		let window = T::BlockNumber::zero();
+		for i in 0..p {
+			RecentContent::<T>::insert(window, blake2_256(&i.encode()), i as TweetId);
+		}
+		// The first window is older than the previous one.
+		let now = T::DedupWindow::get() * 2u32.into();
+	}: {
+		Twitter::<T>::prune_recent_content(now);
+	}
+	verify {
+		assert_eq!(RecentContent::<T>::iter_prefix(window).count(), 0);
+	}
+
+	schedule_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let text = text_with_hashtags(t, h);
+		let publish_at = frame_system::Module::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), text, publish_at)
+	verify {
+		assert!(Twitter::<T>::scheduled_tweets(publish_at, 0).is_some());
+	}
+
+	cancel_scheduled_tweet {
+		let caller = funded_caller::<T>();
+		let publish_at = frame_system::Module::<T>::block_number() + 10u32.into();
+		Twitter::<T>::schedule_tweet(RawOrigin::Signed(caller.clone()).into(), b"later".to_vec(), publish_at)?;
+	}: _(RawOrigin::Signed(caller.clone()), publish_at, 0)
+	verify {
+		assert!(Twitter::<T>::scheduled_tweets(publish_at, 0).is_none());
+	}
+
+	publish_scheduled {
+		let s in 0 .. T::MaxScheduledPerBlock::get();
+		let author = funded_account::<T>("author");
+		let text = text_with_hashtags(T::MaxTextLen::get(), T::MaxHashtagsPerTweet::get());
+		let publish_at = frame_system::Module::<T>::block_number() + 1u32.into();
+		for _ in 0..s {
+			TweetRate::<T>::remove(&author);
+			Twitter::<T>::schedule_tweet(RawOrigin::Signed(author.clone()).into(), text.clone(), publish_at)?;
+		}
+	}: {
+		Twitter::<T>::publish_scheduled(publish_at);
+	}
+	verify {
+		assert_eq!(Twitter::<T>::accounts(&author).len(), s as usize);
+	}
+
+	retweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let a in 0 .. T::MaxAttachments::get();
+		let d in 1 .. T::MaxQuoteDepth::get();
+		let author = funded_account::<T>("author");
+		let original = create_quote_chain::<T>(&author, d);
+		// A full inbox, so that the notification drops the oldest one.
+		<Notifications<T>>::insert(&author, full_inbox::<T>());
+		let caller = funded_caller::<T>();
+		let text = text_with_hashtags(t, h);
+	}: _(RawOrigin::Signed(caller.clone()), original, text, media(a), Some(*b"en"))
+	verify {
+		let tweet = Twitter::<T>::tweets(original + 1).unwrap();
+		assert_eq!(tweet.quote_tweet_id, Some(original));
+		assert_eq!(tweet.media.len(), a as usize);
+	}
+
+	repost {
+		let d in 1 .. T::MaxQuoteDepth::get();
+		let author = funded_account::<T>("author");
+		let original = create_quote_chain::<T>(&author, d);
+		// A full inbox, so that the notification drops the oldest one.
+		<Notifications<T>>::insert(&author, full_inbox::<T>());
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), original)
+	verify {
+		assert_eq!(Twitter::<T>::repost_count(original), 1);
+	}
+
+	comment {
+		let t in 0 .. T::MaxTextLen::get();
+		// Comments already on the parent, expected to have no influence on the weight.
+		let c in 0 .. T::MaxCommentsPerTweet::get() - 1;
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let author = funded_account::<T>("author");
+		let parent = create_tweet::<T>(&author, 0);
+		// A full inbox, so that the notification drops the oldest one.
+		<Notifications<T>>::insert(&author, full_inbox::<T>());
+		<NextCommentIndex>::insert(parent, c);
+		// Muted words that all have to be looked for.
+		<MutedWords<T>>::insert(&author, muted_words::<T>(T::MaxMutedWords::get()));
+		let caller = funded_caller::<T>();
+		// Replies restricted to followers, the caller only being let in as mentioned.
+		<Tweets<T>>::mutate(parent, |tweet| tweet.as_mut().unwrap().reply_policy = ReplyPolicy::Followers);
+		<TweetMentions<T>>::insert(parent, &caller, ());
+		let text = text_with_hashtags(t, h);
+	}: _(RawOrigin::Signed(caller.clone()), text, parent, Some(*b"en"))
+	verify {
+		assert_eq!(Twitter::<T>::comments(parent, c), Some(parent + 1));
+	}
+
+	delete_tweet {
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, h);
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert!(Twitter::<T>::is_deleted(id));
+	}
+
+	undo_retweet {
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let author = funded_account::<T>("author");
+		let original = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		let text = text_with_hashtags(0, h);
+		Twitter::<T>::retweet(RawOrigin::Signed(caller.clone()).into(), original, text, vec![], None)?;
+		let retweet_id = original + 1;
+	}: _(RawOrigin::Signed(caller.clone()), retweet_id)
+	verify {
+		assert!(Twitter::<T>::tweets(retweet_id).is_none());
+	}
+
+	edit_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, h);
+		let new_text = text_with_hashtags(t, h);
+	}: _(RawOrigin::Signed(caller.clone()), id, new_text.clone())
+	verify {
+		assert_eq!(Twitter::<T>::tweets(id).unwrap().text, new_text);
+	}
+
+	set_comments_enabled {
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, 0);
+	}: _(RawOrigin::Signed(caller.clone()), id, false)
+	verify {
+		assert!(!Twitter::<T>::tweets(id).unwrap().comments_enabled);
+	}
+
+	// Relaxing to `Everyone`, which drops the recorded mentions.
+	set_reply_policy {
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, 0);
+		<Tweets<T>>::mutate(id, |tweet| tweet.as_mut().unwrap().reply_policy = ReplyPolicy::Mentioned);
+	}: _(RawOrigin::Signed(caller.clone()), id, ReplyPolicy::Everyone)
+	verify {
+		assert_eq!(Twitter::<T>::tweets(id).unwrap().reply_policy, ReplyPolicy::Everyone);
+	}
+
+	bookmark {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert!(Twitter::<T>::bookmarks(&caller, id).is_some());
+	}
+
+	remove_bookmark {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		Twitter::<T>::bookmark(RawOrigin::Signed(caller.clone()).into(), id)?;
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert!(Twitter::<T>::bookmarks(&caller, id).is_none());
+	}
+
+	pin_tweet {
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, 0);
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Twitter::<T>::pinned_tweet(&caller), Some(id));
+	}
+
+	unpin_tweet {
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, 0);
+		Twitter::<T>::pin_tweet(RawOrigin::Signed(caller.clone()).into(), id)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Twitter::<T>::pinned_tweet(&caller), None);
+	}
+
+	mark_notifications_read {
+		let caller = funded_caller::<T>();
+		<Notifications<T>>::insert(&caller, full_inbox::<T>());
+		let count = T::MaxNotifications::get() as u64;
+		<NotificationCount<T>>::insert(&caller, count);
+	}: _(RawOrigin::Signed(caller.clone()), count - 1)
+	verify {
+		assert_eq!(Twitter::<T>::unread_count(&caller), 0);
+	}
+
+	clear_notifications {
+		let caller = funded_caller::<T>();
+		<Notifications<T>>::insert(&caller, full_inbox::<T>());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Twitter::<T>::notifications(&caller).is_empty());
+	}
+
+	block_account {
+		let target: T::AccountId = account("target", 0, SEED);
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), target.clone())
+	verify {
+		assert!(Twitter::<T>::blocked(&caller, &target).is_some());
+	}
+
+	unblock_account {
+		let target: T::AccountId = account("target", 0, SEED);
+		let caller = funded_caller::<T>();
+		Twitter::<T>::block_account(RawOrigin::Signed(caller.clone()).into(), target.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), target.clone())
+	verify {
+		assert!(Twitter::<T>::blocked(&caller, &target).is_none());
+	}
+
+	mute_word {
+		let caller = funded_caller::<T>();
+		<MutedWords<T>>::insert(&caller, muted_words::<T>(T::MaxMutedWords::get().saturating_sub(1)));
+		let word = vec![b'y'; MaxMutedWordLen::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), word)
+	verify {
+		assert_eq!(Twitter::<T>::muted_words(&caller).len() as u32, T::MaxMutedWords::get());
+	}
+
+	unmute_word {
+		let caller = funded_caller::<T>();
+		let words = muted_words::<T>(T::MaxMutedWords::get());
+		// The last word, found after all the others.
+		let word = words.last().cloned().unwrap_or_default().into_inner();
+		<MutedWords<T>>::insert(&caller, words);
+	}: _(RawOrigin::Signed(caller.clone()), word)
+	verify {
+		assert_eq!(Twitter::<T>::muted_words(&caller).len() as u32, T::MaxMutedWords::get().saturating_sub(1));
+	}
+
+	add_delegate {
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), delegate.clone())
+	verify {
+		assert!(Twitter::<T>::delegates(&caller, &delegate).is_some());
+	}
+
+	remove_delegate {
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let caller = funded_caller::<T>();
+		Twitter::<T>::add_delegate(RawOrigin::Signed(caller.clone()).into(), delegate.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), delegate.clone())
+	verify {
+		assert!(Twitter::<T>::delegates(&caller, &delegate).is_none());
+	}
+
+	follow_hashtag {
+		let caller = funded_caller::<T>();
+		let tag = vec![b'a'; MaxFollowedHashtagLen::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), tag.clone())
+	verify {
+		assert!(<FollowedHashtags<T>>::contains_key(&caller, &tag));
+	}
+
+	unfollow_hashtag {
+		let caller = funded_caller::<T>();
+		let tag = vec![b'a'; MaxFollowedHashtagLen::get() as usize];
+		Twitter::<T>::follow_hashtag(RawOrigin::Signed(caller.clone()).into(), tag.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), tag.clone())
+	verify {
+		assert!(!<FollowedHashtags<T>>::contains_key(&caller, &tag));
+	}
+
+	notify_hashtag_followers {
+		let f in 0 .. T::MaxHashtagFanout::get();
+		let author = funded_account::<T>("author");
+		let tags = vec![b"aa".to_vec()];
+		for i in 0..f {
+			let follower: T::AccountId = account("follower", i, SEED);
+			<HashtagFollowers<T>>::insert(&tags[0], &follower, ());
+			// A full inbox, so that the notification drops the oldest one.
+			<Notifications<T>>::insert(&follower, full_inbox::<T>());
+		}
+	}: {
+		Twitter::<T>::notify_hashtag_followers(&author, 0, &tags);
+	}
+	verify {
+		for i in 0..f {
+			let follower: T::AccountId = account("follower", i, SEED);
+			assert_eq!(Twitter::<T>::notification_count(&follower), 1);
+		}
+	}
+
+	set_profile {
+		let caller = funded_caller::<T>();
+		let display_name = vec![b'n'; MaxDisplayNameLen::get() as usize];
+		let bio = vec![b'b'; MaxBioLen::get() as usize];
+		let avatar_cid = Some(vec![b'c'; MaxAvatarCidLen::get() as usize]);
+	}: _(RawOrigin::Signed(caller.clone()), display_name, bio, avatar_cid)
+	verify {
+		assert!(Twitter::<T>::profiles(&caller).is_some());
+	}
+
+	clear_profile {
+		let caller = funded_caller::<T>();
+		Twitter::<T>::set_profile(RawOrigin::Signed(caller.clone()).into(), b"name".to_vec(), vec![], None)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Twitter::<T>::profiles(&caller).is_none());
+	}
+
+	claim_handle {
+		let caller = funded_caller::<T>();
+		let handle = vec![b'a'; MaxHandleLen::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), handle)
+	verify {
+		assert!(Twitter::<T>::handle_of(&caller).is_some());
+	}
+
+	release_handle {
+		let caller = funded_caller::<T>();
+		Twitter::<T>::claim_handle(RawOrigin::Signed(caller.clone()).into(), b"alice".to_vec())?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Twitter::<T>::handle_of(&caller).is_none());
+	}
+
+	subscribe_premium {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Twitter::<T>::premium(&caller).is_some());
+	}
+
+	cancel_premium {
+		let caller = funded_caller::<T>();
+		Twitter::<T>::subscribe_premium(RawOrigin::Signed(caller.clone()).into())?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Twitter::<T>::premium(&caller).is_none());
+	}
+
+	like {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		// A full inbox, so that the notification drops the oldest one.
+		<Notifications<T>>::insert(&author, full_inbox::<T>());
+		// The like is ranked after every tweet on the board.
+		<TopTweets<T>>::put(full_leaderboard::<T>());
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Twitter::<T>::like_count(id), 1);
+	}
+
+	unlike {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		Twitter::<T>::like(RawOrigin::Signed(caller.clone()).into(), id)?;
+		<TopTweets<T>>::put(full_leaderboard::<T>());
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Twitter::<T>::like_count(id), 0);
+	}
+
+	react {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		// Switching reactions updates two counters.
+		Twitter::<T>::react(RawOrigin::Signed(caller.clone()).into(), id, Reaction::Like)?;
+	}: _(RawOrigin::Signed(caller.clone()), id, Reaction::Angry)
+	verify {
+		assert_eq!(Twitter::<T>::reaction_counts(id), [0, 0, 0, 0, 1]);
+	}
+
+	remove_reaction {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		Twitter::<T>::react(RawOrigin::Signed(caller.clone()).into(), id, Reaction::Love)?;
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Twitter::<T>::reactions(id, &caller), None);
+	}
+
+	follow {
+		let target: T::AccountId = account("target", 0, SEED);
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), target.clone())
+	verify {
+		assert_eq!(Twitter::<T>::follower_count(&target), 1);
+	}
+
+	unfollow {
+		let target: T::AccountId = account("target", 0, SEED);
+		let caller = funded_caller::<T>();
+		Twitter::<T>::follow(RawOrigin::Signed(caller.clone()).into(), target.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), target.clone())
+	verify {
+		assert_eq!(Twitter::<T>::follower_count(&target), 0);
+	}
+
+	tip {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		let amount = T::Currency::minimum_balance() * 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), id, amount)
+	verify {
+		assert_eq!(Twitter::<T>::tip_total(id), amount);
+	}
+
+	new_poll_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let id = Twitter::<T>::next_tweet_id();
+		let text = text_with_hashtags(t, h);
+		let options = vec![vec![b'a'; MaxPollOptionLen::get() as usize]; MaxPollOptions::get() as usize];
+		let close_at = frame_system::Module::<T>::block_number() + 100u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), text, options, close_at)
+	verify {
+		assert!(Twitter::<T>::polls(id).is_some());
+	}
+
+	vote {
+		let author = funded_account::<T>("author");
+		let id = Twitter::<T>::next_tweet_id();
+		let options = vec![vec![b'a'; MaxPollOptionLen::get() as usize]; MaxPollOptions::get() as usize];
+		let close_at = frame_system::Module::<T>::block_number() + 100u32.into();
+		Twitter::<T>::new_poll_tweet(RawOrigin::Signed(author).into(), b"poll".to_vec(), options, close_at)?;
+		let caller = funded_caller::<T>();
+		Twitter::<T>::vote(RawOrigin::Signed(caller.clone()).into(), id, 0)?;
+	}: _(RawOrigin::Signed(caller.clone()), id, MaxPollOptions::get() as u8 - 1)
+	verify {
+		assert_eq!(Twitter::<T>::poll_votes(id, &caller), Some(MaxPollOptions::get() as u8 - 1));
+	}
+
+	close_poll {
+		let author = funded_account::<T>("author");
+		let id = Twitter::<T>::next_tweet_id();
+		let options = vec![vec![b'a'; MaxPollOptionLen::get() as usize]; MaxPollOptions::get() as usize];
+		let close_at = frame_system::Module::<T>::block_number() + 100u32.into();
+		Twitter::<T>::new_poll_tweet(RawOrigin::Signed(author).into(), b"poll".to_vec(), options, close_at)?;
+		frame_system::Module::<T>::set_block_number(close_at + One::one());
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert_eq!(Twitter::<T>::poll_results(id), Some(vec![0; MaxPollOptions::get() as usize]));
+	}
+
+	mint_tweet {
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, 0);
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert_eq!(Twitter::<T>::tweet_owner(id), Some(caller));
+	}
+
+	transfer_tweet {
+		let caller = funded_caller::<T>();
+		let id = create_tweet::<T>(&caller, 0);
+		Twitter::<T>::mint_tweet(RawOrigin::Signed(caller.clone()).into(), id)?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(caller), id, to.clone())
+	verify {
+		assert_eq!(Twitter::<T>::tweet_owner(id), Some(to));
+	}
+
+	force_remove_tweet {
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, h);
+		let reporter = funded_caller::<T>();
+		Twitter::<T>::report_tweet(RawOrigin::Signed(reporter).into(), id, Reason::Illegal)?;
+		let origin = T::ForceRemoveOrigin::successful_origin();
+	}: {
+		Twitter::<T>::force_remove_tweet(origin, id)?;
+	}
+	verify {
+		assert!(Twitter::<T>::censored(id).is_some());
+	}
+
+	set_global_announcement {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let origin = T::AnnouncementOrigin::successful_origin();
+	}: {
+		Twitter::<T>::set_global_announcement(origin, Some(id))?;
+	}
+	verify {
+		assert_eq!(Twitter::<T>::global_announcement(), Some(id));
+	}
+
+	set_text_limit {
+	}: _(RawOrigin::Root, 1)
+	verify {
+		assert_eq!(Twitter::<T>::text_limit(), 1);
+	}
+
+	set_verified {
+		let who: T::AccountId = account("who", 0, SEED);
+		let origin = T::VerifyOrigin::successful_origin();
+	}: {
+		Twitter::<T>::set_verified(origin, who.clone(), true)?;
+	}
+	verify {
+		assert!(Twitter::<T>::is_verified(&who));
+	}
+
+	// The counted report that hides the tweet.
+	report_tweet {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		make_reports_count::<T>(&caller);
+		let threshold = T::AutoHideThreshold::get();
+		<ReportCount>::insert(id, threshold.saturating_sub(1));
+	}: _(RawOrigin::Signed(caller.clone()), id, Reason::Spam)
+	verify {
+		assert_eq!(Twitter::<T>::report_count(id), threshold.max(1));
+		assert_eq!(Twitter::<T>::tweets(id).unwrap().hidden, threshold > 0);
+	}
+
+	resolve_report {
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, h);
+		let reporter = funded_caller::<T>();
+		make_reports_count::<T>(&reporter);
+		Twitter::<T>::report_tweet(RawOrigin::Signed(reporter).into(), id, Reason::Illegal)?;
+		let origin = T::ModerationOrigin::successful_origin();
+	}: {
+		Twitter::<T>::resolve_report(origin, id, ModerationAction::Remove)?;
+	}
+	verify {
+		assert!(Twitter::<T>::is_deleted(id));
+	}
+
+	purge_account {
+		// Tweets of the caller, each one an item to purge.
+		let l in 0 .. 100;
+		let caller = funded_caller::<T>();
+		for _ in 0..l {
+			TweetRate::<T>::remove(&caller);
+			create_tweet::<T>(&caller, T::MaxHashtagsPerTweet::get());
+		}
+	}: _(RawOrigin::Signed(caller.clone()), l)
+	verify {
+		assert_eq!(Twitter::<T>::account_tweet_count(&caller), 0);
+	}
+
+	// Tweets without a deposit, each one removed with all its hashtags.
+	prune_old_tweets {
+		let n in 0 .. 100;
+		let author = funded_account::<T>("author");
+		for _ in 0..n {
+			TweetRate::<T>::remove(&author);
+			let id = create_tweet::<T>(&author, T::MaxHashtagsPerTweet::get());
+			TweetDeposits::<T>::remove(id);
+		}
+		let block = frame_system::Module::<T>::block_number();
+		PruneCursor::<T>::put(block);
+		let origin = T::PruneOrigin::successful_origin();
+	}: {
+		Twitter::<T>::prune_old_tweets(origin, block + 1u32.into(), n)?;
+	}
+	verify {
+		assert_eq!(Twitter::<T>::accounts(&author), Vec::<TweetId>::new());
+	}
+
+	add_note {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let caller = funded_caller::<T>();
+		<NoteCount>::insert(id, T::MaxNotesPerTweet::get().saturating_sub(1));
+		let text = text_with_hashtags(T::MaxTextLen::get(), 0);
+	}: _(RawOrigin::Signed(caller.clone()), id, text)
+	verify {
+		assert_eq!(Twitter::<T>::note_count(id), T::MaxNotesPerTweet::get());
+	}
+
+	// The rating that gets the note shown.
+	rate_note {
+		let author = funded_account::<T>("author");
+		let id = create_tweet::<T>(&author, 0);
+		let note = Note {
+			author,
+			text: TextOf::<T>::truncate_from(text_with_hashtags(T::MaxTextLen::get(), 0)),
+			up: T::MinNoteRatings::get().saturating_sub(1),
+			down: 0,
+			shown: false,
+		};
+		<Notes<T>>::insert(id, 0, note);
+		<NoteCount>::insert(id, 1);
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), id, 0, true)
+	verify {
+		assert!(Twitter::<T>::notes(id, 0).map_or(false, |note| note.shown));
+	}
+
+	create_community {
+		let caller = funded_caller::<T>();
+		let name = vec![b'a'; MaxCommunityNameLen::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), name)
+	verify {
+		assert!(Twitter::<T>::members(0, &caller).is_some());
+	}
+
+	join_community {
+		let owner = funded_account::<T>("owner");
+		Twitter::<T>::create_community(RawOrigin::Signed(owner).into(), b"community".to_vec())?;
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(Twitter::<T>::members(0, &caller).is_some());
+	}
+
+	leave_community {
+		let owner = funded_account::<T>("owner");
+		Twitter::<T>::create_community(RawOrigin::Signed(owner).into(), b"community".to_vec())?;
+		let caller = funded_caller::<T>();
+		Twitter::<T>::join_community(RawOrigin::Signed(caller.clone()).into(), 0)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert!(Twitter::<T>::members(0, &caller).is_none());
+	}
+
+	remove_member {
+		let member = funded_account::<T>("member");
+		let caller = funded_caller::<T>();
+		Twitter::<T>::create_community(RawOrigin::Signed(caller.clone()).into(), b"community".to_vec())?;
+		Twitter::<T>::join_community(RawOrigin::Signed(member.clone()).into(), 0)?;
+	}: _(RawOrigin::Signed(caller), 0, member.clone())
+	verify {
+		assert!(Twitter::<T>::members(0, &member).is_none());
+	}
+
+	post_to_community {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		Twitter::<T>::create_community(RawOrigin::Signed(caller.clone()).into(), b"community".to_vec())?;
+		let id = Twitter::<T>::next_tweet_id();
+		let text = text_with_hashtags(t, h);
+	}: _(RawOrigin::Signed(caller.clone()), 0, text)
+	verify {
+		assert_eq!(Twitter::<T>::tweets(id).unwrap().community(), Some(0));
+	}
+
+	commit_tweet {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), [0; 32])
+	verify {
+		assert!(Twitter::<T>::commitments([0; 32]).is_some());
+	}
+
+	reveal_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let committer = funded_account::<T>("committer");
+		let caller = funded_caller::<T>();
+		let text = text_with_hashtags(t, h);
+		let hash = blake2_256(&[&text[..], &[0; 32]].concat());
+		Twitter::<T>::commit_tweet(RawOrigin::Signed(committer).into(), hash)?;
+	}: _(RawOrigin::Signed(caller), text, [0; 32])
+	verify {
+		assert!(Twitter::<T>::revealed_tweet(hash).is_some());
+	}
+
+	expire_commitments {
+		let n in 0 .. T::MaxCommitsPerBlock::get();
+		let committer = funded_account::<T>("committer");
+		for i in 0..n {
+			Twitter::<T>::commit_tweet(RawOrigin::Signed(committer.clone()).into(), [i as u8; 32])?;
+		}
+		let expires_at = frame_system::Module::<T>::block_number() + T::RevealWindow::get() + 1u32.into();
+	}: {
+		Twitter::<T>::expire_commitments(expires_at);
+	}
+	verify {
+		assert!(Twitter::<T>::commitments([0; 32]).is_none());
+	}
+
+	update_settings {
+		let caller = funded_caller::<T>();
+		let settings = AccountSettings { reply_policy: ReplyPolicy::Mentioned, mentions_from_non_followers: false, accepts_tips: false };
+	}: _(RawOrigin::Signed(caller.clone()), settings.clone())
+	verify {
+		assert_eq!(Twitter::<T>::settings(&caller), settings);
+	}
+
+	remove_comment {
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let commenter = funded_account::<T>("commenter");
+		let parent = create_tweet::<T>(&caller, 0);
+		let comment = Twitter::<T>::next_tweet_id();
+		Twitter::<T>::comment(RawOrigin::Signed(commenter).into(), text_with_hashtags(0, h), parent, None)?;
+	}: _(RawOrigin::Signed(caller), parent, comment)
+	verify {
+		assert!(Twitter::<T>::comments(parent, 0).is_none());
+	}
+
+	force_create_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let author = funded_account::<T>("author");
+		let id = T::FirstUserTweetId::get().saturating_sub(1);
+	}: _(RawOrigin::Root, id, author.clone(), text_with_hashtags(t, h))
+	verify {
+		assert_eq!(Twitter::<T>::accounts(&author), vec![id]);
+	}
+
+	new_gated_tweet {
+		let t in 0 .. T::MaxTextLen::get();
+		let h in 0 .. T::MaxHashtagsPerTweet::get();
+		let caller = funded_caller::<T>();
+		let id = Twitter::<T>::next_tweet_id();
+		let price = T::Currency::minimum_balance() * 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), text_with_hashtags(t, h), vec![b'a'; T::MaxTextLen::get() as usize], price)
+	verify {
+		assert!(Twitter::<T>::gated_content(id).is_some());
+	}
+
+	unlock {
+		let author = funded_account::<T>("author");
+		let id = Twitter::<T>::next_tweet_id();
+		let price = T::Currency::minimum_balance() * 10u32.into();
+		Twitter::<T>::new_gated_tweet(RawOrigin::Signed(author).into(), b"teaser".to_vec(), b"full".to_vec(), price)?;
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), id)
+	verify {
+		assert!(Twitter::<T>::unlocked(id, &caller).is_some());
+	}
+
+	set_subscription_price {
+		let caller = funded_caller::<T>();
+		let price = T::Currency::minimum_balance() * 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), Some(price))
+	verify {
+		assert_eq!(Twitter::<T>::subscription_price(&caller), Some(price));
+	}
+
+	subscribe {
+		let author = funded_account::<T>("author");
+		let price = T::Currency::minimum_balance() * 10u32.into();
+		Twitter::<T>::set_subscription_price(RawOrigin::Signed(author.clone()).into(), Some(price))?;
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), author.clone())
+	verify {
+		assert!(Twitter::<T>::subscription(&author, &caller).is_some());
+	}
+
+	renew {
+		let author = funded_account::<T>("author");
+		let price = T::Currency::minimum_balance() * 10u32.into();
+		Twitter::<T>::set_subscription_price(RawOrigin::Signed(author.clone()).into(), Some(price))?;
+		let caller = funded_caller::<T>();
+		Twitter::<T>::subscribe(RawOrigin::Signed(caller.clone()).into(), author.clone())?;
+		let expires_at = Twitter::<T>::subscription(&author, &caller).unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), author.clone())
+	verify {
+		assert_eq!(Twitter::<T>::subscription(&author, &caller), Some(expires_at + T::SubscriptionPeriod::get()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, ExtBuilder, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn new_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_new_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn tweet_as() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_tweet_as::<Test>());
+		});
+	}
+
+	#[test]
+	fn new_thread() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_new_thread::<Test>());
+		});
+	}
+
+	#[test]
+	fn new_ephemeral_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_new_ephemeral_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn expire_tweets() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_expire_tweets::<Test>());
+		});
+	}
+
+	#[test]
+	fn prune_hashtag_usage() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_prune_hashtag_usage::<Test>());
+		});
+	}
+
+	#[test]
+	fn prune_recent_content() {
+		ExtBuilder::default().dedup_window(10).build().execute_with(|| {
+			assert_ok!(test_benchmark_prune_recent_content::<Test>());
+		});
+	}
+
+	#[test]
+	fn schedule_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_schedule_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn cancel_scheduled_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_scheduled_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn publish_scheduled() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_publish_scheduled::<Test>());
+		});
+	}
+
+	#[test]
+	fn retweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_retweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn repost() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_repost::<Test>());
+		});
+	}
+
+	#[test]
+	fn comment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_comment::<Test>());
+		});
+	}
+
+	#[test]
+	fn delete_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_delete_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn undo_retweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_undo_retweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn edit_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_edit_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_comments_enabled() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_comments_enabled::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_reply_policy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_reply_policy::<Test>());
+		});
+	}
+
+	#[test]
+	fn bookmark() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_bookmark::<Test>());
+		});
+	}
+
+	#[test]
+	fn remove_bookmark() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_bookmark::<Test>());
+		});
+	}
+
+	#[test]
+	fn pin_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pin_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn unpin_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unpin_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn mark_notifications_read() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mark_notifications_read::<Test>());
+		});
+	}
+
+	#[test]
+	fn clear_notifications() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_clear_notifications::<Test>());
+		});
+	}
+
+	#[test]
+	fn block_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_block_account::<Test>());
+		});
+	}
+
+	#[test]
+	fn unblock_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unblock_account::<Test>());
+		});
+	}
+
+	#[test]
+	fn mute_word() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mute_word::<Test>());
+		});
+	}
+
+	#[test]
+	fn unmute_word() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unmute_word::<Test>());
+		});
+	}
+
+	#[test]
+	fn add_delegate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_delegate::<Test>());
+		});
+	}
+
+	#[test]
+	fn remove_delegate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_delegate::<Test>());
+		});
+	}
+
+	#[test]
+	fn follow_hashtag() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_follow_hashtag::<Test>());
+		});
+	}
+
+	#[test]
+	fn unfollow_hashtag() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unfollow_hashtag::<Test>());
+		});
+	}
+
+	#[test]
+	fn notify_hashtag_followers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_notify_hashtag_followers::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_profile() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_profile::<Test>());
+		});
+	}
+
+	#[test]
+	fn clear_profile() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_clear_profile::<Test>());
+		});
+	}
+
+	#[test]
+	fn claim_handle() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_handle::<Test>());
+		});
+	}
+
+	#[test]
+	fn release_handle() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_release_handle::<Test>());
+		});
+	}
+
+	#[test]
+	fn subscribe_premium() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_subscribe_premium::<Test>());
+		});
+	}
+
+	#[test]
+	fn cancel_premium() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_premium::<Test>());
+		});
+	}
+
+	#[test]
+	fn like() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_like::<Test>());
+		});
+	}
+
+	#[test]
+	fn unlike() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unlike::<Test>());
+		});
+	}
+
+	#[test]
+	fn react() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_react::<Test>());
+		});
+	}
+
+	#[test]
+	fn remove_reaction() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_reaction::<Test>());
+		});
+	}
+
+	#[test]
+	fn follow() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_follow::<Test>());
+		});
+	}
+
+	#[test]
+	fn unfollow() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unfollow::<Test>());
+		});
+	}
+
+	#[test]
+	fn tip() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_tip::<Test>());
+		});
+	}
+
+	#[test]
+	fn new_poll_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_new_poll_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn vote() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_vote::<Test>());
+		});
+	}
+
+	#[test]
+	fn close_poll() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_close_poll::<Test>());
+		});
+	}
+
+	#[test]
+	fn mint_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mint_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn transfer_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn force_remove_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_force_remove_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_global_announcement() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_global_announcement::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_text_limit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_text_limit::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_verified() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_verified::<Test>());
+		});
+	}
+
+	#[test]
+	fn report_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_report_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn resolve_report() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_resolve_report::<Test>());
+		});
+	}
+
+	#[test]
+	fn purge_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_purge_account::<Test>());
+		});
+	}
+
+	#[test]
+	fn prune_old_tweets() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_prune_old_tweets::<Test>());
+		});
+	}
+
+	#[test]
+	fn add_note() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_note::<Test>());
+		});
+	}
+
+	#[test]
+	fn rate_note() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_rate_note::<Test>());
+		});
+	}
+
+	#[test]
+	fn create_community() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_community::<Test>());
+		});
+	}
+
+	#[test]
+	fn join_community() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_join_community::<Test>());
+		});
+	}
+
+	#[test]
+	fn leave_community() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_leave_community::<Test>());
+		});
+	}
+
+	#[test]
+	fn remove_member() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_member::<Test>());
+		});
+	}
+
+	#[test]
+	fn post_to_community() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_post_to_community::<Test>());
+		});
+	}
+
+	#[test]
+	fn commit_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_commit_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn reveal_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_reveal_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn expire_commitments() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_expire_commitments::<Test>());
+		});
+	}
+
+	#[test]
+	fn update_settings() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_settings::<Test>());
+		});
+	}
+
+	#[test]
+	fn remove_comment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_comment::<Test>());
+		});
+	}
+
+	#[test]
+	fn force_create_tweet() {
+		ExtBuilder::default().first_user_tweet_id(1).build().execute_with(|| {
+			assert_ok!(test_benchmark_force_create_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn new_gated_tweet() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_new_gated_tweet::<Test>());
+		});
+	}
+
+	#[test]
+	fn unlock() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unlock::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_subscription_price() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_subscription_price::<Test>());
+		});
+	}
+
+	#[test]
+	fn subscribe() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_subscribe::<Test>());
+		});
+	}
+
+	#[test]
+	fn renew() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_renew::<Test>());
+		});
+	}
+}
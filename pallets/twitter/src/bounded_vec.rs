@@ -0,0 +1,156 @@
+//! A vector with a length bound that is enforced when constructing and decoding it.
+
+#[cfg(feature = "std")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+use codec::{Encode, EncodeLike, Decode, Input, Output};
+use sp_std::{prelude::*, convert::TryFrom, fmt, marker::PhantomData, ops::Deref};
+use frame_support::traits::Get;
+
+/// A `Vec` that never holds more than `S::get()` items.
+///
+/// It is encoded exactly like a `Vec`, but decoding an over-long value fails, so such a value
+/// can not end up in storage.
+pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S: Get<u32>> BoundedVec<T, S> {
+	/// The maximum number of items.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Build from `vec`, dropping the items past the bound.
+	pub fn truncate_from(mut vec: Vec<T>) -> Self {
+		vec.truncate(Self::bound());
+		Self(vec, PhantomData)
+	}
+
+	/// Append an item, failing if the vector is already full.
+	pub fn try_push(&mut self, item: T) -> Result<(), ()> {
+		if self.0.len() >= Self::bound() {
+			return Err(());
+		}
+		self.0.push(item);
+		Ok(())
+	}
+}
+
+impl<T, S> BoundedVec<T, S> {
+	/// Consume self and return the inner `Vec`.
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+
+	/// Retain only the items matching `f`.
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+		self.0.retain(f)
+	}
+
+	/// Remove and return the item at `index`.
+	pub fn remove(&mut self, index: usize) -> T {
+		self.0.remove(index)
+	}
+}
+
+impl<T, S> Default for BoundedVec<T, S> {
+	fn default() -> Self {
+		Self(Vec::new(), PhantomData)
+	}
+}
+
+impl<T: Clone, S> Clone for BoundedVec<T, S> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone(), PhantomData)
+	}
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedVec<T, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<T: PartialEq, S> PartialEq<Vec<T>> for BoundedVec<T, S> {
+	fn eq(&self, other: &Vec<T>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<T: Eq, S> Eq for BoundedVec<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedVec<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<T, S> Deref for BoundedVec<T, S> {
+	type Target = Vec<T>;
+
+	fn deref(&self) -> &Vec<T> {
+		&self.0
+	}
+}
+
+impl<T, S> AsRef<[T]> for BoundedVec<T, S> {
+	fn as_ref(&self) -> &[T] {
+		&self.0
+	}
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
+	type Error = ();
+
+	fn try_from(vec: Vec<T>) -> Result<Self, ()> {
+		if vec.len() > Self::bound() {
+			return Err(());
+		}
+		Ok(Self(vec, PhantomData))
+	}
+}
+
+impl<T, S> From<BoundedVec<T, S>> for Vec<T> {
+	fn from(bounded: BoundedVec<T, S>) -> Vec<T> {
+		bounded.0
+	}
+}
+
+impl<T: Encode, S> Encode for BoundedVec<T, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn encode_to<W: Output>(&self, dest: &mut W) {
+		self.0.encode_to(dest)
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<T: Encode, S> EncodeLike for BoundedVec<T, S> {}
+
+impl<T: Encode, S> EncodeLike<Vec<T>> for BoundedVec<T, S> {}
+
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let vec = Vec::<T>::decode(input)?;
+		Self::try_from(vec).map_err(|_| "BoundedVec exceeds its bound".into())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Serialize, S> Serialize for BoundedVec<T, S> {
+	fn serialize<D: Serializer>(&self, serializer: D) -> Result<D::Ok, D::Error> {
+		self.0.serialize(serializer)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de, T: Deserialize<'de>, S: Get<u32>> Deserialize<'de> for BoundedVec<T, S> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let vec = Vec::<T>::deserialize(deserializer)?;
+		Self::try_from(vec).map_err(|_| serde::de::Error::custom("BoundedVec exceeds its bound"))
+	}
+}
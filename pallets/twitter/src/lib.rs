@@ -1,16 +1,33 @@
 //! A decentralized twitter based on Substrate
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![recursion_limit="512"]
 
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 
 use codec::{Encode, Decode};
-use sp_std::prelude::*;
-use sp_runtime::{RuntimeDebug, DispatchResult};
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure};
-use frame_system::ensure_signed;
+use sp_std::{prelude::*, cell::Cell, collections::btree_map::BTreeMap, convert::TryFrom};
+use sp_runtime::{
+	RuntimeDebug, DispatchError, DispatchResult, Perbill, offchain::storage::StorageValueRef,
+	traits::{IdentifyAccount, One, Saturating, UniqueSaturatedInto, Verify, Zero},
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction},
+};
+use sp_io::hashing::blake2_256;
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, debug, ensure, parameter_types, Parameter,
+	Blake2_128Concat, Twox64Concat, Twox128, StorageHasher, ReversibleStorageHasher, dispatch::{DispatchResultWithPostInfo, PostDispatchInfo},
+	IterableStorageMap, IterableStorageDoubleMap,
+	storage::{StoragePrefixedMap, unhashed, with_transaction, TransactionOutcome, migration::{StorageIterator, StorageKeyIterator, put_storage_value}},
+	traits::{Get, Currency, ReservableCurrency, ExistenceRequirement, EnsureOrigin}, weights::{Pays, Weight},
+	unsigned::ValidateUnsigned,
+};
+use frame_system::{ensure_none, ensure_root, ensure_signed};
 
+mod bounded_vec;
+mod benchmarking;
+mod migrations;
+pub mod weights;
 
 #[cfg(test)]
 mod mock;
@@ -18,158 +35,5163 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub use bounded_vec::BoundedVec;
+pub use weights::WeightInfo;
+
 pub type TweetId = u128;
 
+/// ISO 639-1 code of the language of a tweet, two lowercase ASCII letters.
+pub type LanguageCode = [u8; 2];
+
+/// Index of a note among the notes on a tweet.
+pub type NoteId = u32;
+
 /// Tweet
+///
+/// Other pallets read tweets through the accessors, for instance to reward authors for the
+/// comments they receive:
+///
+/// ```
+/// use pallet_twitter::{Module, Trait, TweetId};
+///
+/// fn comment_authors<T: Trait>(tweet_id: TweetId) -> Vec<(T::AccountId, TweetId)> {
+///     Module::<T>::comments_of(tweet_id)
+///         .into_iter()
+///         .filter_map(Module::<T>::tweets)
+///         .filter(|comment| !comment.text().is_empty())
+///         .map(|comment| (comment.author().clone(), comment.id()))
+///         .collect()
+/// }
+///
+/// let tweet = pallet_twitter::Tweet::<u64, u64, Vec<u8>, Vec<Vec<u8>>>::new(0, 1, b"hello".to_vec(), 5);
+/// assert_eq!(tweet.author(), &1);
+/// assert_eq!(tweet.text(), b"hello");
+/// assert_eq!(tweet.created_at(), 5);
+/// assert_eq!(tweet.quoted(), None);
+/// ```
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub struct Tweet<AccountId, BlockNumber> {
+pub struct Tweet<AccountId, BlockNumber, Text, Media> {
 	/// Identifier of the retweet.
 	id: TweetId,
 	/// Created at, by block number.
 	create_at: BlockNumber,
 	/// Identifier of the original tweet.
 	quote_tweet_id: Option<TweetId>,
+	/// Identifier of the tweet this one is a comment on.
+	parent_id: Option<TweetId>,
+	/// Identifier of the previous tweet of the thread this one continues.
+	thread_prev: Option<TweetId>,
 	/// Text of the retweet.
-	text: Vec<u8>,
-	/// The comments of the retweet.
-	comments: Vec<TweetId>,
+	text: Text,
 	/// Author of the retweet.
 	author: AccountId,
+	/// Whether other accounts can comment on the tweet.
+	comments_enabled: bool,
+	/// Who can interact with the tweet.
+	visibility: Visibility,
+	/// Content identifiers of the attached media.
+	media: Media,
+	/// Language of the text, if the author gave it.
+	lang: Option<LanguageCode>,
+	/// Who can comment on the tweet.
+	reply_policy: ReplyPolicy,
+	/// Whether the tweet is hidden until its reports are resolved, after `AutoHideThreshold`
+	/// of them counted.
+	hidden: bool,
+	/// Identifier of the tweet that started the conversation this one belongs to, its own for
+	/// tweets starting one.
+	conversation_id: TweetId,
+	/// Number of times the text was edited, the previous texts are kept in `EditHistory`.
+	revision: u32,
+	/// Community the tweet was posted to, listed in its `CommunityTweets`.
+	community: Option<CommunityId>,
+}
+
+impl<AccountId, BlockNumber: Copy, Text: AsRef<[u8]>, Media> Tweet<AccountId, BlockNumber, Text, Media> {
+	/// A public tweet of `author` that quotes, continues and comments on nothing, accepts
+	/// comments and has no media.
+	///
+	/// Meant for test fixtures of pallets consuming tweets, the tweets of this pallet are only
+	/// created by its calls.
+	pub fn new(id: TweetId, author: AccountId, text: Text, created_at: BlockNumber) -> Self where Media: Default {
+		Tweet {
+			id,
+			create_at: created_at,
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text,
+			author,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: Default::default(),
+			lang: None,
+			reply_policy: ReplyPolicy::Everyone,
+			hidden: false,
+			conversation_id: id,
+			revision: 0,
+			community: None,
+		}
+	}
+
+	pub fn id(&self) -> TweetId {
+		self.id
+	}
+
+	pub fn author(&self) -> &AccountId {
+		&self.author
+	}
+
+	/// Text of the tweet, empty for reposts and tombstones.
+	pub fn text(&self) -> &[u8] {
+		self.text.as_ref()
+	}
+
+	/// Block at which the tweet was posted.
+	pub fn created_at(&self) -> BlockNumber {
+		self.create_at
+	}
+
+	/// Tweet this one retweets or reposts.
+	pub fn quoted(&self) -> Option<TweetId> {
+		self.quote_tweet_id
+	}
+
+	/// Tweet this one is a comment on. The comments of a tweet are listed by
+	/// `Module::comments_of`.
+	pub fn parent(&self) -> Option<TweetId> {
+		self.parent_id
+	}
+
+	/// Previous tweet of the thread this one continues.
+	pub fn thread_prev(&self) -> Option<TweetId> {
+		self.thread_prev
+	}
+
+	pub fn comments_enabled(&self) -> bool {
+		self.comments_enabled
+	}
+
+	pub fn visibility(&self) -> Visibility {
+		self.visibility
+	}
+
+	/// Content identifiers of the attached media.
+	pub fn media(&self) -> &Media {
+		&self.media
+	}
+
+	/// Language of the text, if the author gave it.
+	pub fn lang(&self) -> Option<LanguageCode> {
+		self.lang
+	}
+
+	pub fn reply_policy(&self) -> ReplyPolicy {
+		self.reply_policy
+	}
+
+	/// Whether likes, retweets and comments are blocked until the reports against the tweet
+	/// are resolved.
+	pub fn is_hidden(&self) -> bool {
+		self.hidden
+	}
+
+	/// Tweet that started the conversation this one belongs to. The tweets of a conversation
+	/// are listed by `Module::conversation`.
+	pub fn conversation_id(&self) -> TweetId {
+		self.conversation_id
+	}
+
+	/// Number of times the text was edited. The previous texts are listed by
+	/// `Module::edit_history`.
+	pub fn revision(&self) -> u32 {
+		self.revision
+	}
+
+	/// Community the tweet was posted to. The tweets of a community are listed by
+	/// `Module::community_feed`.
+	pub fn community(&self) -> Option<CommunityId> {
+		self.community
+	}
+}
+
+/// Who can comment on, like and retweet a tweet.
+///
+/// Tweets are stored on chain and can be read by anyone either way.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Visibility {
+	/// Every account.
+	Public,
+	/// The author, the accounts following it and its running paid subscribers.
+	FollowersOnly,
+}
+
+/// Who can comment on a tweet, on top of what its `Visibility` allows, from the least to the
+/// most restrictive. The author can always comment.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum ReplyPolicy {
+	/// Every account.
+	Everyone,
+	/// The accounts following the author, its running paid subscribers and the accounts
+	/// mentioned by the tweet.
+	Followers,
+	/// The accounts mentioned by the tweet.
+	Mentioned,
+}
+
+/// Defaults an account applies to all of its future tweets and to the interactions with them.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct AccountSettings {
+	/// Least restrictive reply policy of the tweets of the account. Tweets posted with a less
+	/// restrictive one get this one instead.
+	pub reply_policy: ReplyPolicy,
+	/// Whether mentions by accounts the account is not followed by notify it.
+	pub mentions_from_non_followers: bool,
+	/// Whether the tweets of the account can be tipped.
+	pub accepts_tips: bool,
+}
+
+impl Default for AccountSettings {
+	fn default() -> Self {
+		AccountSettings {
+			reply_policy: ReplyPolicy::Everyone,
+			mentions_from_non_followers: true,
+			accepts_tips: true,
+		}
+	}
+}
+
+/// Data owned by an account, as returned page by page by `Module::export_account`.
+///
+/// The tweets are paged, the rest is only on the first page: later pages have empty lists and
+/// no profile, handle or settings.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct AccountExport<AccountId, Tweet> {
+	/// Tweets posted by the account that are not deleted, oldest first.
+	pub tweets: Vec<Tweet>,
+	/// Ids of the tweets liked by the account.
+	pub liked: Vec<TweetId>,
+	/// Ids of the tweets bookmarked by the account.
+	pub bookmarked: Vec<TweetId>,
+	/// Accounts followed by the account.
+	pub following: Vec<AccountId>,
+	pub profile: Option<Profile>,
+	pub handle: Option<Handle>,
+	pub settings: Option<AccountSettings>,
+	/// Cursor of the next page, `None` on the last page.
+	pub next_cursor: Option<u64>,
+}
+
+/// How a tweet was created, as reported to `OnTweetCreated`.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum TweetKind {
+	/// A tweet of its own, including thread segments, ephemeral, scheduled and poll tweets.
+	Tweet,
+	/// A retweet or a repost of another tweet.
+	Retweet,
+	/// A comment on another tweet.
+	Comment,
+}
+
+/// Hook for other pallets to react to new tweets.
+pub trait OnTweetCreated<AccountId> {
+	/// Called once tweet `id` of `author` is stored. It can neither fail nor undo the tweet.
+	fn on_tweet(author: &AccountId, id: TweetId, kind: TweetKind);
+}
+
+impl<AccountId> OnTweetCreated<AccountId> for () {
+	fn on_tweet(_: &AccountId, _: TweetId, _: TweetKind) {}
+}
+
+/// Source of the identities registered on chain, such as `pallet_identity`, so that runtimes
+/// running one do not have to duplicate it in profiles. `()` knows no identity.
+///
+/// A runtime with `pallet_identity` can implement it as:
+///
+/// ```ignore
+/// use pallet_identity::{Data, Judgement};
+///
+/// pub struct Identities;
+/// impl pallet_twitter::IdentityProvider<AccountId> for Identities {
+///     fn display_name(who: &AccountId) -> Option<Vec<u8>> {
+///         match Identity::identity(who)?.info.display {
+///             Data::Raw(name) => Some(name),
+///             _ => None,
+///         }
+///     }
+///
+///     fn is_judged(who: &AccountId) -> bool {
+///         Identity::identity(who).map_or(false, |registration| registration.judgements.iter().any(
+///             |(_, judgement)| matches!(judgement, Judgement::Reasonable | Judgement::KnownGood)
+///         ))
+///     }
+/// }
+/// ```
+pub trait IdentityProvider<AccountId> {
+	/// Display name of the identity registered for `who`, if any.
+	fn display_name(who: &AccountId) -> Option<Vec<u8>>;
+
+	/// Whether a registrar judged the identity of `who` to be good.
+	fn is_judged(who: &AccountId) -> bool;
+}
+
+impl<AccountId> IdentityProvider<AccountId> for () {
+	fn display_name(_: &AccountId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn is_judged(_: &AccountId) -> bool {
+		false
+	}
+}
+
+/// What `TweetProvider` tells about a tweet.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct TweetInfo<AccountId, BlockNumber> {
+	pub id: TweetId,
+	pub author: AccountId,
+	/// Text of the tweet, empty for reposts and tombstones.
+	pub text: Vec<u8>,
+	/// Block at which the tweet was posted.
+	pub created_at: BlockNumber,
+	/// Tweet this one retweets or reposts.
+	pub quoted: Option<TweetId>,
+	/// Tweet this one is a comment on.
+	pub parent: Option<TweetId>,
+	/// Tweet that started the conversation this one belongs to.
+	pub conversation_id: TweetId,
+	/// Whether the quoted tweet was edited or deleted since this one quoted it.
+	pub quote_edited: bool,
+}
+
+/// What a posted tweet leaves in the off-chain index, under `Module::offchain_key`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct IndexedTweet<AccountId, BlockNumber> {
+	pub author: AccountId,
+	/// Block at which the tweet was posted or last edited.
+	pub block: BlockNumber,
+	/// Text of the tweet, only the teaser of a gated tweet.
+	pub text: Vec<u8>,
+}
+
+/// Read access to the tweets for other pallets, without depending on the types and instances
+/// of this one. Implemented by `Module`, tombstones included.
+pub trait TweetProvider<AccountId, BlockNumber> {
+	/// The tweet `id`, if it exists.
+	fn get(id: TweetId) -> Option<TweetInfo<AccountId, BlockNumber>>;
+
+	/// Author of the tweet `id`, if it exists.
+	fn author_of(id: TweetId) -> Option<AccountId>;
+
+	/// Whether the tweet `id` exists.
+	fn exists(id: TweetId) -> bool;
+}
+
+parameter_types! {
+	pub const MaxDisplayNameLen: u32 = 64;
+	pub const MaxBioLen: u32 = 256;
+	pub const MaxAvatarCidLen: u32 = 64;
+	pub const MinHandleLen: u32 = 3;
+	pub const MaxHandleLen: u32 = 20;
+	pub const MaxCommunityNameLen: u32 = 32;
+	pub const MinPollOptions: u32 = 2;
+	pub const MaxPollOptions: u32 = 4;
+	pub const MaxPollOptionLen: u32 = 25;
+	pub const MaxPageLen: u32 = 100;
+	/// Maximum number of blocks `tweets_in_range` scans in a call.
+	pub const MaxRangeBlocks: u32 = 1_000;
+	pub const MaxTopEarners: u32 = 20;
+	/// Maximum number of zero-width characters in a row in a text, enough for emoji sequences.
+	pub const MaxZeroWidthRun: u32 = 1;
+	pub const MaxCidLen: u32 = 64;
+	pub const MaxMutedWordLen: u32 = 32;
+	pub const MaxFollowedHashtagLen: u32 = 64;
+	pub const MaxPreviewLen: u32 = 8;
+}
+
+/// IPFS content identifier, or other opaque hash, of a media attachment.
+pub type MediaCid = BoundedVec<u8, MaxCidLen>;
+
+/// Word an account keeps out of the comments on its tweets, in lowercase.
+pub type MutedWord = BoundedVec<u8, MaxMutedWordLen>;
+
+/// Unique handle of an account, without the leading `@`.
+pub type Handle = BoundedVec<u8, MaxHandleLen>;
+
+pub type CommunityId = u32;
+
+/// Name of a community, not necessarily unique.
+pub type CommunityName = BoundedVec<u8, MaxCommunityNameLen>;
+
+/// Group of accounts with its own feed, which only its members can post to.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Community<AccountId> {
+	pub id: CommunityId,
+	pub name: CommunityName,
+	/// Account that created the community, which can remove its members.
+	pub owner: AccountId,
+}
+
+/// Public profile of an account.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Profile {
+	/// Name shown instead of the account id.
+	pub display_name: BoundedVec<u8, MaxDisplayNameLen>,
+	/// Free-form description of the account.
+	pub bio: BoundedVec<u8, MaxBioLen>,
+	/// IPFS content identifier of the avatar image.
+	pub avatar_cid: Option<BoundedVec<u8, MaxAvatarCidLen>>,
+}
+
+/// Text of one of the options of a poll.
+pub type PollOption = BoundedVec<u8, MaxPollOptionLen>;
+
+/// Poll attached to a tweet.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Poll<BlockNumber> {
+	/// Options voters choose from, between `MinPollOptions` and `MaxPollOptions` of them.
+	pub options: BoundedVec<PollOption, MaxPollOptions>,
+	/// Number of votes for each option, in the order of `options`.
+	pub votes: Vec<u32>,
+	/// Last block at which votes are accepted.
+	pub close_at: BlockNumber,
+}
+
+/// Something another account did to an account or its tweets.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum Notification<AccountId> {
+	/// An account commented on a tweet of the notified one. [commenter, comment_id]
+	CommentedOn(AccountId, TweetId),
+	/// An account liked a tweet of the notified one. [liker, tweet_id]
+	Liked(AccountId, TweetId),
+	/// An account retweeted or reposted a tweet of the notified one. [author, retweet_id]
+	Retweeted(AccountId, TweetId),
+	/// An account mentioned the notified one in a tweet. [author, tweet_id]
+	Mentioned(AccountId, TweetId),
+	/// An account tweeted with a hashtag the notified one follows. [author, tweet_id]
+	HashtagTweeted(AccountId, TweetId),
+}
+
+/// Why an account reported a tweet.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Reason {
+	/// Unsolicited or repetitive content.
+	Spam,
+	/// Harassment or hateful content.
+	Abuse,
+	/// Content that is against the law.
+	Illegal,
+	/// Anything else.
+	Other,
+}
+
+/// How a moderator settles the reports against a tweet.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum ModerationAction {
+	/// Keep the tweet and drop the reports.
+	Dismiss,
+	/// Delete the tweet.
+	Remove,
+}
+
+/// How an account reacted to a tweet.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Reaction {
+	Like,
+	Love,
+	Laugh,
+	Sad,
+	Angry,
+}
+
+/// Annotation added to a tweet by another account, rated by the community.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Note<AccountId, Text> {
+	/// Account that wrote the note.
+	pub author: AccountId,
+	/// Text of the note, bounded like the text of a tweet.
+	pub text: Text,
+	/// Number of accounts that rated the note helpful.
+	pub up: u32,
+	/// Number of accounts that rated the note not helpful.
+	pub down: u32,
+	/// Whether the note was rated helpful enough to be shown with the tweet. A note stays shown
+	/// once it has been.
+	pub shown: bool,
+}
+
+/// Tweet an author signs for any account to submit with `submit_sponsored_tweet`, sparing the
+/// author the transaction fee.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct SponsoredTweet<AccountId, BlockNumber> {
+	/// Account the tweet is posted by, whose key signs `Module::sponsored_signing_payload`.
+	pub author: AccountId,
+	/// Text of the tweet.
+	pub text: Vec<u8>,
+	/// `SponsoredNonce` of the author, a payload is only accepted once.
+	pub nonce: u32,
+	/// Last block at which the payload is accepted.
+	pub valid_until: BlockNumber,
+}
+
+/// `InvalidTransaction::Custom` code of a sponsored tweet whose text `new_tweet` would reject.
+pub const INVALID_SPONSORED_TEXT: u8 = 0;
+
+/// `InvalidTransaction::Custom` code of a sponsored tweet its author cannot post in this block,
+/// because of `MaxTweetsPerAccount` or the rate limit.
+pub const SPONSORED_LIMIT_REACHED: u8 = 1;
+
+/// Storage layout versions of this pallet. `on_runtime_upgrade` brings the storage of older
+/// ones up to date with the functions of the `migrations` module.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+	/// Original layout.
+	V1,
+	/// `Tweet::parent_id` added.
+	V2,
+	/// `Tweet::text` bounded by `MaxTextLen`.
+	V3,
+	/// `Tweet::comments` moved to the `Comments` map.
+	V4,
+	/// `Accounts` moved to the `AccountTweets` map.
+	V5,
+	/// `Tweet::comments_enabled` added.
+	V6,
+	/// `CommentCount` renamed to `NextCommentIndex`, `CommentCount` and `RetweetCount` count
+	/// existing comments and retweets.
+	V7,
+	/// `Tweet::visibility` added.
+	V8,
+	/// `TweetCount` and `TweetCountOf` count existing tweets.
+	V9,
+	/// `Tweet::media` added.
+	V10,
+	/// `Tweet::thread_prev` added.
+	V11,
+	/// `Notifications` holds `Notification`s rather than the ids of mentioning tweets.
+	V12,
+	/// `TopTweets` holds the most liked tweets.
+	V13,
+	/// `AccountLikes` and `AccountLikeCount` index the likes of each account.
+	V14,
+	/// `Tweet::lang` added.
+	V15,
+	/// `TweetsByBlock` indexes the existing tweets.
+	V16,
+	/// `Tweet::reply_policy` added.
+	V17,
+	/// `Tweet::hidden` added.
+	V18,
+	/// `FirstTweetAt` records when the existing accounts first tweeted.
+	V19,
+	/// `LikePreview` and `RetweetPreview` hold some of the existing likers and retweeters.
+	V20,
+	/// `Tweet::conversation_id` added and indexed in `ConversationTweets`.
+	V21,
+	/// `Tweet::revision` added.
+	V22,
+	/// `Tweet::community` added.
+	V23,
+	/// `NextTweetId` is past the ids reserved for system tweets.
+	V24,
+	/// `CommentIndex` added, `Comments` entries of removed comments dropped.
+	V25,
+	/// `Followers` added.
+	V26,
+	/// `ExpiryCursor` starts at the block of the upgrade.
+	V27,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
 }
 
-pub type TweetOf<T> = Tweet<<T as frame_system::Trait>::AccountId, <T as frame_system::Trait>::BlockNumber>;
+pub type BalanceOf<T, I = DefaultInstance> = <<T as Trait<I>>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub type TextOf<T, I = DefaultInstance> = BoundedVec<u8, <T as Trait<I>>::MaxTextBytes>;
+
+pub type NotificationsOf<T, I = DefaultInstance> = BoundedVec<Notification<<T as frame_system::Trait>::AccountId>, <T as Trait<I>>::MaxNotifications>;
+
+/// Some of the accounts that liked or retweeted a tweet.
+pub type PreviewOf<T> = BoundedVec<<T as frame_system::Trait>::AccountId, MaxPreviewLen>;
+
+/// The ids of the most recent tweets, oldest first.
+pub type RecentTweetsOf<T, I = DefaultInstance> = BoundedVec<TweetId, <T as Trait<I>>::RecentBufferSize>;
+
+pub type MutedWordsOf<T, I = DefaultInstance> = BoundedVec<MutedWord, <T as Trait<I>>::MaxMutedWords>;
+
+pub type MediaOf<T, I = DefaultInstance> = BoundedVec<MediaCid, <T as Trait<I>>::MaxAttachments>;
+
+pub type LeaderboardOf<T, I = DefaultInstance> = BoundedVec<(TweetId, u32), <T as Trait<I>>::MaxLeaderboardSize>;
+
+pub type EarnersOf<T, I = DefaultInstance> = BoundedVec<(<T as frame_system::Trait>::AccountId, BalanceOf<T, I>), MaxTopEarners>;
+
+pub type NoteOf<T, I = DefaultInstance> = Note<<T as frame_system::Trait>::AccountId, TextOf<T, I>>;
+
+pub type SponsoredTweetOf<T> = SponsoredTweet<<T as frame_system::Trait>::AccountId, <T as frame_system::Trait>::BlockNumber>;
+
+pub type TweetOf<T, I = DefaultInstance> = Tweet<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+pub type AccountExportOf<T, I = DefaultInstance> = AccountExport<<T as frame_system::Trait>::AccountId, TweetOf<T, I>>;
+
+/// Window and `blake2_256(author, text)` a tweet is recorded under in `RecentContent`.
+type ContentKeyOf<T> = (<T as frame_system::Trait>::BlockNumber, [u8; 32]);
+
+/// A tweet waiting to be published: its author, text and reserved deposit.
+pub type ScheduledTweetOf<T, I = DefaultInstance> = (<T as frame_system::Trait>::AccountId, TextOf<T, I>, BalanceOf<T, I>);
+
+/// A commitment to an anonymous tweet: the account that made it, the block it was made at and
+/// the deposit reserved for it.
+pub type CommitmentOf<T, I = DefaultInstance> = (<T as frame_system::Trait>::AccountId, <T as frame_system::Trait>::BlockNumber, BalanceOf<T, I>);
 
-pub trait Trait: frame_system::Trait {
+pub trait Trait<I: Instance = DefaultInstance>: frame_system::Trait {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Maximum length of the text of a tweet, in characters, until root changes `TextLimit`.
+	type MaxTextLen: Get<u32>;
+
+	/// Maximum length of the text of a tweet, in bytes. A character takes up to 4 bytes.
+	type MaxTextBytes: Get<u32>;
+
+	/// Number of blocks after creation during which a tweet can still be edited.
+	type EditWindow: Get<Self::BlockNumber>;
+
+	/// Maximum number of times a tweet can be edited, each edit keeps the replaced text in
+	/// `EditHistory`.
+	type MaxEdits: Get<u32>;
+
+	/// Maximum number of distinct hashtags in the text of a tweet.
+	type MaxHashtagsPerTweet: Get<u32>;
+
+	/// Maximum number of accounts a tweet can mention.
+	type MaxMentions: Get<u32>;
+
+	/// Maximum number of media attached to a tweet.
+	type MaxAttachments: Get<u32>;
+
+	/// Length of the windows in which `MaxTweetsPerPeriod` applies, in blocks.
+	type RateLimitPeriod: Get<Self::BlockNumber>;
+
+	/// Maximum number of tweets an account can post per period, counting retweets, reposts and
+	/// comments.
+	type MaxTweetsPerPeriod: Get<u32>;
+
+	/// Number of tweets, retweets and comments an account can post without paying the
+	/// transaction fee every `DayLength` blocks.
+	type FreeTweetsPerDay: Get<u32>;
+
+	/// Length of the days `FreeTweetsPerDay` applies to, in blocks.
+	type DayLength: Get<Self::BlockNumber>;
+
+	/// Free balance an account needs to post its `FreeTweetsPerDay` without fees, once the
+	/// deposit of the tweet is reserved.
+	type MinBalanceForFreeTweets: Get<BalanceOf<Self, I>>;
+
+	/// Maximum number of sponsored tweets included in a block, the others wait for the next
+	/// ones.
+	type MaxSponsoredPerBlock: Get<u32>;
+
+	/// Signature of the author over the `Module::sponsored_signing_payload` of a sponsored tweet.
+	type Signature: Verify<Signer = Self::Signer> + Parameter;
+
+	/// Key a `Signature` is checked against, identifying the author.
+	type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// Maximum number of existing tweets of an account, counting retweets, reposts and comments.
+	/// Deleting a tweet makes room for a new one. `AnonymousAccount` is exempt, its tweets are
+	/// bounded by `MaxCommitsPerBlock` instead.
+	type MaxTweetsPerAccount: Get<u32>;
+
+	/// Maximum number of notifications kept for an account, older ones are dropped first.
+	type MaxNotifications: Get<u32>;
+
+	/// Number of the most recent tweets kept in `RecentTweets`, older ones are dropped first.
+	type RecentBufferSize: Get<u32>;
+
+	/// Whether comments are left out of `RecentTweets`.
+	type RecentSkipsComments: Get<bool>;
+
+	/// Maximum number of accounts an account can block.
+	type MaxBlocked: Get<u32>;
+
+	/// Maximum number of words an account can mute.
+	type MaxMutedWords: Get<u32>;
+
+	/// Maximum number of hashtags an account can follow.
+	type MaxFollowedHashtags: Get<u32>;
+
+	/// Maximum number of hashtag followers notified of a new tweet. The others can still find
+	/// it by its hashtags.
+	type MaxHashtagFanout: Get<u32>;
+
+	/// Maximum number of accounts allowed to tweet on behalf of an account.
+	type MaxDelegates: Get<u32>;
+
+	/// Maximum number of tweets an account can bookmark.
+	type MaxBookmarks: Get<u32>;
+
+	/// Maximum number of tweets posted at once as a thread.
+	type MaxThreadLen: Get<u32>;
+
+	/// Maximum number of ephemeral tweets removed in a block, the others wait for the next ones.
+	type MaxExpirationsPerBlock: Get<u32>;
+
+	/// Maximum number of tweets that can be scheduled for publication at a block.
+	type MaxScheduledPerBlock: Get<u32>;
+
+	/// Maximum length of a chain of retweets and reposts, each quoting the next one, down to a
+	/// tweet quoting nothing.
+	type MaxQuoteDepth: Get<u32>;
+
+	/// Number of most liked tweets kept in `TopTweets`.
+	type MaxLeaderboardSize: Get<u32>;
+
+	/// Length in blocks of the windows over which hashtag usage is counted. Must not be zero.
+	type TrendingWindow: Get<Self::BlockNumber>;
+
+	/// Number of past windows whose hashtag usage is kept besides the current one.
+	type KeepWindows: Get<u32>;
+
+	/// Maximum number of hashtag usage counts of old windows removed in a block, the others
+	/// wait for the next ones.
+	type MaxHashtagPrunesPerBlock: Get<u32>;
+
+	/// Length in blocks of the windows over which `new_tweet` rejects an author posting the
+	/// same text again. A text can be posted again once a whole window passed. Zero disables the
+	/// check.
+	type DedupWindow: Get<Self::BlockNumber>;
+
+	/// Maximum number of content hashes of old windows removed in a block, the others wait for
+	/// the next ones.
+	type MaxContentPrunesPerBlock: Get<u32>;
+
+	/// Maximum number of comments on a tweet, deleted ones included.
+	type MaxCommentsPerTweet: Get<u32>;
+
+	/// Maximum number of notes on a tweet.
+	type MaxNotesPerTweet: Get<u32>;
+
+	/// Maximum number of communities that can be created.
+	type MaxCommunities: Get<u32>;
+
+	/// Maximum number of members of a community, its owner included.
+	type MaxMembersTracked: Get<u32>;
+
+	/// Number of blocks after a commitment during which its anonymous tweet can be revealed.
+	type RevealWindow: Get<Self::BlockNumber>;
+
+	/// Maximum number of commitments to anonymous tweets made in a block.
+	type MaxCommitsPerBlock: Get<u32>;
+
+	/// Account the revealed anonymous tweets are authored by. Nobody should hold its key.
+	type AnonymousAccount: Get<Self::AccountId>;
+
+	/// Id of the first tweet posted by an account. The ids below it are reserved for system
+	/// tweets, created at genesis or by `force_create_tweet`.
+	type FirstUserTweetId: Get<TweetId>;
+
+	/// Minimum number of ratings before a note can be shown.
+	type MinNoteRatings: Get<u32>;
+
+	/// Share of helpful ratings a note needs to be shown.
+	type NoteShowThreshold: Get<Perbill>;
+
+	/// The currency in which tweet deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// Amount reserved from the author for every tweet, returned when it is deleted.
+	type TweetDepositBase: Get<BalanceOf<Self, I>>;
+
+	/// Amount reserved from the author for every byte of text and media a tweet stores, on top
+	/// of `TweetDepositBase`.
+	type TweetDepositPerByte: Get<BalanceOf<Self, I>>;
+
+	/// Amount reserved for having a profile, returned when it is cleared.
+	type ProfileDeposit: Get<BalanceOf<Self, I>>;
+
+	/// Amount reserved for holding a handle, returned when it is released.
+	type HandleDeposit: Get<BalanceOf<Self, I>>;
+
+	/// Amount reserved for a premium subscription, returned when it is cancelled.
+	type PremiumFee: Get<BalanceOf<Self, I>>;
+
+	/// Number of blocks a premium subscription lasts before it has to be renewed.
+	type PremiumPeriod: Get<Self::BlockNumber>;
+
+	/// Number of blocks a paid subscription to an author lasts before it has to be renewed.
+	type SubscriptionPeriod: Get<Self::BlockNumber>;
+
+	/// Amount reserved for a commitment to an anonymous tweet, returned when the tweet is
+	/// revealed and forfeited when the commitment expires.
+	type CommitDeposit: Get<BalanceOf<Self, I>>;
+
+	/// Maximum length of the text of a tweet of a premium account, in characters. `TextLimit`
+	/// applies instead if it is more, the text is still bounded by `MaxTextBytes`.
+	type MaxTextLenPremium: Get<u32>;
+
+	/// Number of counted reports that hide a tweet until `ModerationOrigin` resolves them, zero
+	/// to never hide tweets.
+	type AutoHideThreshold: Get<u32>;
+
+	/// Number of blocks an account must have tweeted for before its reports count toward
+	/// `AutoHideThreshold`, zero to count the reports of every account but the author.
+	type MinReporterAge: Get<Self::BlockNumber>;
+
+	/// Origin allowed to resolve reports against tweets.
+	type ModerationOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin allowed to take down any tweet.
+	type ForceRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin allowed to set the `GlobalAnnouncement`.
+	type AnnouncementOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Whether the deposit of a taken down tweet is slashed rather than returned.
+	type SlashCensoredDeposit: Get<bool>;
+
+	/// Whether a retweet joins the conversation of the tweet it quotes rather than starting its
+	/// own. Reposts always start their own.
+	type QuotesJoinConversation: Get<bool>;
+
+	/// Origin allowed to verify accounts.
+	type VerifyOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin allowed to prune old tweets.
+	type PruneOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Called whenever a tweet is created.
+	type TweetHooks: OnTweetCreated<Self::AccountId>;
+
+	/// Identities registered on chain, `()` if the runtime has none.
+	type Identity: IdentityProvider<Self::AccountId>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
 }
 
 
 decl_storage! {
-	trait Store for Module<T: Trait> as TemplateModule {
-		Accounts get(fn accounts): map hasher(blake2_128_concat) T::AccountId => Vec<TweetId>;
-		Tweets get(fn tweets): map hasher(blake2_128_concat) TweetId => Option<TweetOf<T>>;
-		NextTweetId get(fn next_tweet_id): TweetId;
+	trait Store for Module<T: Trait<I>, I: Instance = DefaultInstance> as Twitter {
+		/// Tweets posted by an account, by `(author, index)`.
+		AccountTweets get(fn account_tweets): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u64 => Option<TweetId>;
+		/// Number of tweets ever posted by an account, also the index of the next one.
+		AccountTweetCount get(fn account_tweet_count): map hasher(blake2_128_concat) T::AccountId => u64;
+		/// Block at which an account posted its first tweet.
+		FirstTweetAt get(fn first_tweet_at): map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+		Tweets get(fn tweets): map hasher(blake2_128_concat) TweetId => Option<TweetOf<T, I>>;
+		NextTweetId get(fn next_tweet_id) build(|_| T::FirstUserTweetId::get()): TweetId;
+		/// Number of stored tweets.
+		TweetCount get(fn tweet_count): u128;
+		/// Number of stored tweets of an account.
+		TweetCountOf get(fn tweet_count_of): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Start of the current rate limit window of an account, and the number of tweets it
+		/// posted since.
+		TweetRate get(fn tweet_rate): map hasher(blake2_128_concat) T::AccountId => (T::BlockNumber, u32);
+		/// Last day, counted in `DayLength` blocks, an account posted a tweet without fees in, and
+		/// the number of such tweets it posted that day.
+		FreeTweetsUsed get(fn free_tweets_used): map hasher(blake2_128_concat) T::AccountId => (T::BlockNumber, u32);
+		/// Nonce the next sponsored tweet of an account must carry.
+		SponsoredNonce get(fn sponsored_nonce): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Number of sponsored tweets included in the current block, cleared at its end.
+		SponsoredInBlock get(fn sponsored_in_block): u32;
+		/// Ephemeral tweets by the block at which they expire, by `(block, tweet)`.
+		ExpiringAt: double_map hasher(twox_64_concat) T::BlockNumber, hasher(blake2_128_concat) TweetId => Option<()>;
+		/// Block at which an ephemeral tweet expires.
+		TweetExpiry get(fn tweet_expiry): map hasher(blake2_128_concat) TweetId => Option<T::BlockNumber>;
+		/// First block whose expired tweets have not all been removed yet.
+		ExpiryCursor build(|_| T::BlockNumber::one()): T::BlockNumber;
+		/// Tweets by the block they were created in, by `(block, tweet)`.
+		TweetsByBlock: double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) TweetId => Option<()>;
+		/// First block whose tweets have not all been pruned yet.
+		PruneCursor: T::BlockNumber;
+		/// Last tweet of the `PruneCursor` block, in the order of `TweetsByBlock`, that pruning
+		/// kept, `None` if it kept none of that block yet.
+		PruneKept: Option<TweetId>;
+		/// The ids of the most recent tweets, oldest first. Tweets deleted since are only
+		/// filtered out when read.
+		RecentTweets get(fn recent_tweets): RecentTweetsOf<T, I>;
+		/// Tweets waiting to be published, by `(block, index)`, with their author and the
+		/// deposit reserved for them.
+		ScheduledTweets get(fn scheduled_tweets): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) u32 => Option<ScheduledTweetOf<T, I>>;
+		/// Number of tweets ever scheduled at a block, also the index of the next one.
+		ScheduledCount get(fn scheduled_count): map hasher(twox_64_concat) T::BlockNumber => u32;
+		/// Deposit reserved from the author of a tweet, for the tweet and its `EditHistory`.
+		TweetDeposits get(fn tweet_deposit): map hasher(blake2_128_concat) TweetId => BalanceOf<T, I>;
+		/// Texts replaced by the edits of a tweet, by `(tweet, revision)`, with the block of the
+		/// edit. Revision `r` is the text the tweet had before its edit number `r + 1`.
+		EditHistory: double_map hasher(blake2_128_concat) TweetId, hasher(twox_64_concat) u32 => Option<(T::BlockNumber, TextOf<T, I>)>;
+		/// Total amount tipped to the author of a tweet.
+		TipTotal get(fn tip_total): map hasher(blake2_128_concat) TweetId => BalanceOf<T, I>;
+		/// Total amount tipped to an account over all of its tweets, kept when they are removed.
+		AuthorEarnings get(fn earnings_of): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T, I>;
+		/// Full text of a gated tweet, whose own text is the teaser shown to everyone.
+		GatedContent get(fn gated_content): map hasher(blake2_128_concat) TweetId => Option<TextOf<T, I>>;
+		/// Price paid to the author of a gated tweet to unlock it.
+		GatePrice get(fn gate_price): map hasher(blake2_128_concat) TweetId => Option<BalanceOf<T, I>>;
+		/// Accounts that unlocked a gated tweet, by `(tweet, account)`.
+		Unlocked get(fn unlocked): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		/// Owner of a tweet its author minted as a collectible. The author of the tweet stays the
+		/// same whoever owns it.
+		TweetOwner get(fn tweet_owner): map hasher(blake2_128_concat) TweetId => Option<T::AccountId>;
+
+		Likes get(fn likes): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => bool;
+		LikeCount get(fn like_count): map hasher(blake2_128_concat) TweetId => u32;
+		/// Tweets liked by an account, by `(who, tweet)`. Likes of deleted tweets are only dropped
+		/// from here when the account takes them back or is purged.
+		AccountLikes get(fn account_likes): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) TweetId => Option<()>;
+		/// Number of entries of an account in `AccountLikes`, including the likes of deleted
+		/// tweets not yet dropped from there. It is not the number of tweets the account likes.
+		AccountLikeCount: map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Up to `MaxPreviewLen` accounts liking a tweet, in the order they were added. An account
+		/// liking the tweet is added while there is room, one taking back its like is removed
+		/// without being replaced from `Likes`, so the preview can hold fewer accounts than like
+		/// the tweet until new likes fill it again.
+		LikePreview get(fn like_preview): map hasher(blake2_128_concat) TweetId => PreviewOf<T>;
+		/// The most liked tweets with their like counts, most liked first and older tweets first
+		/// among equally liked ones.
+		TopTweets get(fn top_tweets): LeaderboardOf<T, I>;
+		/// The `MaxTopEarners` accounts with the most `AuthorEarnings`, most first and the first
+		/// to reach an amount first among equal ones.
+		TopEarners get(fn top_earners): EarnersOf<T, I>;
+
+		/// Tweets saved by an account, by `(account, tweet)`.
+		Bookmarks get(fn bookmarks): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) TweetId => Option<()>;
+		BookmarkCount get(fn bookmark_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// Tweet an account shows at the top of its profile.
+		PinnedTweet get(fn pinned_tweet): map hasher(blake2_128_concat) T::AccountId => Option<TweetId>;
+
+		/// `(follower, target)` pairs.
+		Following get(fn following): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		/// `(target, follower)` pairs, the same as `Following` the other way round so that the
+		/// followers of an account can be listed, see `followers_page`.
+		Followers get(fn follower): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		FollowerCount get(fn follower_count): map hasher(blake2_128_concat) T::AccountId => u32;
+		FollowingCount get(fn following_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// `(blocker, blocked)` pairs.
+		Blocked get(fn blocked): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		BlockedCount get(fn blocked_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// Reaction of an account to a tweet, by `(tweet, account)`. Independent of `Likes`.
+		Reactions get(fn reactions): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<Reaction>;
+		/// Number of reactions of each kind to a tweet, indexed by `Reaction as usize`.
+		ReactionCounts get(fn reaction_counts): map hasher(blake2_128_concat) TweetId => [u32; 5];
+
+		/// Accounts allowed to tweet on behalf of an account, by `(owner, delegate)`.
+		Delegates get(fn delegates): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		DelegateCount get(fn delegate_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// Words an account keeps out of the comments on its tweets.
+		MutedWords get(fn muted_words): map hasher(blake2_128_concat) T::AccountId => MutedWordsOf<T, I>;
+
+		/// Defaults an account set for its tweets, `AccountSettings::default()` if it set none.
+		Settings get(fn settings): map hasher(blake2_128_concat) T::AccountId => AccountSettings;
+
+		/// Repost created by an account for a tweet, by `(original, reposter)`.
+		Reposts get(fn reposts): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<TweetId>;
+		RepostCount get(fn repost_count): map hasher(blake2_128_concat) TweetId => u32;
+		/// `blake2_256` of the text of the quoted tweet when a retweet was posted.
+		QuoteSnapshots get(fn quote_snapshot): map hasher(blake2_128_concat) TweetId => Option<[u8; 32]>;
+		/// `(original, retweeter)` pairs, an account quotes a tweet at most once.
+		Retweeted get(fn retweeted): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		/// Number of existing retweets and reposts of a tweet.
+		RetweetCount get(fn retweet_count): map hasher(blake2_128_concat) TweetId => u32;
+		/// Up to `MaxPreviewLen` accounts retweeting or reposting a tweet, filled and emptied like
+		/// `LikePreview`.
+		RetweetPreview get(fn retweet_preview): map hasher(blake2_128_concat) TweetId => PreviewOf<T>;
+
+		/// Comments on a tweet, by `(tweet, index)`. Each comment gets the next index of its
+		/// parent and keeps it, removing a comment leaves a gap.
+		Comments get(fn comments): double_map hasher(blake2_128_concat) TweetId, hasher(twox_64_concat) u32 => Option<TweetId>;
+		/// Index of a comment in the `Comments` of its parent.
+		CommentIndex get(fn comment_index): map hasher(twox_64_concat) TweetId => Option<u32>;
+		/// Number of comments ever made on a tweet, also the index of the next one.
+		NextCommentIndex get(fn next_comment_index): map hasher(blake2_128_concat) TweetId => u32;
+		/// Number of existing comments on a tweet.
+		CommentCount get(fn comment_count): map hasher(blake2_128_concat) TweetId => u32;
+		/// Tweets by the conversation they belong to, by `(conversation, tweet)`.
+		ConversationTweets: double_map hasher(twox_64_concat) TweetId, hasher(twox_64_concat) TweetId => Option<()>;
+
+		/// Poll attached to a tweet.
+		Polls get(fn polls): map hasher(blake2_128_concat) TweetId => Option<Poll<T::BlockNumber>>;
+		/// Option an account voted for, by `(tweet, voter)`.
+		PollVotes get(fn poll_votes): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<u8>;
+		/// Final tallies of the closed polls, in the order of their options.
+		PollResults get(fn poll_results): map hasher(blake2_128_concat) TweetId => Option<Vec<u32>>;
+
+		/// Reports against a tweet, by `(tweet, reporter)`.
+		Reports get(fn reports): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<Reason>;
+		/// Number of reports against a tweet that count toward `AutoHideThreshold`.
+		ReportCount get(fn report_count): map hasher(blake2_128_concat) TweetId => u32;
+		/// Tweets taken down by `ForceRemoveOrigin`, kept with blank text.
+		Censored get(fn censored): map hasher(blake2_128_concat) TweetId => Option<()>;
+		/// Tweet `AnnouncementOrigin` wants every client to show, cleared when it is deleted or
+		/// taken down.
+		GlobalAnnouncement get(fn global_announcement): Option<TweetId>;
+		/// Maximum length of the text of a tweet, in characters, set at genesis or by root.
+		/// `MaxTextLen` applies until it is set.
+		TextLimit: Option<u32>;
+		/// Tweets deleted by their author, kept with blank text.
+		Deleted get(fn is_deleted): map hasher(blake2_128_concat) TweetId => bool;
+
+		/// Tweets by the hashtags in their text, by `(tag, tweet)`. Tags are lowercase and
+		/// stored without the leading `#`.
+		HashtagTweets: double_map hasher(blake2_128_concat) Vec<u8>, hasher(blake2_128_concat) TweetId => Option<()>;
+		/// Hashtags followed by an account, by `(account, tag)`, as stored in `HashtagTweets`.
+		FollowedHashtags: double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Vec<u8> => Option<()>;
+		/// Followers of a hashtag, by `(tag, account)`.
+		HashtagFollowers: double_map hasher(blake2_128_concat) Vec<u8>, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		/// Number of hashtags followed by an account.
+		FollowedHashtagCount get(fn followed_hashtag_count): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Tweets by the language they were tagged with, by `(lang, tweet)`.
+		TweetsByLang: double_map hasher(blake2_128_concat) LanguageCode, hasher(blake2_128_concat) TweetId => Option<()>;
+		/// Accounts mentioned by a tweet that does not let everyone reply, by `(tweet, account)`.
+		TweetMentions: double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		/// Number of tweets posted with a hashtag, by `(window, hashtag)`. Window `w` covers
+		/// blocks `w * TrendingWindow` to `(w + 1) * TrendingWindow - 1`.
+		HashtagUsage get(fn hashtag_usage): double_map hasher(twox_64_concat) T::BlockNumber, hasher(blake2_128_concat) Vec<u8> => u32;
+		/// First window whose hashtag usage has not all been removed yet.
+		HashtagUsageCursor: T::BlockNumber;
+
+		/// Tweets posted by `new_tweet`, by `(window, blake2_256(author, text))`. Only the current
+		/// and the previous `DedupWindow` are kept.
+		RecentContent get(fn recent_content): double_map hasher(twox_64_concat) T::BlockNumber, hasher(blake2_128_concat) [u8; 32] => Option<TweetId>;
+		/// First window whose content hashes have not all been removed yet.
+		RecentContentCursor: T::BlockNumber;
+
+		/// Tweets mentioning an account, oldest first.
+		Notifications get(fn notifications): map hasher(blake2_128_concat) T::AccountId => NotificationsOf<T, I>;
+		/// Number of notifications ever received by an account, also the index of the next one.
+		NotificationCount get(fn notification_count): map hasher(blake2_128_concat) T::AccountId => u64;
+		/// Index of the first notification of an account not marked as read.
+		NotificationsReadUpTo get(fn notifications_read_up_to): map hasher(blake2_128_concat) T::AccountId => u64;
+
+		Profiles get(fn profiles): map hasher(blake2_128_concat) T::AccountId => Option<Profile>;
+		/// Deposit reserved for the profile of an account.
+		ProfileDeposits get(fn profile_deposit): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T, I>;
+
+		/// Accounts whose identity was checked by `VerifyOrigin`.
+		Verified: map hasher(blake2_128_concat) T::AccountId => bool;
+
+		/// Owner of each claimed handle.
+		Handles get(fn handles): map hasher(blake2_128_concat) Handle => Option<T::AccountId>;
+		/// Handle claimed by an account.
+		HandleOf get(fn handle_of): map hasher(blake2_128_concat) T::AccountId => Option<Handle>;
+		/// Deposit reserved for the handle of an account.
+		HandleDeposits get(fn handle_deposit): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T, I>;
+
+		Communities get(fn communities): map hasher(twox_64_concat) CommunityId => Option<Community<T::AccountId>>;
+		/// Number of communities ever created, also the id of the next one.
+		NextCommunityId get(fn next_community_id): CommunityId;
+		/// Members of each community, by `(community, member)`.
+		Members get(fn members): double_map hasher(twox_64_concat) CommunityId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+		/// Number of members of a community.
+		MemberCount get(fn member_count): map hasher(twox_64_concat) CommunityId => u32;
+		/// Tweets posted to a community, by `(community, tweet)`.
+		CommunityTweets: double_map hasher(twox_64_concat) CommunityId, hasher(twox_64_concat) TweetId => Option<()>;
+
+		/// Commitments to anonymous tweets not revealed yet, by `blake2_256(text ++ salt)`.
+		Commitments get(fn commitments): map hasher(blake2_128_concat) [u8; 32] => Option<CommitmentOf<T, I>>;
+		/// Commitments by the block they expire at, by `(block, hash)`.
+		CommitmentsExpiringAt: double_map hasher(twox_64_concat) T::BlockNumber, hasher(blake2_128_concat) [u8; 32] => Option<()>;
+		/// Number of commitments made in a block.
+		CommitCount get(fn commit_count): map hasher(twox_64_concat) T::BlockNumber => u32;
+		/// Tweet revealed from a commitment.
+		RevealedTweet get(fn revealed_tweet): map hasher(blake2_128_concat) [u8; 32] => Option<TweetId>;
+		/// Commitment an anonymous tweet was revealed from and the block it was made at, which
+		/// the text is proven to predate.
+		TweetCommitment get(fn tweet_commitment): map hasher(blake2_128_concat) TweetId => Option<([u8; 32], T::BlockNumber)>;
+
+		/// Block at which the premium subscription of an account expires.
+		Premium get(fn premium): map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+		/// Fee reserved for the premium subscription of an account.
+		PremiumDeposits get(fn premium_deposit): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T, I>;
+		/// Price an author asks for a subscription of `SubscriptionPeriod` blocks.
+		SubscriptionPrice get(fn subscription_price): map hasher(blake2_128_concat) T::AccountId => Option<BalanceOf<T, I>>;
+		/// Block at which a paid subscription expires, by `(author, subscriber)`. Expired ones
+		/// are kept until renewed.
+		Subscriptions get(fn subscription): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+
+		/// Notes on each tweet, by index.
+		Notes get(fn notes): double_map hasher(blake2_128_concat) TweetId, hasher(twox_64_concat) NoteId => Option<NoteOf<T, I>>;
+		/// Number of notes added to each tweet, also the index of the next one.
+		NoteCount get(fn note_count): map hasher(blake2_128_concat) TweetId => u32;
+		/// Rating given to a note by an account, `true` for helpful, keyed by tweet so that the
+		/// ratings of a tweet can be removed together.
+		NoteRatings get(fn note_rating): double_map hasher(blake2_128_concat) TweetId, hasher(blake2_128_concat) (NoteId, T::AccountId) => Option<bool>;
+
+		/// Storage layout version, used for migrations.
+		StorageVersion get(fn storage_version) build(|_| Releases::V27): Releases;
+	}
+	add_extra_genesis {
+		/// Public tweets posted at genesis, by `(author, text)`. No deposit is reserved for them.
+		config(tweets): Vec<(T::AccountId, Vec<u8>)>;
+		/// Public tweets posted at genesis with ids reserved for system tweets, by
+		/// `(id, author, text)`.
+		config(system_tweets): Vec<(TweetId, T::AccountId, Vec<u8>)>;
+		/// `(follower, target)` pairs.
+		config(follows): Vec<(T::AccountId, T::AccountId)>;
+		/// Initial `TextLimit`, `MaxTextLen` if `None`.
+		config(text_limit): Option<u32>;
+		config(phantom): sp_std::marker::PhantomData<I>;
+		build(|config: &GenesisConfig<T, I>| {
+			if let Some(text_limit) = config.text_limit {
+				assert!((1..=T::MaxTextBytes::get()).contains(&text_limit), "genesis text limit is zero or more than MaxTextBytes");
+				<TextLimit<I>>::put(text_limit);
+			}
+			for (id, author, text) in &config.system_tweets {
+				assert!(*id < T::FirstUserTweetId::get(), "genesis system tweet id is not reserved");
+				assert!(!<Tweets<T, I>>::contains_key(id), "genesis system tweet id is duplicated");
+				<Module<T, I>>::insert_system_tweet(*id, author, text.clone()).expect("invalid genesis tweet text");
+				<Module<T, I>>::push_recent(*id);
+			}
+			for (author, text) in &config.tweets {
+				let id = <Module<T, I>>::alloc_id().expect("no tweet id left for genesis tweet");
+				<Module<T, I>>::insert_system_tweet(id, author, text.clone()).expect("invalid genesis tweet text");
+				<Module<T, I>>::push_recent(id);
+			}
+			for (who, target) in &config.follows {
+				assert!(who != target, "genesis account follows itself");
+				assert!(!<Following<T, I>>::contains_key(who, target), "genesis follow is duplicated");
+				<Following<T, I>>::insert(who, target, ());
+				<Followers<T, I>>::insert(target, who, ());
+				<FollowingCount<T, I>>::mutate(who, |count| *count = count.saturating_add(1));
+				<FollowerCount<T, I>>::mutate(target, |count| *count = count.saturating_add(1));
+			}
+		});
 	}
 }
 
 decl_event!(
-	pub enum Event<T> where Tweet = TweetOf<T> {
-		Tweeted(Tweet),
+	pub enum Event<T, I: Instance = DefaultInstance> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		Balance = BalanceOf<T, I>,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// An account posted a tweet. [author, tweet_id, lang, has_identity]
+		///
+		/// `has_identity` tells whether the author has a judged identity with `Identity`.
+		Tweeted(AccountId, TweetId, Option<LanguageCode>, bool),
+		/// An account quoted a tweet with its own text. [author, retweet_id, quoted_id, lang]
+		Retweeted(AccountId, TweetId, TweetId, Option<LanguageCode>),
+		/// Media were attached to a new tweet. [tweet_id, count]
+		MediaAttached(TweetId, u32),
+		/// An account posted a thread, first tweet first. [author, tweet_ids]
+		ThreadPosted(AccountId, Vec<TweetId>),
+		/// An account commented on a tweet. [author, comment_id, parent_id, lang]
+		Commented(AccountId, TweetId, TweetId, Option<LanguageCode>),
+		/// An account scheduled a tweet. [author, publish_at, index]
+		TweetScheduled(AccountId, BlockNumber, u32),
+		/// An account cancelled a scheduled tweet. [author, publish_at, index]
+		ScheduledTweetCancelled(AccountId, BlockNumber, u32),
+		/// An ephemeral tweet expired and was removed. [tweet_id]
+		TweetExpired(TweetId),
+		/// A tweet was deleted by its author. [tweet_id, author]
+		TweetDeleted(TweetId, AccountId),
+		/// A tweet was edited by its author. [tweet_id]
+		TweetEdited(TweetId),
+		/// An account liked a tweet. [who, tweet_id]
+		Liked(AccountId, TweetId),
+		/// An account took back its like. [who, tweet_id]
+		Unliked(AccountId, TweetId),
+		/// An account followed another one. [follower, target]
+		Followed(AccountId, AccountId),
+		/// An account stopped following another one. [follower, target]
+		Unfollowed(AccountId, AccountId),
+		/// An account reposted a tweet. [who, repost_id, original_id]
+		Reposted(AccountId, TweetId, TweetId),
+		/// A retweet was taken back. [retweet_id, original_id]
+		RetweetUndone(TweetId, TweetId),
+		/// An account was mentioned in a tweet. [who, tweet_id]
+		Mentioned(AccountId, TweetId),
+		/// An account cleared its notifications. [who]
+		NotificationsCleared(AccountId),
+		/// An account marked its notifications as read, up to and including an index. [who, index]
+		NotificationsRead(AccountId, u64),
+		/// The author of a tweet enabled or disabled comments on it. [tweet_id, enabled]
+		CommentsEnabledSet(TweetId, bool),
+		/// An account bookmarked a tweet. [who, tweet_id]
+		Bookmarked(AccountId, TweetId),
+		/// An account removed a bookmark. [who, tweet_id]
+		BookmarkRemoved(AccountId, TweetId),
+		/// An account pinned one of its tweets. [who, tweet_id]
+		TweetPinned(AccountId, TweetId),
+		/// An account unpinned its pinned tweet. [who]
+		TweetUnpinned(AccountId),
+		/// An account blocked another one. [blocker, blocked]
+		AccountBlocked(AccountId, AccountId),
+		/// An account unblocked another one. [blocker, blocked]
+		AccountUnblocked(AccountId, AccountId),
+		/// An account set or updated its profile. [who]
+		ProfileSet(AccountId),
+		/// An account removed its profile. [who]
+		ProfileCleared(AccountId),
+		/// An account claimed a handle. [who, handle]
+		HandleClaimed(AccountId, Handle),
+		/// An account released its handle. [who, handle]
+		HandleReleased(AccountId, Handle),
+		/// An account tipped the author of a tweet. [who, tweet_id, amount]
+		Tipped(AccountId, TweetId, Balance),
+		/// An account posted a tweet with a poll. [author, tweet_id]
+		PollCreated(AccountId, TweetId),
+		/// An account voted in a poll, or changed its vote. [who, tweet_id, option_index]
+		VoteCast(AccountId, TweetId, u8),
+		/// An account was verified or lost its verification. [who, verified]
+		VerificationChanged(AccountId, bool),
+		/// An account reported a tweet. [who, tweet_id, reason]
+		TweetReported(AccountId, TweetId, Reason),
+		/// A moderator dismissed the reports against a tweet. [tweet_id]
+		ReportsDismissed(TweetId),
+		/// A moderator removed a reported tweet. [tweet_id]
+		TweetRemovedByModeration(TweetId),
+		/// A tweet was taken down by governance. [tweet_id]
+		TweetCensored(TweetId),
+		/// Part of the data of an account was purged, some is left. [who, remaining]
+		PurgeProgress(AccountId, u32),
+		/// All the data of an account was purged. [who]
+		PurgeComplete(AccountId),
+		/// An account added a note to a tweet. [author, tweet_id, note_id]
+		NoteAdded(AccountId, TweetId, NoteId),
+		/// An account rated a note. [who, tweet_id, note_id, helpful]
+		NoteRated(AccountId, TweetId, NoteId, bool),
+		/// A note was rated helpful enough to be shown. [tweet_id, note_id]
+		NoteShown(TweetId, NoteId),
+		/// An account muted a word. [who, word]
+		WordMuted(AccountId, Vec<u8>),
+		/// An account unmuted a word. [who, word]
+		WordUnmuted(AccountId, Vec<u8>),
+		/// An account allowed another one to tweet on its behalf. [owner, delegate]
+		DelegateAdded(AccountId, AccountId),
+		/// An account no longer allows another one to tweet on its behalf. [owner, delegate]
+		DelegateRemoved(AccountId, AccountId),
+		/// A delegate posted a tweet on behalf of an account. [owner, delegate, tweet_id]
+		TweetedVia(AccountId, AccountId, TweetId),
+		/// An account reacted to a tweet, replacing any previous reaction. [who, tweet_id, reaction]
+		Reacted(AccountId, TweetId, Reaction),
+		/// An account took back its reaction to a tweet. [who, tweet_id, reaction]
+		ReactionRemoved(AccountId, TweetId, Reaction),
+		/// An account subscribed to premium or renewed its subscription. [who, expires_at]
+		PremiumSubscribed(AccountId, BlockNumber),
+		/// An account cancelled its premium subscription and got its fee back. [who, fee]
+		PremiumCancelled(AccountId, Balance),
+		/// An account followed a hashtag. [who, tag]
+		HashtagFollowed(AccountId, Vec<u8>),
+		/// An account unfollowed a hashtag. [who, tag]
+		HashtagUnfollowed(AccountId, Vec<u8>),
+		/// Old tweets were pruned. [pruned, skipped]
+		Pruned(u32, u32),
+		/// The author of a tweet changed who can reply to it. [tweet_id, policy]
+		ReplyPolicySet(TweetId, ReplyPolicy),
+		/// A tweet got `AutoHideThreshold` counted reports and was hidden. [tweet_id]
+		TweetAutoHidden(TweetId),
+		/// A poll was closed with its final tallies. [tweet_id, votes]
+		PollResult(TweetId, Vec<u32>),
+		/// The author of a tweet minted it as a collectible. [author, tweet_id]
+		TweetMinted(AccountId, TweetId),
+		/// A minted tweet changed owner. [tweet_id, from, to]
+		TweetTransferred(TweetId, AccountId, AccountId),
+		/// A tweet became the global announcement. [tweet_id]
+		AnnouncementSet(TweetId),
+		/// The global announcement was cleared.
+		AnnouncementCleared,
+		/// Root changed the maximum length of the text of a tweet. [text_limit]
+		TextLimitChanged(u32),
+		/// An account created a community. [owner, community_id]
+		CommunityCreated(AccountId, CommunityId),
+		/// An account joined a community. [who, community_id]
+		CommunityJoined(AccountId, CommunityId),
+		/// An account left a community, or was removed from it by its owner. [who, community_id]
+		CommunityLeft(AccountId, CommunityId),
+		/// An account posted a tweet to a community. [author, community_id, tweet_id]
+		CommunityPosted(AccountId, CommunityId, TweetId),
+		/// An account committed to an anonymous tweet. [who, hash]
+		TweetCommitted(AccountId, [u8; 32]),
+		/// An anonymous tweet was revealed from its commitment. [tweet_id, hash]
+		TweetRevealed(TweetId, [u8; 32]),
+		/// A commitment expired unrevealed and its deposit was forfeited. [hash]
+		CommitmentExpired([u8; 32]),
+		/// An account updated its settings. [who]
+		SettingsUpdated(AccountId),
+		/// A comment was removed from a tweet by its author or the author of the tweet.
+		/// [parent_id, comment_id, by]
+		CommentRemoved(TweetId, TweetId, AccountId),
+		/// Root created a tweet with a reserved id. [author, tweet_id]
+		SystemTweetCreated(AccountId, TweetId),
+		/// The follower count of an account reached a power of ten, from 10 up.
+		/// [who, follower_count]
+		FollowerMilestone(AccountId, u32),
+		/// A tweet was posted that must be unlocked to read in full. [author, tweet_id, price]
+		GatedTweetCreated(AccountId, TweetId, Balance),
+		/// An account unlocked a gated tweet, paying its author. [who, tweet_id, paid]
+		TweetUnlocked(AccountId, TweetId, Balance),
+		/// An author set or cleared its subscription price. [author, price]
+		SubscriptionPriceSet(AccountId, Option<Balance>),
+		/// An account paid for a subscription to an author. [subscriber, author, expires_at]
+		Subscribed(AccountId, AccountId, BlockNumber),
+		/// An account paid to extend its subscription to an author.
+		/// [subscriber, author, expires_at]
+		SubscriptionRenewed(AccountId, AccountId, BlockNumber),
 	}
 );
 
 // Errors inform users that something went wrong.
 decl_error! {
-	pub enum Error for Module<T: Trait> {
+	pub enum Error for Module<T: Trait<I>, I: Instance> {
 		/// Tweet not found.
 		TweetNotFound,
+		/// The tweet was deleted by its author.
+		TweetDeleted,
 		/// Text too long.
 		TweetTooLong,
+		/// Text is not valid UTF-8.
+		InvalidUtf8,
 		/// Run out of tweet id.
 		NoAvailableTweetId,
+		/// A thread has no tweet.
+		EmptyThread,
+		/// A thread has more than `MaxThreadLen` tweets.
+		ThreadTooLong,
+		/// Signer is not the author of the tweet.
+		NotAuthor,
+		/// The tweet is reserved to the followers of its author.
+		NotVisible,
+		/// The edit window of the tweet has passed.
+		EditWindowExpired,
+		/// The tweet is already liked by the signer.
+		AlreadyLiked,
+		/// The tweet is not liked by the signer.
+		NotLiked,
+		/// An account cannot follow itself.
+		CannotFollowSelf,
+		/// The signer already follows the target.
+		AlreadyFollowing,
+		/// The signer does not follow the target.
+		NotFollowing,
+		/// The signer already reposted the tweet.
+		AlreadyReposted,
+		/// The signer already quoted the tweet.
+		AlreadyRetweeted,
+		/// Quoting the tweet would make a chain of more than `MaxQuoteDepth` quotes.
+		QuoteChainTooDeep,
+		/// The tweet does not quote another tweet.
+		NotARetweet,
+		/// The tweet already has `MaxCommentsPerTweet` comments.
+		TooManyComments,
+		/// The signer can not afford the tweet deposit.
+		InsufficientBalance,
+		/// The text contains more than `MaxHashtagsPerTweet` distinct hashtags.
+		TooManyHashtags,
+		/// More than `MaxMentions` accounts are mentioned.
+		TooManyMentions,
+		/// More than `MaxAttachments` media are attached.
+		TooManyAttachments,
+		/// A media content identifier is longer than `MaxCidLen`.
+		CidTooLong,
+		/// The display name is longer than `MaxDisplayNameLen`.
+		DisplayNameTooLong,
+		/// The bio is longer than `MaxBioLen`.
+		BioTooLong,
+		/// The avatar CID is longer than `MaxAvatarCidLen`.
+		AvatarCidTooLong,
+		/// The signer has no profile.
+		NoProfile,
+		/// The handle is shorter than `MinHandleLen` or has characters other than lowercase
+		/// ASCII letters, digits and `_`.
+		InvalidHandle,
+		/// The handle is longer than `MaxHandleLen`.
+		HandleTooLong,
+		/// The handle belongs to another account.
+		HandleTaken,
+		/// The signer already has a handle.
+		AlreadyHasHandle,
+		/// The signer has no handle.
+		NoHandle,
+		/// An account cannot block itself.
+		CannotBlockSelf,
+		/// The signer already blocks the account.
+		AlreadyBlocked,
+		/// The signer does not block the account.
+		NotBlocked,
+		/// The signer already blocks `MaxBlocked` accounts.
+		TooManyBlocked,
+		/// The author of the tweet blocks the signer.
+		BlockedByAuthor,
+		/// The author of the tweet disabled comments on it.
+		CommentsDisabled,
+		/// The signer has no pinned tweet.
+		NoPinnedTweet,
+		/// The signer has not received a notification with this index.
+		NotificationNotFound,
+		/// The tweet is already bookmarked by the signer.
+		AlreadyBookmarked,
+		/// The tweet is not bookmarked by the signer.
+		NotBookmarked,
+		/// The signer already has `MaxBookmarks` bookmarks.
+		TooManyBookmarks,
+		/// The signer already reported the tweet.
+		AlreadyReported,
+		/// The tweet has not been reported.
+		NoReports,
+		/// The tweet was taken down by governance.
+		Censored,
+		/// Tweets can only be scheduled for future blocks.
+		PublishInPast,
+		/// `MaxScheduledPerBlock` tweets were already scheduled at the block.
+		TooManyScheduled,
+		/// No tweet is scheduled at that block and index.
+		NotScheduled,
+		/// The time to live of an ephemeral tweet must be at least one block.
+		InvalidTtl,
+		/// The signer already posted `MaxTweetsPerPeriod` tweets in the current period.
+		RateLimited,
+		/// The signer already has `MaxTweetsPerAccount` tweets.
+		TooManyTweets,
+		/// The account already has that verification status.
+		NoChange,
+		/// An account cannot tip its own tweet.
+		CannotTipSelf,
+		/// A tip must be more than zero.
+		ZeroTip,
+		/// A poll needs between `MinPollOptions` and `MaxPollOptions` options.
+		InvalidPollOptions,
+		/// A poll option is longer than `MaxPollOptionLen`.
+		PollOptionTooLong,
+		/// The tweet has no poll.
+		NoPoll,
+		/// The poll is closed, or would close before it opens.
+		PollClosed,
+		/// The signer already voted for that option in the poll.
+		AlreadyVoted,
+		/// The poll has no option with that index.
+		InvalidOption,
+		/// The tweet already has `MaxNotesPerTweet` notes.
+		TooManyNotes,
+		/// The tweet has no note with that index.
+		NoteNotFound,
+		/// The signer already rated the note.
+		AlreadyRated,
+		/// An account cannot rate its own note.
+		CannotRateOwnNote,
+		/// A muted word must have between 1 and `MaxMutedWordLen` bytes.
+		InvalidMutedWord,
+		/// The signer already mutes the word.
+		AlreadyMuted,
+		/// The signer does not mute the word.
+		NotMuted,
+		/// The signer already mutes `MaxMutedWords` words.
+		TooManyMutedWords,
+		/// The comment contains a word muted by the author of the tweet.
+		ContainsMutedWord,
+		/// A language code must be two lowercase ASCII letters.
+		InvalidLanguageCode,
+		/// The signer posted the same text in the current or the previous `DedupWindow`.
+		DuplicateTweet,
+		/// An account cannot be its own delegate.
+		CannotDelegateSelf,
+		/// The account already is a delegate of the signer.
+		AlreadyDelegate,
+		/// The signer already has `MaxDelegates` delegates.
+		TooManyDelegates,
+		/// The signer is not a delegate of the account.
+		NotDelegate,
+		/// The signer already reacted to the tweet that way.
+		AlreadyReacted,
+		/// The signer has not reacted to the tweet.
+		NoReaction,
+		/// The signer has no premium subscription.
+		NotPremium,
+		/// A followed hashtag must have between 1 and `MaxFollowedHashtagLen` letters, digits or
+		/// underscores.
+		InvalidHashtag,
+		/// The signer already follows the hashtag.
+		AlreadyFollowingHashtag,
+		/// The signer already follows `MaxFollowedHashtags` hashtags.
+		TooManyFollowedHashtags,
+		/// The signer does not follow the hashtag.
+		NotFollowingHashtag,
+		/// The reply policy of the tweet does not let the signer comment on it.
+		ReplyNotAllowed,
+		/// The reply policy of a tweet can only be relaxed.
+		ReplyPolicyTightened,
+		/// The tweet is hidden until the reports against it are resolved.
+		TweetHidden,
+		/// The tweet was already edited `MaxEdits` times.
+		TooManyEdits,
+		/// The sponsored tweet is badly signed, replayed, expired or over `MaxSponsoredPerBlock`.
+		InvalidSponsoredTweet,
+		/// The poll is still accepting votes.
+		PollOpen,
+		/// The poll was already closed.
+		PollAlreadyClosed,
+		/// The tweet was already minted.
+		AlreadyMinted,
+		/// The tweet was not minted, or the signer does not own it.
+		NotOwner,
+		/// The tweet was minted and its owner is no longer its author.
+		CannotDeleteSold,
+		/// A text limit must be between 1 and `MaxTextBytes`.
+		InvalidTextLimit,
+		/// A community name must have between 1 and `MaxCommunityNameLen` bytes.
+		InvalidCommunityName,
+		/// `MaxCommunities` communities were already created.
+		TooManyCommunities,
+		/// The community does not exist.
+		CommunityNotFound,
+		/// The account already is a member of the community.
+		AlreadyMember,
+		/// The account is not a member of the community.
+		NotAMember,
+		/// The community already has `MaxMembersTracked` members.
+		TooManyMembers,
+		/// The signer does not own the community.
+		NotCommunityOwner,
+		/// The owner of a community cannot leave it or be removed from it.
+		OwnerCannotLeave,
+		/// The hash was already committed to, or its tweet revealed.
+		AlreadyCommitted,
+		/// `MaxCommitsPerBlock` commitments were already made in this block.
+		TooManyCommitments,
+		/// No pending commitment matches the text and salt.
+		CommitmentNotFound,
+		/// The tweet of the commitment was already revealed.
+		AlreadyRevealed,
+		/// The `RevealWindow` of the commitment is over.
+		RevealWindowExpired,
+		/// The author of the tweet does not accept tips.
+		TipsDisabled,
+		/// The tweet is not a comment on that tweet.
+		NotAComment,
+		/// The text contains control characters other than `\n`, bidirectional formatting
+		/// characters or more than `MaxZeroWidthRun` zero-width characters in a row.
+		InvalidCharacters,
+		/// Only reposts can have no text.
+		EmptyTweet,
+		/// A tweet cannot refer to itself.
+		SelfReferenceNotAllowed,
+		/// The tweet id is not below `FirstUserTweetId`.
+		IdNotReserved,
+		/// A tweet with that id exists.
+		IdAlreadyUsed,
+		/// The tweet is not gated.
+		NotGated,
+		/// The gated tweet must be unlocked first.
+		NotUnlocked,
+		/// The account already unlocked the tweet.
+		AlreadyUnlocked,
+		/// A gated tweet or a subscription cannot be free.
+		ZeroPrice,
+		/// An account cannot subscribe to itself.
+		CannotSubscribeSelf,
+		/// The author did not set a subscription price.
+		NoSubscriptionPrice,
+		/// The subscription is still active, renew it instead.
+		AlreadySubscribed,
+		/// The account never subscribed to the author.
+		NotSubscribed,
 	}
 }
 
-pub const MAX_TEXT_LEN: u64 = 140;
-
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+	pub struct Module<T: Trait<I>, I: Instance = DefaultInstance> for enum Call where origin: T::Origin {
 		// Errors must be initialized if they are used by the pallet.
-		type Error = Error<T>;
+		type Error = Error<T, I>;
 
 		// Events must be initialized if they are used by the pallet.
 		fn deposit_event() = default;
 
-		#[weight = 10_000]
-		pub fn new_tweet(origin, text: Vec<u8>) {
-			let author = ensure_signed(origin)?;
+		/// Initial maximum length of the text of a tweet, in characters.
+		const MaxTextLen: u32 = T::MaxTextLen::get();
 
-			ensure!(text.len() <= 140, Error::<T>::TweetTooLong);
+		/// Maximum length of the text of a tweet, in bytes.
+		const MaxTextBytes: u32 = T::MaxTextBytes::get();
 
-			let new_id = Self::alloc_id().ok_or(Error::<T>::NoAvailableTweetId)?;
-			let tweet = Tweet {
-				id: new_id,
-				create_at: <frame_system::Module<T>>::block_number(),
-				quote_tweet_id: None,
-				text,
-				comments: vec![],
-				author: author.clone(),
-			};
+		/// Number of blocks after creation during which a tweet can still be edited.
+		const EditWindow: T::BlockNumber = T::EditWindow::get();
 
-			<Accounts<T>>::mutate(&author, |tweets| {
-				tweets.push(new_id);
-			});
-			<Tweets<T>>::insert(new_id, tweet.clone());
+		/// Maximum number of times a tweet can be edited.
+		const MaxEdits: u32 = T::MaxEdits::get();
 
-			Self::deposit_event(RawEvent::Tweeted(tweet));
-		}
+		/// Maximum number of distinct hashtags in the text of a tweet.
+		const MaxHashtagsPerTweet: u32 = T::MaxHashtagsPerTweet::get();
 
-		#[weight = 10_000]
-		pub fn retweet(origin, tweet_id: TweetId, text: Vec<u8>) {
-			let author = ensure_signed(origin)?;
+		/// Maximum number of accounts a tweet can mention.
+		const MaxMentions: u32 = T::MaxMentions::get();
 
-			ensure!(text.len() <= 140, Error::<T>::TweetTooLong);
-			ensure!(Self::tweets(tweet_id).is_none(), Error::<T>::TweetNotFound);
+		/// Maximum number of media attached to a tweet.
+		const MaxAttachments: u32 = T::MaxAttachments::get();
 
-			let new_id = Self::alloc_id().ok_or(Error::<T>::NoAvailableTweetId)?;
-			let tweet = Tweet {
-				id: new_id,
-				create_at: <frame_system::Module<T>>::block_number(),
-				quote_tweet_id: Some(tweet_id),
-				text,
-				comments: vec![],
-				author: author.clone(),
-			};
+		/// Length of the windows in which `MaxTweetsPerPeriod` applies, in blocks.
+		const RateLimitPeriod: T::BlockNumber = T::RateLimitPeriod::get();
 
-			<Accounts<T>>::mutate(&author, |tweets| {
-				tweets.push(new_id);
-			});
-			<Tweets<T>>::insert(new_id, tweet.clone());
+		/// Maximum number of tweets an account can post per period, counting retweets, reposts and
+		/// comments.
+		const MaxTweetsPerPeriod: u32 = T::MaxTweetsPerPeriod::get();
 
-			Self::deposit_event(RawEvent::Tweeted(tweet));
-		}
+		/// Number of tweets an account can post without fees every day.
+		const FreeTweetsPerDay: u32 = T::FreeTweetsPerDay::get();
 
-		#[weight = 10_000]
-		pub fn comment(origin, text: Vec<u8>, tweet_id: TweetId) {
+		/// Length of a day, in blocks.
+		const DayLength: T::BlockNumber = T::DayLength::get();
+
+		/// Free balance an account needs to post without fees.
+		const MinBalanceForFreeTweets: BalanceOf<T, I> = T::MinBalanceForFreeTweets::get();
+
+		/// Maximum number of sponsored tweets included in a block.
+		const MaxSponsoredPerBlock: u32 = T::MaxSponsoredPerBlock::get();
+
+		/// Maximum number of existing tweets of an account.
+		const MaxTweetsPerAccount: u32 = T::MaxTweetsPerAccount::get();
+
+		/// Maximum number of notifications kept for an account.
+		const MaxNotifications: u32 = T::MaxNotifications::get();
+
+		/// Number of the most recent tweets kept.
+		const RecentBufferSize: u32 = T::RecentBufferSize::get();
+
+		/// Whether comments are left out of the most recent tweets.
+		const RecentSkipsComments: bool = T::RecentSkipsComments::get();
+
+		/// Maximum number of accounts an account can block.
+		const MaxBlocked: u32 = T::MaxBlocked::get();
+
+		/// Maximum number of words an account can mute.
+		const MaxMutedWords: u32 = T::MaxMutedWords::get();
+
+		/// Maximum number of hashtags an account can follow.
+		const MaxFollowedHashtags: u32 = T::MaxFollowedHashtags::get();
+
+		/// Maximum number of hashtag followers notified of a new tweet.
+		const MaxHashtagFanout: u32 = T::MaxHashtagFanout::get();
+
+		/// Maximum number of accounts allowed to tweet on behalf of an account.
+		const MaxDelegates: u32 = T::MaxDelegates::get();
+
+		/// Maximum number of tweets an account can bookmark.
+		const MaxBookmarks: u32 = T::MaxBookmarks::get();
+
+		/// Maximum number of tweets posted at once as a thread.
+		const MaxThreadLen: u32 = T::MaxThreadLen::get();
+
+		/// Maximum number of ephemeral tweets removed in a block.
+		const MaxExpirationsPerBlock: u32 = T::MaxExpirationsPerBlock::get();
+
+		/// Maximum number of tweets that can be scheduled for publication at a block.
+		const MaxScheduledPerBlock: u32 = T::MaxScheduledPerBlock::get();
+
+		/// Maximum length of a chain of retweets and reposts.
+		const MaxQuoteDepth: u32 = T::MaxQuoteDepth::get();
+
+		/// Number of most liked tweets kept in `TopTweets`.
+		const MaxLeaderboardSize: u32 = T::MaxLeaderboardSize::get();
+
+		/// Length in blocks of the windows over which hashtag usage is counted.
+		const TrendingWindow: T::BlockNumber = T::TrendingWindow::get();
+
+		/// Number of past windows whose hashtag usage is kept besides the current one.
+		const KeepWindows: u32 = T::KeepWindows::get();
+
+		/// Maximum number of hashtag usage counts of old windows removed in a block.
+		const MaxHashtagPrunesPerBlock: u32 = T::MaxHashtagPrunesPerBlock::get();
+
+		/// Length in blocks of the windows over which duplicate tweets are rejected.
+		const DedupWindow: T::BlockNumber = T::DedupWindow::get();
+
+		/// Maximum number of content hashes of old windows removed in a block.
+		const MaxContentPrunesPerBlock: u32 = T::MaxContentPrunesPerBlock::get();
+
+		/// Maximum number of comments on a tweet.
+		const MaxCommentsPerTweet: u32 = T::MaxCommentsPerTweet::get();
+
+		/// Maximum number of notes on a tweet.
+		const MaxNotesPerTweet: u32 = T::MaxNotesPerTweet::get();
+
+		/// Maximum number of communities that can be created.
+		const MaxCommunities: u32 = T::MaxCommunities::get();
+
+		/// Maximum number of members of a community.
+		const MaxMembersTracked: u32 = T::MaxMembersTracked::get();
+
+		/// Number of blocks after a commitment during which its anonymous tweet can be revealed.
+		const RevealWindow: T::BlockNumber = T::RevealWindow::get();
+
+		/// Maximum number of commitments to anonymous tweets made in a block.
+		const MaxCommitsPerBlock: u32 = T::MaxCommitsPerBlock::get();
+
+		/// Account the revealed anonymous tweets are authored by.
+		const AnonymousAccount: T::AccountId = T::AnonymousAccount::get();
+
+		/// Id of the first tweet posted by an account, the ids below it being reserved.
+		const FirstUserTweetId: TweetId = T::FirstUserTweetId::get();
+
+		/// Minimum number of ratings before a note can be shown.
+		const MinNoteRatings: u32 = T::MinNoteRatings::get();
+
+		/// Share of helpful ratings a note needs to be shown.
+		const NoteShowThreshold: Perbill = T::NoteShowThreshold::get();
+
+		/// Amount reserved from the author for every tweet.
+		const TweetDepositBase: BalanceOf<T, I> = T::TweetDepositBase::get();
+
+		/// Amount reserved from the author for every byte of text and media a tweet stores.
+		const TweetDepositPerByte: BalanceOf<T, I> = T::TweetDepositPerByte::get();
+
+		/// Amount reserved for having a profile.
+		const ProfileDeposit: BalanceOf<T, I> = T::ProfileDeposit::get();
+
+		/// Amount reserved for holding a handle.
+		const HandleDeposit: BalanceOf<T, I> = T::HandleDeposit::get();
+
+		/// Amount reserved for a premium subscription.
+		const PremiumFee: BalanceOf<T, I> = T::PremiumFee::get();
+
+		/// Number of blocks a premium subscription lasts.
+		const PremiumPeriod: T::BlockNumber = T::PremiumPeriod::get();
+
+		/// Number of blocks a paid subscription to an author lasts.
+		const SubscriptionPeriod: T::BlockNumber = T::SubscriptionPeriod::get();
+
+		/// Amount reserved for a commitment to an anonymous tweet.
+		const CommitDeposit: BalanceOf<T, I> = T::CommitDeposit::get();
+
+		/// Maximum length of the text of a tweet of a premium account, in characters.
+		const MaxTextLenPremium: u32 = T::MaxTextLenPremium::get();
+
+		/// Number of counted reports that hide a tweet, zero to never hide tweets.
+		const AutoHideThreshold: u32 = T::AutoHideThreshold::get();
+
+		/// Number of blocks an account must have tweeted for before its reports count.
+		const MinReporterAge: T::BlockNumber = T::MinReporterAge::get();
+
+		/// Whether the deposit of a taken down tweet is slashed rather than returned.
+		const SlashCensoredDeposit: bool = T::SlashCensoredDeposit::get();
+
+		/// Whether a retweet joins the conversation of the tweet it quotes.
+		const QuotesJoinConversation: bool = T::QuotesJoinConversation::get();
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::expire_tweets(now)
+				.saturating_add(Self::publish_scheduled(now))
+				.saturating_add(Self::expire_commitments(now))
+				.saturating_add(Self::prune_hashtag_usage(now))
+				.saturating_add(Self::prune_recent_content(now))
+				.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		fn on_finalize() {
+			<SponsoredInBlock<I>>::kill();
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate()
+		}
+
+		fn offchain_worker(block: T::BlockNumber) {
+			let (count, digest) = Self::indexed_digest(block);
+			debug::native::info!("{} tweets of block {:?} indexed, digest {:?}", count, block, digest);
+		}
+
+		/// Post a tweet, notifying the `mentions` accounts.
+		///
+		/// Mentions of the signer, repeated mentions and mentions of accounts blocking the signer
+		/// are ignored. `media` holds the content identifiers of the attached media, `lang` the
+		/// ISO 639-1 code of the language of the text and `reply_policy` who can comment on the
+		/// tweet, at least as restrictive as the `AccountSettings` of the signer.
+		///
+		/// The signer cannot post the same text twice within `DedupWindow` blocks.
+		///
+		/// The weight of indexing hashtags the text does not contain is refunded. No fee is paid
+		/// for the `FreeTweetsPerDay` first tweets of a day.
+		#[weight = T::WeightInfo::new_tweet(
+			text.len() as u32,
+			T::MaxHashtagsPerTweet::get(),
+			mentions.len() as u32,
+			media.len() as u32,
+		).saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		#[allow(clippy::too_many_arguments)]
+		pub fn new_tweet(
+			origin,
+			text: Vec<u8>,
+			mentions: Vec<T::AccountId>,
+			comments_enabled: bool,
+			visibility: Visibility,
+			media: Vec<Vec<u8>>,
+			lang: Option<LanguageCode>,
+			reply_policy: ReplyPolicy,
+		) -> DispatchResultWithPostInfo {
+			let author = ensure_signed(origin)?;
+
+			let text_len = text.len() as u32;
+			let mentions_len = mentions.len() as u32;
+			let media_len = media.len() as u32;
+			let (_, hashtags) = Self::post_tweet(author.clone(), text, mentions, comments_enabled, visibility, media, lang, reply_policy, None, true)?;
+			let actual_weight = T::WeightInfo::new_tweet(text_len, hashtags, mentions_len, media_len)
+				.saturating_add(Self::hashtag_fanout_weight(hashtags));
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Self::tweet_pays_fee(&author) })
+		}
+
+		/// Post the public tweet `payload` of `payload.author`, signed by its key.
+		///
+		/// The call is submitted unsigned, so that accounts without balance for the fee can
+		/// tweet. `ValidateUnsigned` checks `signature`, the `SponsoredNonce` of the author, the
+		/// `valid_until` block, the text, `DedupWindow`, `MaxTweetsPerAccount` and the rate limit,
+		/// and lets at most `MaxSponsoredPerBlock` such calls into a block. The tweet is then
+		/// posted as by `new_tweet` but without a deposit, the nonce is used even if it is
+		/// rejected.
+		#[weight = T::WeightInfo::new_tweet(payload.text.len() as u32, T::MaxHashtagsPerTweet::get(), 0, 0)
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn submit_sponsored_tweet(origin, payload: SponsoredTweetOf<T>, signature: T::Signature) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			Self::check_sponsored(&payload, &signature).map_err(|_| Error::<T, I>::InvalidSponsoredTweet)?;
+
+			let SponsoredTweet { author, text, nonce, .. } = payload;
+			<SponsoredNonce<T, I>>::insert(&author, nonce.saturating_add(1));
+			<SponsoredInBlock<I>>::mutate(|count| *count = count.saturating_add(1));
+
+			let text_len = text.len() as u32;
+			let (_, hashtags) = Self::post_tweet(author, text, Vec::new(), true, Visibility::Public, Vec::new(), None, ReplyPolicy::Everyone, None, false)?;
+			let actual_weight = T::WeightInfo::new_tweet(text_len, hashtags, 0, 0)
+				.saturating_add(Self::hashtag_fanout_weight(hashtags))
+				.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Post a public tweet on behalf of `owner`, who the signer must be a delegate of.
+		///
+		/// The tweet is `owner`'s in every way: its deposit, rate limit and tweet cap are those of
+		/// `owner`. `Tweeted` is emitted as for `new_tweet`, followed by `TweetedVia`.
+		///
+		/// The weight of indexing hashtags the text does not contain is refunded.
+		#[weight = T::WeightInfo::tweet_as(text.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn tweet_as(origin, owner: T::AccountId, text: Vec<u8>) -> DispatchResultWithPostInfo {
+			let delegate = ensure_signed(origin)?;
+
+			ensure!(<Delegates<T, I>>::contains_key(&owner, &delegate), Error::<T, I>::NotDelegate);
+			let text_len = text.len() as u32;
+			let (new_id, hashtags) = Self::post_tweet(owner.clone(), text, Vec::new(), true, Visibility::Public, Vec::new(), None, ReplyPolicy::Everyone, None, true)?;
+
+			Self::deposit_event(RawEvent::TweetedVia(owner, delegate, new_id));
+			Ok(Some(T::WeightInfo::tweet_as(text_len, hashtags).saturating_add(Self::hashtag_fanout_weight(hashtags))).into())
+		}
+
+		/// Post a thread of tweets, each one continuing the previous one.
+		///
+		/// Either every tweet of the thread is posted, with consecutive ids, or none is.
+		#[weight = T::WeightInfo::new_thread(texts.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()).saturating_mul(texts.len() as Weight))]
+		pub fn new_thread(origin, texts: Vec<Vec<u8>>) {
+			let author = ensure_signed(origin)?;
+
+			ensure!(!texts.is_empty(), Error::<T, I>::EmptyThread);
+			ensure!(texts.len() <= T::MaxThreadLen::get() as usize, Error::<T, I>::ThreadTooLong);
+			let segments = texts.into_iter()
+				.map(|text| {
+					let text = Self::ensure_text(&author, text)?;
+					let hashtags = Self::ensure_hashtags(&text)?;
+					let deposit = Self::tweet_deposit_for(&text);
+					Ok((text, hashtags, deposit))
+				})
+				.collect::<Result<Vec<_>, Error<T, I>>>()?;
+			let len = segments.len() as u32;
+			let total = segments.iter().fold(Zero::zero(), |total: BalanceOf<T, I>, (_, _, deposit)| total.saturating_add(*deposit));
+			ensure!(T::Currency::can_reserve(&author, total), Error::<T, I>::InsufficientBalance);
+			Self::ensure_tweet_capacity(&author, len)?;
+			let rate = Self::ensure_rate_limit(&author, len)?;
+			ensure!(
+				Self::next_tweet_id().checked_add(len as TweetId).is_some(),
+				Error::<T, I>::NoAvailableTweetId
+			);
+
+			let ids = Self::transactional(|| {
+				let mut ids: Vec<TweetId> = Vec::with_capacity(segments.len());
+				for (text, hashtags, deposit) in segments {
+					let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+					let tweet = Tweet {
+						id: new_id,
+						create_at: <frame_system::Module<T>>::block_number(),
+						quote_tweet_id: None,
+						parent_id: None,
+						thread_prev: ids.last().copied(),
+						text,
+						author: author.clone(),
+						comments_enabled: true,
+						visibility: Visibility::Public,
+						media: Default::default(),
+						lang: None,
+						reply_policy: Self::settings(&author).reply_policy,
+						hidden: false,
+						conversation_id: ids.first().copied().unwrap_or(new_id),
+						revision: 0,
+						community: None,
+					};
+
+					Self::reserve_deposit(&author, new_id, deposit)?;
+					Self::push_account_tweet(&author, new_id);
+					Self::index_hashtags(new_id, &hashtags);
+					Self::count_hashtags(&hashtags);
+					Self::notify_hashtag_followers(&author, new_id, &hashtags);
+					<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+					<Tweets<T, I>>::insert(new_id, tweet);
+					ids.push(new_id);
+				}
+				<TweetRate<T, I>>::insert(&author, rate);
+				Ok(ids)
+			})?;
+
+			for id in &ids {
+				Self::on_tweet_created(&author, *id, TweetKind::Tweet);
+			}
+			Self::deposit_event(RawEvent::ThreadPosted(author, ids));
+		}
+
+		/// Post a tweet that is removed `ttl_blocks` blocks from now.
+		///
+		/// At most `MaxExpirationsPerBlock` tweets are removed in a block, so the removal can be
+		/// delayed when many tweets expire at once.
+		#[weight = T::WeightInfo::new_ephemeral_tweet(text.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn new_ephemeral_tweet(origin, text: Vec<u8>, ttl_blocks: T::BlockNumber) {
 			let author = ensure_signed(origin)?;
 
-			ensure!(text.len() <= 140, Error::<T>::TweetTooLong);
-			ensure!(Self::tweets(tweet_id).is_none(), Error::<T>::TweetNotFound);
+			Self::transactional(|| {
+				let text = Self::ensure_text(&author, text)?;
+				let hashtags = Self::ensure_hashtags(&text)?;
+				ensure!(!ttl_blocks.is_zero(), Error::<T, I>::InvalidTtl);
+				let now = <frame_system::Module<T>>::block_number();
+				let expires_at = now.saturating_add(ttl_blocks);
+				let deposit = Self::ensure_can_reserve(&author, &text)?;
+				Self::ensure_tweet_capacity(&author, 1)?;
+				let rate = Self::ensure_rate_limit(&author, 1)?;
+
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let tweet = Tweet {
+					id: new_id,
+					create_at: now,
+					quote_tweet_id: None,
+					parent_id: None,
+					thread_prev: None,
+					text,
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media: Default::default(),
+					lang: None,
+					reply_policy: Self::settings(&author).reply_policy,
+					hidden: false,
+					conversation_id: new_id,
+					revision: 0,
+					community: None,
+				};
+
+				Self::reserve_deposit(&author, new_id, deposit)?;
+				<TweetRate<T, I>>::insert(&author, rate);
+				Self::push_account_tweet(&author, new_id);
+				Self::index_hashtags(new_id, &hashtags);
+				Self::count_hashtags(&hashtags);
+				Self::notify_hashtag_followers(&author, new_id, &hashtags);
+				<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, tweet);
+				<ExpiringAt<T, I>>::insert(expires_at, new_id, ());
+				<TweetExpiry<T, I>>::insert(new_id, expires_at);
+
+				Self::on_tweet_created(&author, new_id, TweetKind::Tweet);
+				let has_identity = T::Identity::is_judged(&author);
+				Self::deposit_event(RawEvent::Tweeted(author, new_id, None, has_identity));
+				Ok(())
+			})?;
+		}
+
+		/// Schedule a tweet to be published at the start of block `publish_at`.
+		///
+		/// The tweet deposit is reserved and the rate limit applied right away, as for a tweet
+		/// posted now. At most `MaxScheduledPerBlock` tweets can ever be scheduled at a block,
+		/// cancelled ones included.
+		#[weight = T::WeightInfo::schedule_tweet(text.len() as u32, T::MaxHashtagsPerTweet::get())]
+		pub fn schedule_tweet(origin, text: Vec<u8>, publish_at: T::BlockNumber) {
+			let author = ensure_signed(origin)?;
+
+			let text = Self::ensure_text(&author, text)?;
+			Self::ensure_hashtags(&text)?;
+			ensure!(publish_at > <frame_system::Module<T>>::block_number(), Error::<T, I>::PublishInPast);
+			let index = Self::scheduled_count(publish_at);
+			ensure!(index < T::MaxScheduledPerBlock::get(), Error::<T, I>::TooManyScheduled);
+			let deposit = Self::ensure_can_reserve(&author, &text)?;
+			Self::ensure_tweet_capacity(&author, 1)?;
+			let rate = Self::ensure_rate_limit(&author, 1)?;
+
+			T::Currency::reserve(&author, deposit).map_err(|_| Error::<T, I>::InsufficientBalance)?;
+			<TweetRate<T, I>>::insert(&author, rate);
+			<ScheduledTweets<T, I>>::insert(publish_at, index, (&author, text, deposit));
+			<ScheduledCount<T, I>>::insert(publish_at, index + 1);
+
+			Self::deposit_event(RawEvent::TweetScheduled(author, publish_at, index));
+		}
+
+		/// Cancel a tweet scheduled by the signer and return its deposit.
+		#[weight = T::WeightInfo::cancel_scheduled_tweet()]
+		pub fn cancel_scheduled_tweet(origin, publish_at: T::BlockNumber, index: u32) {
+			let who = ensure_signed(origin)?;
+
+			let (author, _, deposit) = Self::scheduled_tweets(publish_at, index).ok_or(Error::<T, I>::NotScheduled)?;
+			ensure!(author == who, Error::<T, I>::NotAuthor);
+
+			<ScheduledTweets<T, I>>::remove(publish_at, index);
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(RawEvent::ScheduledTweetCancelled(who, publish_at, index));
+		}
+
+		/// Quote tweet `tweet_id`, adding `text` and `media`.
+		///
+		/// An account can quote a tweet once, and the retweet can be at most `MaxQuoteDepth`
+		/// quotes away from a tweet quoting nothing. The hash of the quoted text is kept in
+		/// `QuoteSnapshots`, see `quote_is_stale`.
+		///
+		/// The weight of indexing hashtags the text does not contain and of walking a shorter
+		/// quote chain is refunded. No fee is paid for the `FreeTweetsPerDay` first tweets of a
+		/// day.
+		#[weight = T::WeightInfo::retweet(
+			text.len() as u32,
+			T::MaxHashtagsPerTweet::get(),
+			media.len() as u32,
+			T::MaxQuoteDepth::get(),
+		).saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn retweet(
+			origin,
+			tweet_id: TweetId,
+			text: Vec<u8>,
+			media: Vec<Vec<u8>>,
+			lang: Option<LanguageCode>,
+		) -> DispatchResultWithPostInfo {
+			let author = ensure_signed(origin)?;
+
+			let info = Self::transactional(|| {
+				let text = Self::ensure_text(&author, text)?;
+				Self::ensure_lang(lang)?;
+				let hashtags = Self::ensure_hashtags(&text)?;
+				let media = Self::ensure_media(media)?;
+				let media_count = media.len() as u32;
+				let original = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+				ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+				ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+				ensure!(!original.hidden, Error::<T, I>::TweetHidden);
+				Self::ensure_visible(&original, &author)?;
+				ensure!(!<Retweeted<T, I>>::contains_key(tweet_id, &author), Error::<T, I>::AlreadyRetweeted);
+				let depth = Self::ensure_quote_depth(&original)?;
+				let deposit = Self::ensure_can_reserve(&author, &(&text, &media))?;
+				Self::ensure_tweet_capacity(&author, 1)?;
+				let rate = Self::ensure_rate_limit(&author, 1)?;
+				let actual_weight = T::WeightInfo::retweet(text.len() as u32, hashtags.len() as u32, media_count, depth)
+					.saturating_add(Self::hashtag_fanout_weight(hashtags.len() as u32));
+
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let conversation_id = if T::QuotesJoinConversation::get() { original.conversation_id } else { new_id };
+				let tweet = Tweet {
+					id: new_id,
+					create_at: <frame_system::Module<T>>::block_number(),
+					quote_tweet_id: Some(tweet_id),
+					parent_id: None,
+					thread_prev: None,
+					text,
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media,
+					lang,
+					reply_policy: Self::settings(&author).reply_policy,
+					hidden: false,
+					conversation_id,
+					revision: 0,
+					community: None,
+				};
+
+				Self::reserve_deposit(&author, new_id, deposit)?;
+				<TweetRate<T, I>>::insert(&author, rate);
+				Self::push_account_tweet(&author, new_id);
+				Self::index_hashtags(new_id, &hashtags);
+				Self::count_hashtags(&hashtags);
+				Self::notify_hashtag_followers(&author, new_id, &hashtags);
+				Self::index_lang(new_id, lang);
+				<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, tweet);
+				<Retweeted<T, I>>::insert(tweet_id, &author, ());
+				<RetweetCount<I>>::mutate(tweet_id, |count| *count = count.saturating_add(1));
+				<RetweetPreview<T, I>>::mutate(tweet_id, |preview| Self::add_to_preview(preview, &author));
+				<QuoteSnapshots<I>>::insert(new_id, blake2_256(&original.text));
+
+				Self::notify(&original.author, &author, Notification::Retweeted(author.clone(), new_id));
+				Self::on_tweet_created(&author, new_id, TweetKind::Retweet);
+				let pays_fee = Self::tweet_pays_fee(&author);
+				Self::deposit_event(RawEvent::Retweeted(author, new_id, tweet_id, lang));
+				Self::deposit_media_event(new_id, media_count);
+				Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
+			})?;
+			Ok(info)
+		}
+
+		/// Share a tweet as is, without adding any text.
+		///
+		/// The repost is stored as a tweet with empty text quoting the original, so it counts
+		/// towards `MaxQuoteDepth` like a retweet.
+		#[weight = T::WeightInfo::repost(T::MaxQuoteDepth::get())]
+		pub fn repost(origin, tweet_id: TweetId) {
+			let author = ensure_signed(origin)?;
+
+			Self::transactional(|| {
+				let original = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+				ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+				ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+				ensure!(!original.hidden, Error::<T, I>::TweetHidden);
+				Self::ensure_visible(&original, &author)?;
+				ensure!(!<Reposts<T, I>>::contains_key(tweet_id, &author), Error::<T, I>::AlreadyReposted);
+				Self::ensure_quote_depth(&original)?;
+				let deposit = Self::ensure_can_reserve(&author, &TextOf::<T, I>::default())?;
+				Self::ensure_tweet_capacity(&author, 1)?;
+				let rate = Self::ensure_rate_limit(&author, 1)?;
+
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let repost = Tweet {
+					id: new_id,
+					create_at: <frame_system::Module<T>>::block_number(),
+					quote_tweet_id: Some(tweet_id),
+					parent_id: None,
+					thread_prev: None,
+					text: Default::default(),
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media: Default::default(),
+					lang: None,
+					reply_policy: Self::settings(&author).reply_policy,
+					hidden: false,
+					conversation_id: new_id,
+					revision: 0,
+					community: None,
+				};
+
+				Self::reserve_deposit(&author, new_id, deposit)?;
+				<TweetRate<T, I>>::insert(&author, rate);
+				Self::push_account_tweet(&author, new_id);
+				<ConversationTweets<I>>::insert(repost.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, repost);
+				<Reposts<T, I>>::insert(tweet_id, &author, new_id);
+				<RepostCount<I>>::mutate(tweet_id, |count| *count = count.saturating_add(1));
+				<RetweetCount<I>>::mutate(tweet_id, |count| *count = count.saturating_add(1));
+				<RetweetPreview<T, I>>::mutate(tweet_id, |preview| Self::add_to_preview(preview, &author));
+
+				Self::notify(&original.author, &author, Notification::Retweeted(author.clone(), new_id));
+				Self::on_tweet_created(&author, new_id, TweetKind::Retweet);
+				Self::deposit_event(RawEvent::Reposted(author, new_id, tweet_id));
+				Ok(())
+			})?;
+		}
+
+		/// Comment on tweet `tweet_id`, in language `lang` if given.
+		///
+		/// The weight of indexing hashtags the text does not contain is refunded. No fee is paid
+		/// for the `FreeTweetsPerDay` first tweets of a day.
+		#[weight = T::WeightInfo::comment(text.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn comment(origin, text: Vec<u8>, tweet_id: TweetId, lang: Option<LanguageCode>) -> DispatchResultWithPostInfo {
+			let author = ensure_signed(origin)?;
+
+			let info = Self::transactional(|| {
+				let text = Self::ensure_text(&author, text)?;
+				Self::ensure_lang(lang)?;
+				let hashtags = Self::ensure_hashtags(&text)?;
+				let parent = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+				ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+				ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+				ensure!(!parent.hidden, Error::<T, I>::TweetHidden);
+				Self::ensure_visible(&parent, &author)?;
+				ensure!(parent.comments_enabled, Error::<T, I>::CommentsDisabled);
+				Self::ensure_can_reply(&parent, &author)?;
+				ensure!(Self::has_unlocked(&parent, &author), Error::<T, I>::NotUnlocked);
+				ensure!(!<Blocked<T, I>>::contains_key(&parent.author, &author), Error::<T, I>::BlockedByAuthor);
+				ensure!(!Self::contains_muted_word(&parent.author, &text), Error::<T, I>::ContainsMutedWord);
+				let index = Self::next_comment_index(tweet_id);
+				ensure!(index < T::MaxCommentsPerTweet::get(), Error::<T, I>::TooManyComments);
+				let deposit = Self::ensure_can_reserve(&author, &text)?;
+				Self::ensure_tweet_capacity(&author, 1)?;
+				let rate = Self::ensure_rate_limit(&author, 1)?;
+				let actual_weight = T::WeightInfo::comment(text.len() as u32, hashtags.len() as u32)
+					.saturating_add(Self::hashtag_fanout_weight(hashtags.len() as u32));
+
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let comment = Tweet {
+					id: new_id,
+					create_at: <frame_system::Module<T>>::block_number(),
+					quote_tweet_id: None,
+					parent_id: Some(tweet_id),
+					thread_prev: None,
+					text,
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media: Default::default(),
+					lang,
+					reply_policy: Self::settings(&author).reply_policy,
+					hidden: false,
+					conversation_id: parent.conversation_id,
+					revision: 0,
+					community: None,
+				};
+
+				<Comments<I>>::insert(tweet_id, index, new_id);
+				<CommentIndex<I>>::insert(new_id, index);
+				<NextCommentIndex<I>>::insert(tweet_id, index + 1);
+				<CommentCount<I>>::mutate(tweet_id, |count| *count = count.saturating_add(1));
+				Self::reserve_deposit(&author, new_id, deposit)?;
+				<TweetRate<T, I>>::insert(&author, rate);
+				Self::push_account_tweet(&author, new_id);
+				Self::index_hashtags(new_id, &hashtags);
+				Self::count_hashtags(&hashtags);
+				Self::notify_hashtag_followers(&author, new_id, &hashtags);
+				Self::index_lang(new_id, lang);
+				<ConversationTweets<I>>::insert(comment.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, comment);
+
+				Self::notify(&parent.author, &author, Notification::CommentedOn(author.clone(), new_id));
+				Self::on_tweet_created(&author, new_id, TweetKind::Comment);
+				let pays_fee = Self::tweet_pays_fee(&author);
+				Self::deposit_event(RawEvent::Commented(author, new_id, tweet_id, lang));
+				Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
+			})?;
+			Ok(info)
+		}
+
+		/// Delete a tweet authored by the signer.
+		///
+		/// The tweet is kept as a tombstone with blank text and no media, keeping its author,
+		/// comments and links to other tweets so that threads still resolve. Its deposit is
+		/// returned and it can no longer be interacted with. A minted tweet owned by another
+		/// account cannot be deleted.
+		#[weight = T::WeightInfo::delete_tweet(T::MaxHashtagsPerTweet::get())]
+		pub fn delete_tweet(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			ensure!(!Self::is_sold(&tweet), Error::<T, I>::CannotDeleteSold);
+
+			Self::delete_to_tombstone(tweet);
+
+			Self::deposit_event(RawEvent::TweetDeleted(tweet_id, who));
+		}
+
+		/// Remove comment `comment_id` from tweet `parent_id`, as the author of either.
+		///
+		/// The comment is deleted as by `delete_tweet`, its own comments kept, and taken out of
+		/// the `Comments` of the parent. A comment deleted before is only taken out. The author
+		/// of the comment cannot remove it once minted and owned by another account, the author
+		/// of the parent can. The other comments keep their indices.
+		#[weight = T::WeightInfo::remove_comment(T::MaxHashtagsPerTweet::get())]
+		pub fn remove_comment(origin, parent_id: TweetId, comment_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(parent_id != comment_id, Error::<T, I>::SelfReferenceNotAllowed);
+			let comment = Self::tweets(comment_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(comment.parent_id == Some(parent_id), Error::<T, I>::NotAComment);
+			let parent_author = Self::tweets(parent_id).map(|parent| parent.author);
+			ensure!(comment.author == who || parent_author.as_ref() == Some(&who), Error::<T, I>::NotAuthor);
+			ensure!(parent_author == Some(who.clone()) || !Self::is_sold(&comment), Error::<T, I>::CannotDeleteSold);
+			let index = <CommentIndex<I>>::take(comment_id).ok_or(Error::<T, I>::NotAComment)?;
+
+			<Comments<I>>::remove(parent_id, index);
+			if !Self::is_deleted(comment_id) {
+				Self::delete_to_tombstone(comment);
+			}
+
+			Self::deposit_event(RawEvent::CommentRemoved(parent_id, comment_id, who));
+		}
+
+		/// Take back a retweet or repost created by the signer, unless it was minted and is
+		/// owned by another account.
+		#[weight = T::WeightInfo::undo_retweet(T::MaxHashtagsPerTweet::get())]
+		pub fn undo_retweet(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+			let original = tweet.quote_tweet_id.ok_or(Error::<T, I>::NotARetweet)?;
+			ensure!(!Self::is_sold(&tweet), Error::<T, I>::CannotDeleteSold);
+
+			Self::remove_tweet(&tweet);
+
+			Self::deposit_event(RawEvent::RetweetUndone(tweet_id, original));
+		}
+
+		/// Replace the text of a tweet authored by the signer, keeping the replaced text in
+		/// `EditHistory`.
+		///
+		/// Only allowed up to and including block `create_at + EditWindow`, and `MaxEdits` times.
+		/// Every kept text adds its own deposit to the deposit of the tweet.
+		#[weight = T::WeightInfo::edit_tweet(new_text.len() as u32, T::MaxHashtagsPerTweet::get())]
+		pub fn edit_tweet(origin, tweet_id: TweetId, new_text: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			let new_text = Self::ensure_text(&who, new_text)?;
+			let hashtags = Self::ensure_hashtags(&new_text)?;
+
+			<Tweets<T, I>>::try_mutate_exists(tweet_id, |maybe_tweet| -> DispatchResult {
+				let tweet = maybe_tweet.as_mut().ok_or(Error::<T, I>::TweetNotFound)?;
+				ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+				ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+				ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+
+				let now = <frame_system::Module<T>>::block_number();
+				ensure!(now <= tweet.create_at.saturating_add(T::EditWindow::get()), Error::<T, I>::EditWindowExpired);
+				ensure!(tweet.revision < T::MaxEdits::get(), Error::<T, I>::TooManyEdits);
+				let deposit = Self::tweet_deposit_for(&tweet.text);
+				T::Currency::reserve(&who, deposit).map_err(|_| Error::<T, I>::InsufficientBalance)?;
+				<TweetDeposits<T, I>>::mutate(tweet_id, |total| *total = total.saturating_add(deposit));
+
+				Self::unindex_hashtags(tweet_id, &tweet.text);
+				Self::index_hashtags(tweet_id, &hashtags);
+				let old_text = sp_std::mem::replace(&mut tweet.text, new_text);
+				<EditHistory<T, I>>::insert(tweet_id, tweet.revision, (now, old_text));
+				tweet.revision = tweet.revision.saturating_add(1);
+				Ok(())
+			})?;
+
+			Self::index_offchain(tweet_id);
+			Self::deposit_event(RawEvent::TweetEdited(tweet_id));
+		}
+
+		/// Allow or forbid comments on a tweet authored by the signer.
+		///
+		/// Existing comments are kept.
+		#[weight = T::WeightInfo::set_comments_enabled()]
+		pub fn set_comments_enabled(origin, tweet_id: TweetId, enabled: bool) {
+			let who = ensure_signed(origin)?;
+
+			<Tweets<T, I>>::try_mutate_exists(tweet_id, |maybe_tweet| -> DispatchResult {
+				let tweet = maybe_tweet.as_mut().ok_or(Error::<T, I>::TweetNotFound)?;
+				ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+				ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+				ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+
+				tweet.comments_enabled = enabled;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::CommentsEnabledSet(tweet_id, enabled));
+		}
+
+		/// Relax who can reply to a tweet authored by the signer.
+		///
+		/// Existing comments are kept.
+		#[weight = T::WeightInfo::set_reply_policy()]
+		pub fn set_reply_policy(origin, tweet_id: TweetId, policy: ReplyPolicy) {
+			let who = ensure_signed(origin)?;
+
+			<Tweets<T, I>>::try_mutate_exists(tweet_id, |maybe_tweet| -> DispatchResult {
+				let tweet = maybe_tweet.as_mut().ok_or(Error::<T, I>::TweetNotFound)?;
+				ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+				ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+				ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+				ensure!(policy <= tweet.reply_policy, Error::<T, I>::ReplyPolicyTightened);
+
+				tweet.reply_policy = policy;
+				Ok(())
+			})?;
+			if policy == ReplyPolicy::Everyone {
+				<TweetMentions<T, I>>::remove_prefix(tweet_id);
+			}
+
+			Self::deposit_event(RawEvent::ReplyPolicySet(tweet_id, policy));
+		}
+
+		/// Save a tweet for the signer, without notifying anyone or counting it publicly.
+		#[weight = T::WeightInfo::bookmark()]
+		pub fn bookmark(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Tweets<T, I>>::contains_key(tweet_id), Error::<T, I>::TweetNotFound);
+			ensure!(!<Bookmarks<T, I>>::contains_key(&who, tweet_id), Error::<T, I>::AlreadyBookmarked);
+			let count = Self::bookmark_count(&who);
+			ensure!(count < T::MaxBookmarks::get(), Error::<T, I>::TooManyBookmarks);
+
+			<Bookmarks<T, I>>::insert(&who, tweet_id, ());
+			<BookmarkCount<T, I>>::insert(&who, count + 1);
+
+			Self::deposit_event(RawEvent::Bookmarked(who, tweet_id));
+		}
+
+		/// Remove a bookmark of the signer, also allowed once the tweet is deleted.
+		#[weight = T::WeightInfo::remove_bookmark()]
+		pub fn remove_bookmark(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Bookmarks<T, I>>::contains_key(&who, tweet_id), Error::<T, I>::NotBookmarked);
+
+			<Bookmarks<T, I>>::remove(&who, tweet_id);
+			<BookmarkCount<T, I>>::mutate(&who, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(RawEvent::BookmarkRemoved(who, tweet_id));
+		}
+
+		/// Pin a tweet authored by the signer, replacing any previous pin.
+		#[weight = T::WeightInfo::pin_tweet()]
+		pub fn pin_tweet(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+
+			<PinnedTweet<T, I>>::insert(&who, tweet_id);
+
+			Self::deposit_event(RawEvent::TweetPinned(who, tweet_id));
+		}
+
+		#[weight = T::WeightInfo::unpin_tweet()]
+		pub fn unpin_tweet(origin) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<PinnedTweet<T, I>>::contains_key(&who), Error::<T, I>::NoPinnedTweet);
+
+			<PinnedTweet<T, I>>::remove(&who);
+
+			Self::deposit_event(RawEvent::TweetUnpinned(who));
+		}
+
+		/// Mark the notifications of the signer as read, up to and including the one at
+		/// `up_to_index`.
+		///
+		/// The notification at index `i` is `notifications[i + len - notification_count]`, as
+		/// the oldest ones are dropped. Marking fewer notifications than already read is a no-op.
+		#[weight = T::WeightInfo::mark_notifications_read()]
+		pub fn mark_notifications_read(origin, up_to_index: u64) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(up_to_index < Self::notification_count(&who), Error::<T, I>::NotificationNotFound);
+
+			<NotificationsReadUpTo<T, I>>::mutate(&who, |read| *read = (*read).max(up_to_index + 1));
+
+			Self::deposit_event(RawEvent::NotificationsRead(who, up_to_index));
+		}
+
+		/// Remove all notifications of the signer.
+		#[weight = T::WeightInfo::clear_notifications()]
+		pub fn clear_notifications(origin) {
+			let who = ensure_signed(origin)?;
+
+			<Notifications<T, I>>::remove(&who);
+
+			Self::deposit_event(RawEvent::NotificationsCleared(who));
+		}
+
+		/// Stop `target` from commenting on the tweets of the signer and from notifying it.
+		#[weight = T::WeightInfo::block_account()]
+		pub fn block_account(origin, target: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(who != target, Error::<T, I>::CannotBlockSelf);
+			ensure!(!<Blocked<T, I>>::contains_key(&who, &target), Error::<T, I>::AlreadyBlocked);
+			let count = Self::blocked_count(&who);
+			ensure!(count < T::MaxBlocked::get(), Error::<T, I>::TooManyBlocked);
+
+			<Blocked<T, I>>::insert(&who, &target, ());
+			<BlockedCount<T, I>>::insert(&who, count + 1);
+
+			Self::deposit_event(RawEvent::AccountBlocked(who, target));
+		}
+
+		#[weight = T::WeightInfo::unblock_account()]
+		pub fn unblock_account(origin, target: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Blocked<T, I>>::contains_key(&who, &target), Error::<T, I>::NotBlocked);
+
+			<Blocked<T, I>>::remove(&who, &target);
+			<BlockedCount<T, I>>::mutate(&who, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(RawEvent::AccountUnblocked(who, target));
+		}
+
+		/// Allow `who` to tweet on behalf of the signer with `tweet_as`.
+		#[weight = T::WeightInfo::add_delegate()]
+		pub fn add_delegate(origin, who: T::AccountId) {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(owner != who, Error::<T, I>::CannotDelegateSelf);
+			ensure!(!<Delegates<T, I>>::contains_key(&owner, &who), Error::<T, I>::AlreadyDelegate);
+			let count = Self::delegate_count(&owner);
+			ensure!(count < T::MaxDelegates::get(), Error::<T, I>::TooManyDelegates);
+
+			<Delegates<T, I>>::insert(&owner, &who, ());
+			<DelegateCount<T, I>>::insert(&owner, count + 1);
+
+			Self::deposit_event(RawEvent::DelegateAdded(owner, who));
+		}
+
+		#[weight = T::WeightInfo::remove_delegate()]
+		pub fn remove_delegate(origin, who: T::AccountId) {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(<Delegates<T, I>>::contains_key(&owner, &who), Error::<T, I>::NotDelegate);
+
+			<Delegates<T, I>>::remove(&owner, &who);
+			<DelegateCount<T, I>>::mutate(&owner, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(RawEvent::DelegateRemoved(owner, who));
+		}
+
+		/// Keep comments containing `word` off the tweets of the signer.
+		///
+		/// Words are matched case-insensitively, as ASCII, anywhere in the comment.
+		#[weight = T::WeightInfo::mute_word()]
+		pub fn mute_word(origin, word: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			let word = Self::ensure_muted_word(word)?;
+			let mut words = Self::muted_words(&who);
+			ensure!(!words.contains(&word), Error::<T, I>::AlreadyMuted);
+			words.try_push(word.clone()).map_err(|_| Error::<T, I>::TooManyMutedWords)?;
+			<MutedWords<T, I>>::insert(&who, words);
+
+			Self::deposit_event(RawEvent::WordMuted(who, word.into_inner()));
+		}
+
+		#[weight = T::WeightInfo::unmute_word()]
+		pub fn unmute_word(origin, word: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			let word = Self::ensure_muted_word(word)?;
+			let mut words = Self::muted_words(&who);
+			let index = words.iter().position(|muted| *muted == word).ok_or(Error::<T, I>::NotMuted)?;
+			words.remove(index);
+			if words.is_empty() {
+				<MutedWords<T, I>>::remove(&who);
+			} else {
+				<MutedWords<T, I>>::insert(&who, words);
+			}
+
+			Self::deposit_event(RawEvent::WordUnmuted(who, word.into_inner()));
+		}
+
+		/// Get notified of new tweets with the hashtag `tag`, given with or without its `#`.
+		///
+		/// Only the first `MaxHashtagFanout` followers found are notified of a tweet, the tweets
+		/// of a hashtag can always be read with `hashtag_tweets`.
+		#[weight = T::WeightInfo::follow_hashtag()]
+		pub fn follow_hashtag(origin, tag: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			let tag = Self::ensure_followed_hashtag(tag)?;
+			ensure!(!<FollowedHashtags<T, I>>::contains_key(&who, &tag), Error::<T, I>::AlreadyFollowingHashtag);
+			let count = Self::followed_hashtag_count(&who);
+			ensure!(count < T::MaxFollowedHashtags::get(), Error::<T, I>::TooManyFollowedHashtags);
+
+			<FollowedHashtags<T, I>>::insert(&who, &tag, ());
+			<HashtagFollowers<T, I>>::insert(&tag, &who, ());
+			<FollowedHashtagCount<T, I>>::insert(&who, count + 1);
+
+			Self::deposit_event(RawEvent::HashtagFollowed(who, tag));
+		}
+
+		#[weight = T::WeightInfo::unfollow_hashtag()]
+		pub fn unfollow_hashtag(origin, tag: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			let tag = Self::ensure_followed_hashtag(tag)?;
+			<FollowedHashtags<T, I>>::take(&who, &tag).ok_or(Error::<T, I>::NotFollowingHashtag)?;
+			<HashtagFollowers<T, I>>::remove(&tag, &who);
+			<FollowedHashtagCount<T, I>>::mutate_exists(&who, |count| {
+				*count = count.and_then(|count| count.checked_sub(1)).filter(|&count| count > 0);
+			});
+
+			Self::deposit_event(RawEvent::HashtagUnfollowed(who, tag));
+		}
+
+		/// Set the profile of the signer, replacing any previous one.
+		///
+		/// The profile deposit is reserved when the first profile is set.
+		#[weight = T::WeightInfo::set_profile()]
+		pub fn set_profile(origin, display_name: Vec<u8>, bio: Vec<u8>, avatar_cid: Option<Vec<u8>>) {
+			let who = ensure_signed(origin)?;
+
+			let profile = Profile {
+				display_name: BoundedVec::try_from(display_name).map_err(|_| Error::<T, I>::DisplayNameTooLong)?,
+				bio: BoundedVec::try_from(bio).map_err(|_| Error::<T, I>::BioTooLong)?,
+				avatar_cid: avatar_cid
+					.map(BoundedVec::try_from)
+					.transpose()
+					.map_err(|_| Error::<T, I>::AvatarCidTooLong)?,
+			};
+
+			if !<Profiles<T, I>>::contains_key(&who) {
+				let deposit = T::ProfileDeposit::get();
+				T::Currency::reserve(&who, deposit).map_err(|_| Error::<T, I>::InsufficientBalance)?;
+				<ProfileDeposits<T, I>>::insert(&who, deposit);
+			}
+			<Profiles<T, I>>::insert(&who, profile);
+
+			Self::deposit_event(RawEvent::ProfileSet(who));
+		}
+
+		/// Remove the profile of the signer and return its deposit.
+		#[weight = T::WeightInfo::clear_profile()]
+		pub fn clear_profile(origin) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Profiles<T, I>>::contains_key(&who), Error::<T, I>::NoProfile);
+
+			<Profiles<T, I>>::remove(&who);
+			T::Currency::unreserve(&who, <ProfileDeposits<T, I>>::take(&who));
+
+			Self::deposit_event(RawEvent::ProfileCleared(who));
+		}
+
+		/// Claim a free handle for the signer, reserving the handle deposit.
+		#[weight = T::WeightInfo::claim_handle()]
+		pub fn claim_handle(origin, handle: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			let handle = Handle::try_from(handle).map_err(|_| Error::<T, I>::HandleTooLong)?;
+			ensure!(
+				handle.len() >= MinHandleLen::get() as usize &&
+					handle.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'_'),
+				Error::<T, I>::InvalidHandle
+			);
+			ensure!(!<Handles<T, I>>::contains_key(&handle), Error::<T, I>::HandleTaken);
+			ensure!(!<HandleOf<T, I>>::contains_key(&who), Error::<T, I>::AlreadyHasHandle);
+
+			let deposit = T::HandleDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T, I>::InsufficientBalance)?;
+			<HandleDeposits<T, I>>::insert(&who, deposit);
+			<Handles<T, I>>::insert(&handle, &who);
+			<HandleOf<T, I>>::insert(&who, &handle);
+
+			Self::deposit_event(RawEvent::HandleClaimed(who, handle));
+		}
+
+		/// Release the handle of the signer and return its deposit.
+		#[weight = T::WeightInfo::release_handle()]
+		pub fn release_handle(origin) {
+			let who = ensure_signed(origin)?;
+
+			let handle = <HandleOf<T, I>>::take(&who).ok_or(Error::<T, I>::NoHandle)?;
+			<Handles<T, I>>::remove(&handle);
+			T::Currency::unreserve(&who, <HandleDeposits<T, I>>::take(&who));
+
+			Self::deposit_event(RawEvent::HandleReleased(who, handle));
+		}
+
+		/// Subscribe the signer to premium for `PremiumPeriod` blocks, reserving `PremiumFee`.
+		///
+		/// Renewing a subscription, expired or not, restarts the period from now without
+		/// reserving the fee again.
+		#[weight = T::WeightInfo::subscribe_premium()]
+		pub fn subscribe_premium(origin) {
+			let who = ensure_signed(origin)?;
+
+			if !<Premium<T, I>>::contains_key(&who) {
+				let fee = T::PremiumFee::get();
+				T::Currency::reserve(&who, fee).map_err(|_| Error::<T, I>::InsufficientBalance)?;
+				<PremiumDeposits<T, I>>::insert(&who, fee);
+			}
+			let expires_at = <frame_system::Module<T>>::block_number().saturating_add(T::PremiumPeriod::get());
+			<Premium<T, I>>::insert(&who, expires_at);
+
+			Self::deposit_event(RawEvent::PremiumSubscribed(who, expires_at));
+		}
+
+		/// Cancel the premium subscription of the signer and return its fee.
+		#[weight = T::WeightInfo::cancel_premium()]
+		pub fn cancel_premium(origin) {
+			let who = ensure_signed(origin)?;
+
+			<Premium<T, I>>::take(&who).ok_or(Error::<T, I>::NotPremium)?;
+			let fee = <PremiumDeposits<T, I>>::take(&who);
+			T::Currency::unreserve(&who, fee);
+
+			Self::deposit_event(RawEvent::PremiumCancelled(who, fee));
+		}
+
+		/// Like a tweet, which can move it into `TopTweets`.
+		#[weight = T::WeightInfo::like()]
+		pub fn like(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			ensure!(!tweet.hidden, Error::<T, I>::TweetHidden);
+			Self::ensure_visible(&tweet, &who)?;
+			ensure!(!Self::likes(tweet_id, &who), Error::<T, I>::AlreadyLiked);
+
+			<Likes<T, I>>::insert(tweet_id, &who, true);
+			<AccountLikes<T, I>>::insert(&who, tweet_id, ());
+			<AccountLikeCount<T, I>>::mutate(&who, |count| *count = count.saturating_add(1));
+			<LikePreview<T, I>>::mutate(tweet_id, |preview| Self::add_to_preview(preview, &who));
+			let likes = <LikeCount<I>>::mutate(tweet_id, |count| {
+				*count = count.saturating_add(1);
+				*count
+			});
+			Self::rank_tweet(tweet_id, likes);
+			Self::notify(&tweet.author, &who, Notification::Liked(who.clone(), tweet_id));
+
+			Self::deposit_event(RawEvent::Liked(who, tweet_id));
+		}
+
+		/// Take back a like.
+		///
+		/// A tweet moving down in `TopTweets` is not replaced by a tweet outside of it that now
+		/// has more likes, such a tweet gets back on the board when it is liked again.
+		#[weight = T::WeightInfo::unlike()]
+		pub fn unlike(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::likes(tweet_id, &who), Error::<T, I>::NotLiked);
+
+			Self::remove_like(&who, tweet_id);
+
+			Self::deposit_event(RawEvent::Unliked(who, tweet_id));
+		}
+
+		/// React to a tweet, replacing the previous reaction of the signer if any.
+		#[weight = T::WeightInfo::react()]
+		pub fn react(origin, tweet_id: TweetId, reaction: Reaction) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			Self::ensure_visible(&tweet, &who)?;
+			let previous = Self::reactions(tweet_id, &who);
+			ensure!(previous != Some(reaction), Error::<T, I>::AlreadyReacted);
+
+			<Reactions<T, I>>::insert(tweet_id, &who, reaction);
+			<ReactionCounts<I>>::mutate(tweet_id, |counts| {
+				if let Some(previous) = previous {
+					counts[previous as usize] = counts[previous as usize].saturating_sub(1);
+				}
+				counts[reaction as usize] = counts[reaction as usize].saturating_add(1);
+			});
+
+			Self::deposit_event(RawEvent::Reacted(who, tweet_id, reaction));
+		}
+
+		/// Take back a reaction.
+		#[weight = T::WeightInfo::remove_reaction()]
+		pub fn remove_reaction(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let reaction = <Reactions<T, I>>::take(tweet_id, &who).ok_or(Error::<T, I>::NoReaction)?;
+			<ReactionCounts<I>>::mutate_exists(tweet_id, |counts| {
+				let mut remaining = counts.unwrap_or_default();
+				remaining[reaction as usize] = remaining[reaction as usize].saturating_sub(1);
+				*counts = Some(remaining).filter(|remaining| remaining.iter().any(|&count| count > 0));
+			});
+
+			Self::deposit_event(RawEvent::ReactionRemoved(who, tweet_id, reaction));
+		}
+
+		#[weight = T::WeightInfo::follow()]
+		pub fn follow(origin, target: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(who != target, Error::<T, I>::CannotFollowSelf);
+			ensure!(!<Following<T, I>>::contains_key(&who, &target), Error::<T, I>::AlreadyFollowing);
+
+			<Following<T, I>>::insert(&who, &target, ());
+			<Followers<T, I>>::insert(&target, &who, ());
+			<FollowingCount<T, I>>::mutate(&who, |count| *count = count.saturating_add(1));
+			let followers = <FollowerCount<T, I>>::mutate(&target, |count| {
+				*count = count.saturating_add(1);
+				*count
+			});
+
+			Self::deposit_event(RawEvent::Followed(who, target.clone()));
+			if Self::is_follower_milestone(followers) {
+				Self::deposit_event(RawEvent::FollowerMilestone(target, followers));
+			}
+		}
+
+		#[weight = T::WeightInfo::unfollow()]
+		pub fn unfollow(origin, target: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Following<T, I>>::contains_key(&who, &target), Error::<T, I>::NotFollowing);
+
+			<Following<T, I>>::remove(&who, &target);
+			<Followers<T, I>>::remove(&target, &who);
+			<FollowingCount<T, I>>::mutate(&who, |count| *count = count.saturating_sub(1));
+			<FollowerCount<T, I>>::mutate(&target, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(RawEvent::Unfollowed(who, target));
+		}
+
+		/// Take down a tweet, keeping it as a tombstone with blank text so that the comments and
+		/// retweets referring to it still resolve.
+		///
+		/// The origin must be `ForceRemoveOrigin`. The deposit of the author is slashed or
+		/// returned according to `SlashCensoredDeposit`, and the media, poll, edit history, gated
+		/// text, off-chain index entry and reports of the tweet are dropped. The tombstone can no
+		/// longer be edited, commented on or retweeted.
+		#[weight = T::WeightInfo::force_remove_tweet(T::MaxHashtagsPerTweet::get())]
+		pub fn force_remove_tweet(origin, tweet_id: TweetId) {
+			T::ForceRemoveOrigin::ensure_origin(origin)?;
+
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+
+			<Tweets<T, I>>::try_mutate_exists(tweet_id, |maybe_tweet| -> DispatchResult {
+				let tweet = maybe_tweet.as_mut().ok_or(Error::<T, I>::TweetNotFound)?;
+
+				Self::unindex_hashtags(tweet_id, &tweet.text);
+				tweet.text = Default::default();
+				tweet.media = Default::default();
+				tweet.comments_enabled = false;
+				<EditHistory<T, I>>::remove_prefix(tweet_id);
+				tweet.revision = 0;
+
+				let deposit = <TweetDeposits<T, I>>::take(tweet_id);
+				if T::SlashCensoredDeposit::get() {
+					let _ = T::Currency::slash_reserved(&tweet.author, deposit);
+				} else {
+					T::Currency::unreserve(&tweet.author, deposit);
+				}
+				Ok(())
+			})?;
+
+			<Censored<I>>::insert(tweet_id, ());
+			Self::rank_tweet(tweet_id, 0);
+			<Reports<T, I>>::remove_prefix(tweet_id);
+			<ReportCount<I>>::remove(tweet_id);
+			<Polls<T, I>>::remove(tweet_id);
+			<PollResults<I>>::remove(tweet_id);
+			<PollVotes<T, I>>::remove_prefix(tweet_id);
+
+			Self::clear_announcement(tweet_id);
+			Self::clear_gate(tweet_id);
+			sp_io::offchain_index::clear(&Self::offchain_key(tweet_id));
+
+			Self::deposit_event(RawEvent::TweetCensored(tweet_id));
+		}
+
+		/// Set the tweet every client should show as the official announcement of the chain, or
+		/// clear it with `None`.
+		///
+		/// The origin must be `AnnouncementOrigin`. The announcement is cleared by itself when
+		/// its tweet is deleted or taken down.
+		#[weight = T::WeightInfo::set_global_announcement()]
+		pub fn set_global_announcement(origin, tweet_id: Option<TweetId>) {
+			T::AnnouncementOrigin::ensure_origin(origin)?;
+
+			match tweet_id {
+				Some(tweet_id) => {
+					ensure!(<Tweets<T, I>>::contains_key(tweet_id), Error::<T, I>::TweetNotFound);
+					ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+					ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+
+					<GlobalAnnouncement<I>>::put(tweet_id);
+					Self::deposit_event(RawEvent::AnnouncementSet(tweet_id));
+				}
+				None => {
+					<GlobalAnnouncement<I>>::kill();
+					Self::deposit_event(RawEvent::AnnouncementCleared);
+				}
+			}
+		}
+
+		/// Create a public tweet of `author` with the id `id`, reserved for system tweets.
+		///
+		/// The origin must be root. No deposit is reserved and no rate limit applies.
+		#[weight = T::WeightInfo::force_create_tweet(text.len() as u32, T::MaxHashtagsPerTweet::get())]
+		pub fn force_create_tweet(origin, id: TweetId, author: T::AccountId, text: Vec<u8>) {
+			ensure_root(origin)?;
+
+			ensure!(id < T::FirstUserTweetId::get(), Error::<T, I>::IdNotReserved);
+			ensure!(!<Tweets<T, I>>::contains_key(id), Error::<T, I>::IdAlreadyUsed);
+			Self::insert_system_tweet(id, &author, text)?;
+
+			Self::on_tweet_created(&author, id, TweetKind::Tweet);
+			Self::deposit_event(RawEvent::SystemTweetCreated(author, id));
+		}
+
+		/// Set the maximum length of the text of new tweets, retweets and comments, in
+		/// characters. Existing tweets are kept whatever their length.
+		///
+		/// The origin must be root. The limit must be between 1 and `MaxTextBytes`.
+		#[weight = T::WeightInfo::set_text_limit()]
+		pub fn set_text_limit(origin, new_limit: u32) {
+			ensure_root(origin)?;
+
+			ensure!((1..=T::MaxTextBytes::get()).contains(&new_limit), Error::<T, I>::InvalidTextLimit);
+
+			<TextLimit<I>>::put(new_limit);
+
+			Self::deposit_event(RawEvent::TextLimitChanged(new_limit));
+		}
+
+		/// Transfer `amount` from the signer to the author of a tweet.
+		#[weight = T::WeightInfo::tip()]
+		pub fn tip(origin, tweet_id: TweetId, #[compact] amount: BalanceOf<T, I>) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroTip);
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			ensure!(tweet.author != who, Error::<T, I>::CannotTipSelf);
+			ensure!(Self::settings(&tweet.author).accepts_tips, Error::<T, I>::TipsDisabled);
+
+			T::Currency::transfer(&who, &tweet.author, amount, ExistenceRequirement::KeepAlive)?;
+			<TipTotal<T, I>>::mutate(tweet_id, |total| *total = total.saturating_add(amount));
+			let earnings = <AuthorEarnings<T, I>>::mutate(&tweet.author, |earnings| {
+				*earnings = earnings.saturating_add(amount);
+				*earnings
+			});
+			Self::rank_earner(&tweet.author, earnings);
+
+			Self::deposit_event(RawEvent::Tipped(who, tweet_id, amount));
+		}
+
+		/// Post a tweet carrying a poll that accepts votes up to and including block `close_at`.
+		#[weight = T::WeightInfo::new_poll_tweet(text.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn new_poll_tweet(origin, text: Vec<u8>, options: Vec<Vec<u8>>, close_at: T::BlockNumber) {
+			let author = ensure_signed(origin)?;
+
+			Self::transactional(|| {
+				let text = Self::ensure_text(&author, text)?;
+				let hashtags = Self::ensure_hashtags(&text)?;
+				ensure!(
+					options.len() >= MinPollOptions::get() as usize && options.len() <= MaxPollOptions::get() as usize,
+					Error::<T, I>::InvalidPollOptions
+				);
+				let options = options
+					.into_iter()
+					.map(PollOption::try_from)
+					.collect::<Result<Vec<_>, _>>()
+					.map_err(|_| Error::<T, I>::PollOptionTooLong)?;
+				let now = <frame_system::Module<T>>::block_number();
+				ensure!(close_at >= now, Error::<T, I>::PollClosed);
+				let deposit = Self::ensure_can_reserve(&author, &(&text, &options))?;
+				Self::ensure_tweet_capacity(&author, 1)?;
+				let rate = Self::ensure_rate_limit(&author, 1)?;
+
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let tweet = Tweet {
+					id: new_id,
+					create_at: now,
+					quote_tweet_id: None,
+					parent_id: None,
+					thread_prev: None,
+					text,
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media: Default::default(),
+					lang: None,
+					reply_policy: Self::settings(&author).reply_policy,
+					hidden: false,
+					conversation_id: new_id,
+					revision: 0,
+					community: None,
+				};
+				let poll = Poll {
+					votes: sp_std::vec![0; options.len()],
+					options: BoundedVec::truncate_from(options),
+					close_at,
+				};
+
+				Self::reserve_deposit(&author, new_id, deposit)?;
+				<TweetRate<T, I>>::insert(&author, rate);
+				Self::push_account_tweet(&author, new_id);
+				Self::index_hashtags(new_id, &hashtags);
+				Self::count_hashtags(&hashtags);
+				Self::notify_hashtag_followers(&author, new_id, &hashtags);
+				<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, tweet);
+				<Polls<T, I>>::insert(new_id, poll);
+
+				Self::on_tweet_created(&author, new_id, TweetKind::Tweet);
+				Self::deposit_event(RawEvent::PollCreated(author, new_id));
+				Ok(())
+			})?;
+		}
+
+		/// Vote for the option at `option_index` in the poll of a tweet.
+		///
+		/// An account has one vote per poll, which it can move to another option up to and
+		/// including `close_at`, whether or not the poll was closed by then.
+		#[weight = T::WeightInfo::vote()]
+		pub fn vote(origin, tweet_id: TweetId, option_index: u8) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			let previous = Self::poll_votes(tweet_id, &who);
+			ensure!(previous != Some(option_index), Error::<T, I>::AlreadyVoted);
+
+			<Polls<T, I>>::try_mutate(tweet_id, |maybe_poll| -> DispatchResult {
+				let poll = maybe_poll.as_mut().ok_or(Error::<T, I>::NoPoll)?;
+				let now = <frame_system::Module<T>>::block_number();
+				ensure!(now <= poll.close_at, Error::<T, I>::PollClosed);
+				ensure!((option_index as usize) < poll.votes.len(), Error::<T, I>::InvalidOption);
+
+				if let Some(old) = previous.and_then(|old| poll.votes.get_mut(old as usize)) {
+					*old = old.saturating_sub(1);
+				}
+				let votes = &mut poll.votes[option_index as usize];
+				*votes = votes.saturating_add(1);
+				Ok(())
+			})?;
+			<PollVotes<T, I>>::insert(tweet_id, &who, option_index);
+
+			Self::deposit_event(RawEvent::VoteCast(who, tweet_id, option_index));
+		}
+
+		/// Close the poll of a tweet once `close_at` is past, recording its final tallies in
+		/// `PollResults`.
+		///
+		/// Any account can close a poll, once.
+		#[weight = T::WeightInfo::close_poll()]
+		pub fn close_poll(origin, tweet_id: TweetId) {
+			ensure_signed(origin)?;
+
+			let poll = Self::polls(tweet_id).ok_or(Error::<T, I>::NoPoll)?;
+			ensure!(<frame_system::Module<T>>::block_number() > poll.close_at, Error::<T, I>::PollOpen);
+			ensure!(!<PollResults<I>>::contains_key(tweet_id), Error::<T, I>::PollAlreadyClosed);
+
+			<PollResults<I>>::insert(tweet_id, &poll.votes);
+
+			Self::deposit_event(RawEvent::PollResult(tweet_id, poll.votes));
+		}
+
+		/// Mint a tweet authored by the signer as a collectible, making the signer its first
+		/// owner in `TweetOwner`.
+		///
+		/// A tweet is minted once. Once its owner is another account, its author can no longer
+		/// delete it.
+		#[weight = T::WeightInfo::mint_tweet()]
+		pub fn mint_tweet(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(tweet.author == who, Error::<T, I>::NotAuthor);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			ensure!(!<TweetOwner<T, I>>::contains_key(tweet_id), Error::<T, I>::AlreadyMinted);
+
+			<TweetOwner<T, I>>::insert(tweet_id, &who);
+
+			Self::deposit_event(RawEvent::TweetMinted(who, tweet_id));
+		}
+
+		/// Give a minted tweet owned by the signer to `to`.
+		#[weight = T::WeightInfo::transfer_tweet()]
+		pub fn transfer_tweet(origin, tweet_id: TweetId, to: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::tweet_owner(tweet_id) == Some(who.clone()), Error::<T, I>::NotOwner);
+			ensure!(to != who, Error::<T, I>::NoChange);
+
+			<TweetOwner<T, I>>::insert(tweet_id, &to);
+
+			Self::deposit_event(RawEvent::TweetTransferred(tweet_id, who, to));
+		}
+
+		/// Grant or take back the verified badge of an account.
+		///
+		/// The origin must be `VerifyOrigin`.
+		#[weight = T::WeightInfo::set_verified()]
+		pub fn set_verified(origin, who: T::AccountId, verified: bool) {
+			T::VerifyOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::is_verified(&who) != verified, Error::<T, I>::NoChange);
+
+			if verified {
+				<Verified<T, I>>::insert(&who, true);
+			} else {
+				<Verified<T, I>>::remove(&who);
+			}
+
+			Self::deposit_event(RawEvent::VerificationChanged(who, verified));
+		}
+
+		/// Flag a tweet for the moderators.
+		///
+		/// The report counts toward `AutoHideThreshold` unless the signer is the author or first
+		/// tweeted less than `MinReporterAge` blocks ago. The tweet is hidden once it has
+		/// `AutoHideThreshold` counted reports.
+		#[weight = T::WeightInfo::report_tweet()]
+		pub fn report_tweet(origin, tweet_id: TweetId, reason: Reason) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!<Reports<T, I>>::contains_key(tweet_id, &who), Error::<T, I>::AlreadyReported);
+
+			<Reports<T, I>>::insert(tweet_id, &who, reason);
+			let counted = Self::report_counts(&tweet, &who);
+			let count = <ReportCount<I>>::mutate(tweet_id, |count| {
+				if counted {
+					*count = count.saturating_add(1);
+				}
+				*count
+			});
+
+			Self::deposit_event(RawEvent::TweetReported(who, tweet_id, reason));
+			let threshold = T::AutoHideThreshold::get();
+			if threshold > 0 && count >= threshold && !tweet.hidden {
+				<Tweets<T, I>>::insert(tweet_id, TweetOf::<T, I> { hidden: true, ..tweet });
+				Self::deposit_event(RawEvent::TweetAutoHidden(tweet_id));
+			}
+		}
+
+		/// Settle the reports against a tweet, either dropping them or deleting the tweet.
+		///
+		/// The origin must be `ModerationOrigin`. A tweet whose reports are dismissed is no longer
+		/// hidden. A removed tweet is left as a tombstone as by `delete_tweet`, so its deposit is
+		/// returned and its comments and quotes still resolve.
+		#[weight = T::WeightInfo::resolve_report(T::MaxHashtagsPerTweet::get())]
+		pub fn resolve_report(origin, tweet_id: TweetId, action: ModerationAction) {
+			T::ModerationOrigin::ensure_origin(origin)?;
+
+			// Reports that do not count towards `ReportCount` are settled as well.
+			ensure!(<Reports<T, I>>::iter_prefix(tweet_id).next().is_some(), Error::<T, I>::NoReports);
+
+			match action {
+				ModerationAction::Dismiss => {
+					<Reports<T, I>>::remove_prefix(tweet_id);
+					<ReportCount<I>>::remove(tweet_id);
+					<Tweets<T, I>>::mutate(tweet_id, |tweet| {
+						if let Some(tweet) = tweet {
+							tweet.hidden = false;
+						}
+					});
+
+					Self::deposit_event(RawEvent::ReportsDismissed(tweet_id));
+				}
+				ModerationAction::Remove => {
+					let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+					Self::delete_to_tombstone(tweet);
+
+					Self::deposit_event(RawEvent::TweetRemovedByModeration(tweet_id));
+				}
+			}
+		}
+
+		/// Remove all the data of the signer, at most `limit` items at a time.
+		///
+		/// The tweets of the signer are deleted as by `delete_tweet`, then its likes, follows,
+		/// blocks, bookmarks and followed hashtags are taken back, each counting as one item. Once
+		/// none is left its profile, handle, pin, settings and notifications are removed and their
+		/// deposits returned, and `PurgeComplete` is emitted. Until then `PurgeProgress` tells how many
+		/// items are left and the call can be repeated. Follows of other accounts, votes, reports,
+		/// community memberships, scheduled tweets and tweets owned by other accounts are kept, as
+		/// are the `AuthorEarnings` of the signer and its place on `TopEarners`.
+		///
+		/// The weight of the items left unprocessed is refunded.
+		#[weight = T::WeightInfo::purge_account(*limit)]
+		pub fn purge_account(origin, limit: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let purged = Self::purge_step(who, limit, false);
+			Ok(Some(T::WeightInfo::purge_account(purged)).into())
+		}
+
+		/// Remove all the data of `who` as `purge_account` does, at most `limit` items at a time.
+		/// Once none is left, its `AuthorEarnings` are dropped as well and it is taken off
+		/// `TopEarners`.
+		///
+		/// The origin must be `ForceRemoveOrigin`. The weight of the items left unprocessed is
+		/// refunded.
+		#[weight = T::WeightInfo::purge_account(*limit)]
+		pub fn force_purge_account(origin, who: T::AccountId, limit: u32) -> DispatchResultWithPostInfo {
+			T::ForceRemoveOrigin::ensure_origin(origin)?;
+
+			let purged = Self::purge_step(who, limit, true);
+			Ok(Some(T::WeightInfo::purge_account(purged)).into())
+		}
+
+		/// Remove up to `limit` tweets created before block `older_than`, oldest first, with
+		/// everything indexed under them.
+		///
+		/// The origin must be `PruneOrigin`. Pinned tweets and tweets still holding a deposit are
+		/// skipped and not considered again, `Pruned` tells how many tweets were removed and
+		/// skipped. At most `limit` blocks are scanned, and the weight of the tweets and blocks
+		/// left unprocessed is refunded.
+		#[weight = T::WeightInfo::prune_old_tweets(*limit)
+			.saturating_add(T::DbWeight::get().reads(*limit as Weight))]
+		pub fn prune_old_tweets(origin, older_than: T::BlockNumber, limit: u32) -> DispatchResultWithPostInfo {
+			T::PruneOrigin::ensure_origin(origin)?;
+
+			let (pruned, skipped, scanned) = Self::prune_tweets_before(older_than, limit);
+
+			Self::deposit_event(RawEvent::Pruned(pruned, skipped));
+			let actual_weight = T::WeightInfo::prune_old_tweets(pruned + skipped)
+				.saturating_add(T::DbWeight::get().reads(scanned as Weight));
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Annotate a tweet with a note for other accounts to rate.
+		#[weight = T::WeightInfo::add_note()]
+		pub fn add_note(origin, tweet_id: TweetId, text: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Tweets<T, I>>::contains_key(tweet_id), Error::<T, I>::TweetNotFound);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			let note_id = Self::note_count(tweet_id);
+			ensure!(note_id < T::MaxNotesPerTweet::get(), Error::<T, I>::TooManyNotes);
+			let text = Self::ensure_text(&who, text)?;
+
+			<Notes<T, I>>::insert(tweet_id, note_id, Note { author: who.clone(), text, up: 0, down: 0, shown: false });
+			<NoteCount<I>>::insert(tweet_id, note_id + 1);
+
+			Self::deposit_event(RawEvent::NoteAdded(who, tweet_id, note_id));
+		}
+
+		/// Rate a note as helpful or not, once per account.
+		///
+		/// The note is shown once it has `MinNoteRatings` ratings of which at least
+		/// `NoteShowThreshold` are helpful.
+		#[weight = T::WeightInfo::rate_note()]
+		pub fn rate_note(origin, tweet_id: TweetId, note_id: NoteId, helpful: bool) {
+			let who = ensure_signed(origin)?;
+
+			let mut note = Self::notes(tweet_id, note_id).ok_or(Error::<T, I>::NoteNotFound)?;
+			ensure!(note.author != who, Error::<T, I>::CannotRateOwnNote);
+			let key = (note_id, who.clone());
+			ensure!(!<NoteRatings<T, I>>::contains_key(tweet_id, &key), Error::<T, I>::AlreadyRated);
+
+			if helpful {
+				note.up = note.up.saturating_add(1);
+			} else {
+				note.down = note.down.saturating_add(1);
+			}
+			let ratings = note.up.saturating_add(note.down);
+			let newly_shown = !note.shown
+				&& ratings >= T::MinNoteRatings::get()
+				&& Perbill::from_rational_approximation(note.up, ratings) >= T::NoteShowThreshold::get();
+			note.shown |= newly_shown;
+			<NoteRatings<T, I>>::insert(tweet_id, &key, helpful);
+			<Notes<T, I>>::insert(tweet_id, note_id, note);
+
+			Self::deposit_event(RawEvent::NoteRated(who, tweet_id, note_id, helpful));
+			if newly_shown {
+				Self::deposit_event(RawEvent::NoteShown(tweet_id, note_id));
+			}
+		}
+
+		/// Create a community owned by the signer, who becomes its first member.
+		///
+		/// At most `MaxCommunities` communities can ever be created.
+		#[weight = T::WeightInfo::create_community()]
+		pub fn create_community(origin, name: Vec<u8>) {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(!name.is_empty(), Error::<T, I>::InvalidCommunityName);
+			let name = CommunityName::try_from(name).map_err(|_| Error::<T, I>::InvalidCommunityName)?;
+			let id = Self::next_community_id();
+			ensure!(id < T::MaxCommunities::get(), Error::<T, I>::TooManyCommunities);
+
+			<Communities<T, I>>::insert(id, Community { id, name, owner: owner.clone() });
+			<NextCommunityId<I>>::put(id + 1);
+			<Members<T, I>>::insert(id, &owner, ());
+			<MemberCount<I>>::insert(id, 1);
+
+			Self::deposit_event(RawEvent::CommunityCreated(owner, id));
+		}
+
+		/// Join a community, so that the signer can post to it.
+		#[weight = T::WeightInfo::join_community()]
+		pub fn join_community(origin, community_id: CommunityId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Communities<T, I>>::contains_key(community_id), Error::<T, I>::CommunityNotFound);
+			ensure!(!<Members<T, I>>::contains_key(community_id, &who), Error::<T, I>::AlreadyMember);
+			let count = Self::member_count(community_id);
+			ensure!(count < T::MaxMembersTracked::get(), Error::<T, I>::TooManyMembers);
+
+			<Members<T, I>>::insert(community_id, &who, ());
+			<MemberCount<I>>::insert(community_id, count + 1);
+
+			Self::deposit_event(RawEvent::CommunityJoined(who, community_id));
+		}
+
+		/// Leave a community. Tweets already posted to it stay in its feed.
+		#[weight = T::WeightInfo::leave_community()]
+		pub fn leave_community(origin, community_id: CommunityId) {
+			let who = ensure_signed(origin)?;
+
+			Self::remove_from_community(community_id, who)?;
+		}
+
+		/// Remove `who` from a community owned by the signer.
+		#[weight = T::WeightInfo::remove_member()]
+		pub fn remove_member(origin, community_id: CommunityId, who: T::AccountId) {
+			let owner = ensure_signed(origin)?;
+
+			let community = Self::communities(community_id).ok_or(Error::<T, I>::CommunityNotFound)?;
+			ensure!(community.owner == owner, Error::<T, I>::NotCommunityOwner);
+
+			Self::remove_from_community(community_id, who)?;
+		}
+
+		/// Post a public tweet to a community the signer is a member of.
+		///
+		/// The tweet is posted as by `new_tweet`, `Tweeted` is followed by `CommunityPosted`.
+		///
+		/// The weight of indexing hashtags the text does not contain is refunded.
+		#[weight = T::WeightInfo::post_to_community(text.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn post_to_community(origin, community_id: CommunityId, text: Vec<u8>) -> DispatchResultWithPostInfo {
+			let author = ensure_signed(origin)?;
+
+			ensure!(<Communities<T, I>>::contains_key(community_id), Error::<T, I>::CommunityNotFound);
+			ensure!(<Members<T, I>>::contains_key(community_id, &author), Error::<T, I>::NotAMember);
+			let text_len = text.len() as u32;
+			let (new_id, hashtags) = Self::post_tweet(
+				author.clone(), text, Vec::new(), true, Visibility::Public, Vec::new(), None, ReplyPolicy::Everyone, Some(community_id), true,
+			)?;
+
+			Self::deposit_event(RawEvent::CommunityPosted(author.clone(), community_id, new_id));
+			let actual_weight = T::WeightInfo::post_to_community(text_len, hashtags)
+				.saturating_add(Self::hashtag_fanout_weight(hashtags));
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Self::tweet_pays_fee(&author) })
+		}
+
+		/// Commit to an anonymous tweet by its `hash`, `blake2_256(text ++ salt)`, reserving
+		/// `CommitDeposit`.
+		///
+		/// The tweet can be revealed with `reveal_tweet` for `RevealWindow` blocks, after which
+		/// the commitment expires and the deposit is forfeited.
+		#[weight = T::WeightInfo::commit_tweet()]
+		pub fn commit_tweet(origin, hash: [u8; 32]) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!<Commitments<T, I>>::contains_key(hash) && !<RevealedTweet<I>>::contains_key(hash),
+				Error::<T, I>::AlreadyCommitted
+			);
+			let now = <frame_system::Module<T>>::block_number();
+			let count = Self::commit_count(now);
+			ensure!(count < T::MaxCommitsPerBlock::get(), Error::<T, I>::TooManyCommitments);
+			let deposit = T::CommitDeposit::get();
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T, I>::InsufficientBalance)?;
+
+			let expires_at = now.saturating_add(T::RevealWindow::get()).saturating_add(One::one());
+			<Commitments<T, I>>::insert(hash, (&who, now, deposit));
+			<CommitmentsExpiringAt<T, I>>::insert(expires_at, hash, ());
+			<CommitCount<T, I>>::insert(now, count + 1);
+
+			Self::deposit_event(RawEvent::TweetCommitted(who, hash));
+		}
+
+		/// Reveal the anonymous tweet committed to as `blake2_256(text ++ salt)`, posting it as a
+		/// public tweet of `AnonymousAccount` and returning the deposit to the committer.
+		///
+		/// Anyone can reveal a tweet, up to and including block `RevealWindow` after its
+		/// commitment. `TweetCommitment` records the block of the commitment.
+		///
+		/// `AnonymousAccount` is exempt from `MaxTweetsPerAccount` and the rate limit, which would
+		/// otherwise let the first anonymous tweets lock out all the later ones. The revealed tweet
+		/// holds no deposit, so `prune_old_tweets` can remove it; until then the storage it takes
+		/// is bounded by the `MaxCommitsPerBlock` commitments of a block, each of which held
+		/// `CommitDeposit`.
+		#[weight = T::WeightInfo::reveal_tweet(text.len() as u32, T::MaxHashtagsPerTweet::get())
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn reveal_tweet(origin, text: Vec<u8>, salt: [u8; 32]) {
+			ensure_signed(origin)?;
+
+			let hash = blake2_256(&[&text[..], &salt[..]].concat());
+			ensure!(!<RevealedTweet<I>>::contains_key(hash), Error::<T, I>::AlreadyRevealed);
+			let (committer, committed_at, deposit) = Self::commitments(hash).ok_or(Error::<T, I>::CommitmentNotFound)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now <= committed_at.saturating_add(T::RevealWindow::get()), Error::<T, I>::RevealWindowExpired);
+			let author = T::AnonymousAccount::get();
+			let text = Self::ensure_text(&author, text)?;
+			let hashtags = Self::ensure_hashtags(&text)?;
+
+			let new_id = Self::transactional(|| {
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let tweet = Tweet {
+					id: new_id,
+					create_at: now,
+					quote_tweet_id: None,
+					parent_id: None,
+					thread_prev: None,
+					text,
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media: Default::default(),
+					lang: None,
+					reply_policy: ReplyPolicy::Everyone,
+					hidden: false,
+					conversation_id: new_id,
+					revision: 0,
+					community: None,
+				};
+
+				Self::push_account_tweet(&author, new_id);
+				Self::index_hashtags(new_id, &hashtags);
+				Self::count_hashtags(&hashtags);
+				Self::notify_hashtag_followers(&author, new_id, &hashtags);
+				<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, tweet);
+				<Commitments<T, I>>::remove(hash);
+				<CommitmentsExpiringAt<T, I>>::remove(committed_at.saturating_add(T::RevealWindow::get()).saturating_add(One::one()), hash);
+				<RevealedTweet<I>>::insert(hash, new_id);
+				<TweetCommitment<T, I>>::insert(new_id, (hash, committed_at));
+				Ok(new_id)
+			})?;
+			T::Currency::unreserve(&committer, deposit);
+
+			Self::deposit_event(RawEvent::Tweeted(author.clone(), new_id, None, false));
+			Self::deposit_event(RawEvent::TweetRevealed(new_id, hash));
+			Self::on_tweet_created(&author, new_id, TweetKind::Tweet);
+		}
+
+		/// Set the defaults applied to the future tweets of the signer and to the interactions
+		/// with them.
+		///
+		/// Tweets already posted keep their reply policy.
+		#[weight = T::WeightInfo::update_settings()]
+		pub fn update_settings(origin, settings: AccountSettings) {
+			let who = ensure_signed(origin)?;
+
+			if settings == AccountSettings::default() {
+				<Settings<T, I>>::remove(&who);
+			} else {
+				<Settings<T, I>>::insert(&who, settings);
+			}
+
+			Self::deposit_event(RawEvent::SettingsUpdated(who));
+		}
+
+		/// Post a public tweet showing `teaser_text`, whose `full_text` is for the accounts that
+		/// pay `price` to its author with `unlock`.
+		///
+		/// Only the accounts that unlocked the tweet can comment on it. The deposit covers both
+		/// texts, and only the hashtags of the teaser are indexed.
+		#[weight = T::WeightInfo::new_gated_tweet(
+			teaser_text.len().saturating_add(full_text.len()) as u32,
+			T::MaxHashtagsPerTweet::get(),
+		).saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()))]
+		pub fn new_gated_tweet(origin, teaser_text: Vec<u8>, full_text: Vec<u8>, #[compact] price: BalanceOf<T, I>) {
+			let author = ensure_signed(origin)?;
+
+			Self::transactional(|| {
+				ensure!(!price.is_zero(), Error::<T, I>::ZeroPrice);
+				let text = Self::ensure_text(&author, teaser_text)?;
+				let full_text = Self::ensure_text(&author, full_text)?;
+				let hashtags = Self::ensure_hashtags(&text)?;
+				let deposit = Self::ensure_can_reserve(&author, &(&text, &full_text))?;
+				Self::ensure_tweet_capacity(&author, 1)?;
+				let rate = Self::ensure_rate_limit(&author, 1)?;
+
+				let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+				let tweet = Tweet {
+					id: new_id,
+					create_at: <frame_system::Module<T>>::block_number(),
+					quote_tweet_id: None,
+					parent_id: None,
+					thread_prev: None,
+					text,
+					author: author.clone(),
+					comments_enabled: true,
+					visibility: Visibility::Public,
+					media: Default::default(),
+					lang: None,
+					reply_policy: Self::settings(&author).reply_policy,
+					hidden: false,
+					conversation_id: new_id,
+					revision: 0,
+					community: None,
+				};
+
+				Self::reserve_deposit(&author, new_id, deposit)?;
+				<TweetRate<T, I>>::insert(&author, rate);
+				Self::push_account_tweet(&author, new_id);
+				Self::index_hashtags(new_id, &hashtags);
+				Self::count_hashtags(&hashtags);
+				Self::notify_hashtag_followers(&author, new_id, &hashtags);
+				<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+				<Tweets<T, I>>::insert(new_id, tweet);
+				<GatedContent<T, I>>::insert(new_id, full_text);
+				<GatePrice<T, I>>::insert(new_id, price);
+
+				Self::on_tweet_created(&author, new_id, TweetKind::Tweet);
+				Self::deposit_event(RawEvent::GatedTweetCreated(author, new_id, price));
+				Ok(())
+			})?;
+		}
+
+		/// Pay the price of a gated tweet to its author to read it in full and comment on it.
+		///
+		/// The author unlocks its own tweets for free. A tweet can be unlocked once per account.
+		#[weight = T::WeightInfo::unlock()]
+		pub fn unlock(origin, tweet_id: TweetId) {
+			let who = ensure_signed(origin)?;
+
+			let tweet = Self::tweets(tweet_id).ok_or(Error::<T, I>::TweetNotFound)?;
+			ensure!(!<Censored<I>>::contains_key(tweet_id), Error::<T, I>::Censored);
+			ensure!(!Self::is_deleted(tweet_id), Error::<T, I>::TweetDeleted);
+			let price = Self::gate_price(tweet_id).ok_or(Error::<T, I>::NotGated)?;
+			ensure!(!<Unlocked<T, I>>::contains_key(tweet_id, &who), Error::<T, I>::AlreadyUnlocked);
+
+			let paid = if tweet.author == who {
+				Zero::zero()
+			} else {
+				T::Currency::transfer(&who, &tweet.author, price, ExistenceRequirement::KeepAlive)?;
+				price
+			};
+			<Unlocked<T, I>>::insert(tweet_id, &who, ());
+
+			Self::deposit_event(RawEvent::TweetUnlocked(who, tweet_id, paid));
+		}
+
+		/// Set the price of a subscription of `SubscriptionPeriod` blocks to the signer, or stop
+		/// taking new subscriptions and renewals with `None`. Running subscriptions are kept.
+		#[weight = T::WeightInfo::set_subscription_price()]
+		pub fn set_subscription_price(origin, price: Option<BalanceOf<T, I>>) {
+			let who = ensure_signed(origin)?;
+
+			match price {
+				Some(price) => {
+					ensure!(!price.is_zero(), Error::<T, I>::ZeroPrice);
+					<SubscriptionPrice<T, I>>::insert(&who, price);
+				}
+				None => <SubscriptionPrice<T, I>>::remove(&who),
+			}
+
+			Self::deposit_event(RawEvent::SubscriptionPriceSet(who, price));
+		}
+
+		/// Subscribe to `author` for `SubscriptionPeriod` blocks from now, paying its
+		/// `SubscriptionPrice` to it.
+		///
+		/// Subscribers see the followers-only tweets of the author and reply to them as its
+		/// followers do.
+		#[weight = T::WeightInfo::subscribe()]
+		pub fn subscribe(origin, author: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			ensure!(who != author, Error::<T, I>::CannotSubscribeSelf);
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(!Self::is_subscriber(&author, &who, now), Error::<T, I>::AlreadySubscribed);
+			let price = Self::subscription_price(&author).ok_or(Error::<T, I>::NoSubscriptionPrice)?;
+
+			T::Currency::transfer(&who, &author, price, ExistenceRequirement::KeepAlive)?;
+			let expires_at = now.saturating_add(T::SubscriptionPeriod::get());
+			<Subscriptions<T, I>>::insert(&author, &who, expires_at);
+
+			Self::deposit_event(RawEvent::Subscribed(who, author, expires_at));
+		}
+
+		/// Extend the subscription of the signer to `author` by `SubscriptionPeriod` blocks,
+		/// paying its current `SubscriptionPrice` to it. An expired subscription restarts from
+		/// now.
+		#[weight = T::WeightInfo::renew()]
+		pub fn renew(origin, author: T::AccountId) {
+			let who = ensure_signed(origin)?;
+
+			let expires_at = Self::subscription(&author, &who).ok_or(Error::<T, I>::NotSubscribed)?;
+			let price = Self::subscription_price(&author).ok_or(Error::<T, I>::NoSubscriptionPrice)?;
+
+			T::Currency::transfer(&who, &author, price, ExistenceRequirement::KeepAlive)?;
+			let now = <frame_system::Module<T>>::block_number();
+			let expires_at = expires_at.max(now).saturating_add(T::SubscriptionPeriod::get());
+			<Subscriptions<T, I>>::insert(&author, &who, expires_at);
+
+			Self::deposit_event(RawEvent::SubscriptionRenewed(who, author, expires_at));
+		}
+	}
+}
+
+impl<T: Trait<I>, I: Instance> Module<T, I> {
+	/// Ids of the tweets posted by an account that still exist, oldest first.
+	pub fn accounts(who: &T::AccountId) -> Vec<TweetId> {
+		(0..Self::account_tweet_count(who))
+			.filter_map(|index| Self::account_tweets(who, index))
+			.filter(|id| <Tweets<T, I>>::contains_key(*id) && !Self::is_deleted(*id))
+			.collect()
+	}
+
+	/// Tweets posted by an account that still exist and are not deleted, oldest first, skipping
+	/// the first `offset` of them and returning at most `limit`, itself capped at `MaxPageLen`.
+	pub fn tweets_page_of(who: &T::AccountId, offset: u32, limit: u32) -> Vec<TweetOf<T, I>> {
+		(0..Self::account_tweet_count(who))
+			.filter_map(|index| Self::account_tweets(who, index))
+			.filter(|id| !Self::is_deleted(*id))
+			.filter_map(|tweet_id| Self::tweets(tweet_id))
+			.skip(offset as usize)
+			.take(limit.min(MaxPageLen::get()) as usize)
+			.collect()
+	}
+
+	/// The most recent tweets that still exist and are not deleted, newest first, at most
+	/// `limit`, itself capped at `MaxPageLen`.
+	pub fn recent(limit: u32) -> Vec<TweetOf<T, I>> {
+		Self::recent_tweets()
+			.iter()
+			.rev()
+			.filter(|id| !Self::is_deleted(**id))
+			.filter_map(|id| Self::tweets(*id))
+			.take(limit.min(MaxPageLen::get()) as usize)
+			.collect()
+	}
+
+	/// Tweets posted by an account that still exist and are not deleted, newest first, skipping
+	/// the first `offset` of them and returning at most `limit`, itself capped at `MaxPageLen`.
+	pub fn account_timeline(who: &T::AccountId, offset: u32, limit: u32) -> Vec<TweetOf<T, I>> {
+		(0..Self::account_tweet_count(who))
+			.rev()
+			.filter_map(|index| Self::account_tweets(who, index))
+			.filter(|id| !Self::is_deleted(*id))
+			.filter_map(|tweet_id| Self::tweets(tweet_id))
+			.skip(offset as usize)
+			.take(limit.min(MaxPageLen::get()) as usize)
+			.collect()
+	}
+
+	/// Whether the tweet quoted by retweet `retweet_id` changed since it was quoted, by an edit
+	/// or its deletion, `None` if the retweet or the quoted tweet no longer exists or the
+	/// retweet predates `QuoteSnapshots`.
+	pub fn quote_is_stale(retweet_id: TweetId) -> Option<bool> {
+		let snapshot = Self::quote_snapshot(retweet_id)?;
+		let original = Self::tweets(Self::tweets(retweet_id)?.quote_tweet_id?)?;
+		Some(blake2_256(&original.text) != snapshot)
+	}
+
+	/// Ids of the tweets created from block `from` up to but excluding block `to`, in block order
+	/// and by id within a block, at most `limit`, itself capped at `MaxPageLen`. Tweets of block
+	/// `from` up to id `after` are left out.
+	///
+	/// At most `MaxRangeBlocks` blocks are scanned. Along with the ids comes `None` once the range
+	/// is covered, or the block and `after` to pass to list the rest of it.
+	///
+	/// Deleted and removed tweets are not listed.
+	pub fn tweets_in_range(
+		from: T::BlockNumber,
+		to: T::BlockNumber,
+		after: Option<TweetId>,
+		limit: u32,
+	) -> (Vec<TweetId>, Option<(T::BlockNumber, Option<TweetId>)>) {
+		let limit = limit.min(MaxPageLen::get()) as usize;
+		let mut ids = Vec::new();
+		let mut block = from;
+		let mut after = after;
+		let mut scanned = 0;
+		while block < to {
+			if ids.len() == limit || scanned == MaxRangeBlocks::get() {
+				return (ids, Some((block, after)));
+			}
+			let mut in_block: Vec<TweetId> = <TweetsByBlock<T, I>>::iter_prefix(block)
+				.map(|(id, ())| id)
+				.filter(|id| after.map_or(true, |after| *id > after) && !Self::is_deleted(*id))
+				.collect();
+			in_block.sort_unstable();
+			scanned += 1;
+			if in_block.len() > limit - ids.len() {
+				in_block.truncate(limit - ids.len());
+				after = in_block.last().copied();
+				ids.extend(in_block);
+				return (ids, Some((block, after)));
+			}
+			ids.extend(in_block);
+			after = None;
+			block += One::one();
+		}
+		(ids, None)
+	}
+
+	/// A page of the data owned by `who`, for backups and access requests.
+	///
+	/// `cursor` is `0` for the first page and the `next_cursor` of the previous page after that.
+	/// A page holds the tweets posted by `who` at the indices `cursor` to `cursor + limit` of
+	/// `AccountTweets`, `limit` being capped at `MaxPageLen`, skipping deleted ones.
+	pub fn export_account(who: &T::AccountId, cursor: u64, limit: u32) -> AccountExportOf<T, I> {
+		let count = Self::account_tweet_count(who);
+		let end = cursor.saturating_add(limit.min(MaxPageLen::get()) as u64).min(count);
+		let tweets = (cursor..end)
+			.filter_map(|index| Self::account_tweets(who, index))
+			.filter(|id| !Self::is_deleted(*id))
+			.filter_map(|tweet_id| Self::tweets(tweet_id))
+			.collect();
+		let next_cursor = if end < count { Some(end) } else { None };
+		if cursor > 0 {
+			return AccountExport {
+				tweets,
+				liked: Vec::new(),
+				bookmarked: Vec::new(),
+				following: Vec::new(),
+				profile: None,
+				handle: None,
+				settings: None,
+				next_cursor,
+			};
+		}
+
+		AccountExport {
+			tweets,
+			liked: <AccountLikes<T, I>>::iter_prefix(who)
+				.map(|(id, ())| id)
+				.filter(|id| Self::likes(id, who))
+				.collect(),
+			bookmarked: <Bookmarks<T, I>>::iter_prefix(who).map(|(id, ())| id).collect(),
+			following: <Following<T, I>>::iter_prefix(who).map(|(target, ())| target).collect(),
+			profile: Self::profiles(who),
+			handle: Self::handle_of(who),
+			settings: Some(Self::settings(who)),
+			next_cursor,
+		}
+	}
+
+	/// The tweets with the given ids, in the same order, with `None` for the ones that do not
+	/// exist or are deleted. Only the first `MaxPageLen` ids are looked up.
+	pub fn tweets_by_ids(ids: &[TweetId]) -> Vec<Option<TweetOf<T, I>>> {
+		ids.iter()
+			.take(MaxPageLen::get() as usize)
+			.map(|&id| Self::tweets(id).filter(|_| !Self::is_deleted(id)))
+			.collect()
+	}
+
+	/// Comments on a tweet that still exist, deleted ones included as tombstones, oldest first,
+	/// skipping the first `offset` of them and returning at most `limit`, itself capped at
+	/// `MaxPageLen`.
+	pub fn comments_page_of(tweet_id: TweetId, offset: u32, limit: u32) -> Vec<TweetOf<T, I>> {
+		(0..Self::next_comment_index(tweet_id))
+			.filter_map(|index| Self::comments(tweet_id, index))
+			.filter_map(|tweet_id| Self::tweets(tweet_id))
+			.skip(offset as usize)
+			.take(limit.min(MaxPageLen::get()) as usize)
+			.collect()
+	}
+
+	/// Ids of the comments on a tweet at the indices `start` to `start + len` of `Comments`, `len`
+	/// being capped at `MaxPageLen`, oldest first.
+	///
+	/// A comment keeps its index, so that pages stay stable: the comments at `start` onwards
+	/// are the same whatever is removed before them. Removed comments leave a gap which is
+	/// skipped, tombstones are kept. Neither the tweet nor its comments are decoded.
+	pub fn comments_page(tweet_id: TweetId, start: u32, len: u32) -> Vec<TweetId> {
+		let end = start.saturating_add(len.min(MaxPageLen::get())).min(Self::next_comment_index(tweet_id));
+		(start..end)
+			.filter_map(|index| Self::comments(tweet_id, index))
+			.filter(|id| <Tweets<T, I>>::contains_key(id))
+			.collect()
+	}
+
+	/// Followers of `who` after `start_key` in storage order, at most `limit` of them, itself
+	/// capped at `MaxPageLen`.
+	///
+	/// `start_key` is `None` for the first page, then the last follower of the previous page.
+	/// The order is that of the hashed keys of `Followers`, so that a page is read without
+	/// going through the ones before it.
+	pub fn followers_page(who: &T::AccountId, start_key: Option<T::AccountId>, limit: u32) -> Vec<T::AccountId> {
+		let prefix = [
+			&<Followers<T, I> as StoragePrefixedMap<()>>::final_prefix()[..],
+			&Blake2_128Concat::hash(&who.encode())[..],
+		].concat();
+		let mut key = start_key.map_or_else(|| prefix.clone(), |start| <Followers<T, I>>::hashed_key_for(who, start));
+		let mut followers = Vec::new();
+		while followers.len() < limit.min(MaxPageLen::get()) as usize {
+			match sp_io::storage::next_key(&key).filter(|next| next.starts_with(&prefix)) {
+				Some(next) => {
+					if let Ok(follower) = T::AccountId::decode(&mut Blake2_128Concat::reverse(&next[prefix.len()..])) {
+						followers.push(follower);
+					}
+					key = next;
+				}
+				None => break,
+			}
+		}
+		followers
+	}
+
+	/// Full text of the gated tweet `tweet_id` if `who` is its author or unlocked it, `None`
+	/// otherwise and for tweets that are not gated.
+	pub fn gated_text(tweet_id: TweetId, who: &T::AccountId) -> Option<TextOf<T, I>> {
+		Self::tweets(tweet_id)
+			.filter(|tweet| Self::has_unlocked(tweet, who))
+			.and_then(|_| Self::gated_content(tweet_id))
+	}
+
+	/// The texts a tweet had before each of its edits with the blocks of the edits, oldest
+	/// first. Empty for missing tweets and tombstones.
+	pub fn edit_history(id: TweetId) -> Vec<(T::BlockNumber, TextOf<T, I>)> {
+		let revisions = Self::tweets(id).map_or(0, |tweet| tweet.revision);
+		(0..revisions).filter_map(|revision| <EditHistory<T, I>>::get(id, revision)).collect()
+	}
+
+	/// Tweets of the conversation started by tweet `id` that still exist, deleted ones included
+	/// as tombstones, oldest first, skipping the first `offset` of them and returning at most
+	/// `limit`, itself capped at `MaxPageLen`.
+	///
+	/// Tweets of a conversation are posted in the order of their ids, so only the tweets of the
+	/// page are decoded.
+	pub fn conversation(id: TweetId, offset: u32, limit: u32) -> Vec<TweetOf<T, I>> {
+		let mut ids: Vec<TweetId> = <ConversationTweets<I>>::iter_prefix(id).map(|(tweet_id, _)| tweet_id).collect();
+		ids.sort_unstable();
+		ids.into_iter()
+			.skip(offset as usize)
+			.take(limit.min(MaxPageLen::get()) as usize)
+			.filter_map(|tweet_id| Self::tweets(tweet_id))
+			.collect()
+	}
+
+	/// Ids of the tweets posted to a community, newest first, skipping the first `offset` of them
+	/// and returning at most `limit`, itself capped at `MaxPageLen`.
+	pub fn community_feed(community_id: CommunityId, offset: u32, limit: u32) -> Vec<TweetId> {
+		let mut ids: Vec<TweetId> = <CommunityTweets<I>>::iter_prefix(community_id).map(|(id, _)| id).collect();
+		ids.sort_unstable_by(|a, b| b.cmp(a));
+		ids.into_iter()
+			.skip(offset as usize)
+			.take(limit.min(MaxPageLen::get()) as usize)
+			.collect()
+	}
+
+	/// Whether an account has a premium subscription that has not expired at block `now`.
+	pub fn is_premium(who: &T::AccountId, now: T::BlockNumber) -> bool {
+		Self::premium(who).map_or(false, |expires_at| now < expires_at)
+	}
+
+	/// Whether `who` has a paid subscription to `author` running at block `now`.
+	pub fn is_subscriber(author: &T::AccountId, who: &T::AccountId, now: T::BlockNumber) -> bool {
+		Self::subscription(author, who).map_or(false, |expires_at| now < expires_at)
+	}
+
+	/// Display name of the identity `Identity` has for an account, if any.
+	pub fn display_name_of(who: &T::AccountId) -> Option<Vec<u8>> {
+		T::Identity::display_name(who)
+	}
+
+	/// Whether an account carries the verified badge.
+	pub fn is_verified(who: &T::AccountId) -> bool {
+		<Verified<T, I>>::get(who)
+	}
+
+	/// Ids of the comments on a tweet, oldest first.
+	pub fn comments_of(tweet_id: TweetId) -> Vec<TweetId> {
+		(0..Self::next_comment_index(tweet_id))
+			.filter_map(|index| Self::comments(tweet_id, index))
+			.collect()
+	}
+
+	/// Ids of the tweets bookmarked by an account, oldest first.
+	pub fn bookmarks_of(who: &T::AccountId) -> Vec<TweetId> {
+		let mut ids: Vec<TweetId> = <Bookmarks<T, I>>::iter_prefix(who).map(|(id, ())| id).collect();
+		ids.sort_unstable();
+		ids
+	}
+
+	/// Hashtags followed by an account, without their `#`, in no particular order.
+	pub fn followed_hashtags(who: &T::AccountId) -> Vec<Vec<u8>> {
+		<FollowedHashtags<T, I>>::iter_prefix(who).map(|(tag, ())| tag).collect()
+	}
+
+	/// Ids of the tweets whose text contains `#tag`, oldest first. `tag` is given without the
+	/// `#` and matched case-insensitively.
+	pub fn hashtag_tweets(tag: &[u8]) -> Vec<TweetId> {
+		let mut ids: Vec<TweetId> = <HashtagTweets<I>>::iter_prefix(tag.to_ascii_lowercase())
+			.map(|(id, ())| id)
+			.collect();
+		ids.sort_unstable();
+		ids
+	}
+
+	/// Ids of the existing tweets tagged with language `lang`, oldest first.
+	pub fn tweets_in_lang(lang: LanguageCode) -> Vec<TweetId> {
+		let mut ids: Vec<TweetId> = <TweetsByLang<I>>::iter_prefix(lang)
+			.map(|(id, ())| id)
+			.collect();
+		ids.sort_unstable();
+		ids
+	}
+
+	/// Distinct hashtags in `text`, lowercased and without the leading `#`, in order of
+	/// appearance.
+	///
+	/// A hashtag is a `#` that does not follow a letter, digit or `_`, followed by one or more
+	/// ASCII letters, digits or `_`.
+	pub fn hashtags(text: &[u8]) -> Vec<Vec<u8>> {
+		let is_tag_char = |c: &u8| c.is_ascii_alphanumeric() || *c == b'_';
+		let mut tags: Vec<Vec<u8>> = Vec::new();
+		let mut i = 0;
+		while i < text.len() {
+			if text[i] == b'#' && (i == 0 || !is_tag_char(&text[i - 1])) {
+				let len = text[i + 1..].iter().take_while(|c| is_tag_char(c)).count();
+				let tag = text[i + 1..i + 1 + len].to_ascii_lowercase();
+				if !tag.is_empty() && !tags.contains(&tag) {
+					tags.push(tag);
+				}
+				i += 1 + len;
+			} else {
+				i += 1;
+			}
+		}
+		tags
+	}
+
+	/// Index of the `TrendingWindow` long window `block` falls in.
+	pub fn window_index(block: T::BlockNumber) -> T::BlockNumber {
+		block / T::TrendingWindow::get().max(One::one())
+	}
+
+	/// The `top_n` hashtags most used in `window`, with the number of tweets they were used in,
+	/// most used first and in alphabetical order among equally used ones.
+	pub fn trending(window: T::BlockNumber, top_n: u32) -> Vec<(Vec<u8>, u32)> {
+		let mut tags: Vec<(Vec<u8>, u32)> = <HashtagUsage<T, I>>::iter_prefix(window).collect();
+		tags.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+		tags.truncate(top_n as usize);
+		tags
+	}
+
+	/// Take back the like of `who` on `tweet_id`, moving the tweet in `TopTweets`.
+	fn remove_like(who: &T::AccountId, tweet_id: TweetId) {
+		<Likes<T, I>>::remove(tweet_id, who);
+		<AccountLikes<T, I>>::remove(who, tweet_id);
+		<AccountLikeCount<T, I>>::mutate(who, |count| *count = count.saturating_sub(1));
+		<LikePreview<T, I>>::mutate(tweet_id, |preview| preview.retain(|account| account != who));
+		let likes = <LikeCount<I>>::mutate(tweet_id, |count| {
+			*count = count.saturating_sub(1);
+			*count
+		});
+		Self::rank_tweet(tweet_id, likes);
+	}
+
+	/// Drop all the likes on `tweet_id`, taking it off `TopTweets`.
+	///
+	/// The entries of the likers in `AccountLikes` are left for `purge_items` to drop, so that
+	/// the cost does not grow with the number of likes.
+	fn clear_likes(tweet_id: TweetId) {
+		<Likes<T, I>>::remove_prefix(tweet_id);
+		<LikeCount<I>>::remove(tweet_id);
+		<LikePreview<T, I>>::remove(tweet_id);
+		Self::rank_tweet(tweet_id, 0);
+	}
+
+	/// Add `who` at the end of `preview` unless it is full or already holds `who`.
+	fn add_to_preview(preview: &mut PreviewOf<T>, who: &T::AccountId) {
+		if !preview.contains(who) {
+			let _ = preview.try_push(who.clone());
+		}
+	}
+
+	/// Drop the reactions to `tweet_id`.
+	fn clear_reactions(tweet_id: TweetId) {
+		<Reactions<T, I>>::remove_prefix(tweet_id);
+		<ReactionCounts<I>>::remove(tweet_id);
+	}
+
+	/// Drop the notes on `tweet_id` and their ratings.
+	fn clear_notes(tweet_id: TweetId) {
+		<Notes<T, I>>::remove_prefix(tweet_id);
+		<NoteCount<I>>::remove(tweet_id);
+		<NoteRatings<T, I>>::remove_prefix(tweet_id);
+	}
+
+	/// Number of items `purge_account` still has to remove for `who`.
+	fn purge_remaining(who: &T::AccountId) -> u32 {
+		let tweets = Self::account_tweet_count(who).min(u32::max_value() as u64) as u32;
+		tweets
+			.saturating_add(<AccountLikeCount<T, I>>::get(who))
+			.saturating_add(Self::following_count(who))
+			.saturating_add(Self::blocked_count(who))
+			.saturating_add(Self::bookmark_count(who))
+			.saturating_add(Self::followed_hashtag_count(who))
+	}
+
+	/// Remove up to `limit` tweets, likes, follows, blocks, bookmarks and followed hashtags of
+	/// `who`, in that order, returning how many were removed.
+	fn purge_items(who: &T::AccountId, limit: u32) -> u32 {
+		let mut purged = 0;
+		// The tweets, newest first so that `AccountTweetCount` follows.
+		while purged < limit {
+			let count = Self::account_tweet_count(who);
+			if count == 0 {
+				break;
+			}
+			if let Some(id) = <AccountTweets<T, I>>::take(who, count - 1) {
+				match Self::tweets(id) {
+					Some(tweet) if tweet.author == *who && !Self::is_deleted(id) && !Self::is_sold(&tweet) => {
+						Self::delete_to_tombstone(tweet);
+						Self::deposit_event(RawEvent::TweetDeleted(id, who.clone()));
+					}
+					_ => {}
+				}
+			}
+			<AccountTweetCount<T, I>>::insert(who, count - 1);
+			purged += 1;
+		}
+
+		let liked: Vec<TweetId> = <AccountLikes<T, I>>::iter_prefix(who)
+			.take((limit - purged) as usize)
+			.map(|(id, ())| id)
+			.collect();
+		for id in &liked {
+			if Self::likes(id, who) {
+				Self::remove_like(who, *id);
+			} else {
+				// Left behind by `clear_likes`.
+				<AccountLikes<T, I>>::remove(who, id);
+				<AccountLikeCount<T, I>>::mutate(who, |count| *count = count.saturating_sub(1));
+			}
+		}
+		purged += liked.len() as u32;
+
+		let followed: Vec<T::AccountId> = <Following<T, I>>::iter_prefix(who)
+			.take((limit - purged) as usize)
+			.map(|(target, ())| target)
+			.collect();
+		for target in &followed {
+			<Following<T, I>>::remove(who, target);
+			<Followers<T, I>>::remove(target, who);
+			<FollowingCount<T, I>>::mutate(who, |count| *count = count.saturating_sub(1));
+			<FollowerCount<T, I>>::mutate(target, |count| *count = count.saturating_sub(1));
+		}
+		purged += followed.len() as u32;
+
+		let blocked: Vec<T::AccountId> = <Blocked<T, I>>::iter_prefix(who)
+			.take((limit - purged) as usize)
+			.map(|(target, ())| target)
+			.collect();
+		for target in &blocked {
+			<Blocked<T, I>>::remove(who, target);
+			<BlockedCount<T, I>>::mutate(who, |count| *count = count.saturating_sub(1));
+		}
+		purged += blocked.len() as u32;
+
+		let bookmarked: Vec<TweetId> = <Bookmarks<T, I>>::iter_prefix(who)
+			.take((limit - purged) as usize)
+			.map(|(id, ())| id)
+			.collect();
+		for id in &bookmarked {
+			<Bookmarks<T, I>>::remove(who, id);
+			<BookmarkCount<T, I>>::mutate(who, |count| *count = count.saturating_sub(1));
+		}
+		purged += bookmarked.len() as u32;
+
+		let tags: Vec<Vec<u8>> = <FollowedHashtags<T, I>>::iter_prefix(who)
+			.take((limit - purged) as usize)
+			.map(|(tag, ())| tag)
+			.collect();
+		for tag in &tags {
+			<FollowedHashtags<T, I>>::remove(who, tag);
+			<HashtagFollowers<T, I>>::remove(tag, who);
+			<FollowedHashtagCount<T, I>>::mutate(who, |count| *count = count.saturating_sub(1));
+		}
+		purged + tags.len() as u32
+	}
+
+	/// Remove up to `limit` items of `who`, then the rest of its data once none is left, and its
+	/// earnings too if `forced`. Returns the number of items removed.
+	fn purge_step(who: T::AccountId, limit: u32, forced: bool) -> u32 {
+		let purged = Self::purge_items(&who, limit);
+		let remaining = Self::purge_remaining(&who);
+		if remaining == 0 {
+			Self::purge_singletons(&who);
+			if forced {
+				<AuthorEarnings<T, I>>::remove(&who);
+				Self::rank_earner(&who, Zero::zero());
+			}
+			Self::deposit_event(RawEvent::PurgeComplete(who));
+		} else {
+			Self::deposit_event(RawEvent::PurgeProgress(who, remaining));
+		}
+		purged
+	}
+
+	/// Remove the data of `who` left once `purge_items` is done, returning its deposits.
+	fn purge_singletons(who: &T::AccountId) {
+		<AccountTweetCount<T, I>>::remove(who);
+		<FirstTweetAt<T, I>>::remove(who);
+		<AccountLikeCount<T, I>>::remove(who);
+		<FollowingCount<T, I>>::remove(who);
+		<BlockedCount<T, I>>::remove(who);
+		<BookmarkCount<T, I>>::remove(who);
+		<TweetCountOf<T, I>>::remove(who);
+		<PinnedTweet<T, I>>::remove(who);
+		<Notifications<T, I>>::remove(who);
+		<NotificationCount<T, I>>::remove(who);
+		<NotificationsReadUpTo<T, I>>::remove(who);
+		<Verified<T, I>>::remove(who);
+		<MutedWords<T, I>>::remove(who);
+		<Settings<T, I>>::remove(who);
+		<Delegates<T, I>>::remove_prefix(who);
+		<DelegateCount<T, I>>::remove(who);
+		<Profiles<T, I>>::remove(who);
+		T::Currency::unreserve(who, <ProfileDeposits<T, I>>::take(who));
+		if let Some(handle) = <HandleOf<T, I>>::take(who) {
+			<Handles<T, I>>::remove(&handle);
+		}
+		T::Currency::unreserve(who, <HandleDeposits<T, I>>::take(who));
+		<Premium<T, I>>::remove(who);
+		T::Currency::unreserve(who, <PremiumDeposits<T, I>>::take(who));
+		<SubscriptionPrice<T, I>>::remove(who);
+	}
+
+	/// Move `tweet_id` to its place in `TopTweets` now that it has `likes` likes, evicting the
+	/// least liked tweet if the board is full. Censored tweets and tweets without likes are
+	/// taken off the board.
+	fn rank_tweet(tweet_id: TweetId, likes: u32) {
+		<TopTweets<T, I>>::mutate(|board| {
+			let mut entries = sp_std::mem::take(board).into_inner();
+			entries.retain(|&(id, _)| id != tweet_id);
+			if likes > 0 && !<Censored<I>>::contains_key(tweet_id) {
+				// Older tweets come first among equally liked ones.
+				let index = entries.iter()
+					.position(|&(id, count)| count < likes || (count == likes && id > tweet_id))
+					.unwrap_or_else(|| entries.len());
+				entries.insert(index, (tweet_id, likes));
+			}
+			*board = LeaderboardOf::<T, I>::truncate_from(entries);
+		});
+	}
+
+	/// Move `who` to its place in `TopEarners` now that it earned `earnings`, evicting the
+	/// account that earned the least if the board is full. Accounts without earnings are taken
+	/// off the board.
+	fn rank_earner(who: &T::AccountId, earnings: BalanceOf<T, I>) {
+		<TopEarners<T, I>>::mutate(|board| {
+			let mut entries = sp_std::mem::take(board).into_inner();
+			entries.retain(|(account, _)| account != who);
+			if !earnings.is_zero() {
+				let index = entries.iter()
+					.position(|(_, earned)| *earned < earnings)
+					.unwrap_or_else(|| entries.len());
+				entries.insert(index, (who.clone(), earnings));
+			}
+			*board = EarnersOf::<T, I>::truncate_from(entries);
+		});
+	}
+
+	/// The deposit for a tweet storing `content`, its text and media: `TweetDepositBase` plus
+	/// `TweetDepositPerByte` for every byte of its encoding.
+	fn tweet_deposit_for(content: &impl Encode) -> BalanceOf<T, I> {
+		let bytes = content.encode().len() as u32;
+		T::TweetDepositBase::get().saturating_add(T::TweetDepositPerByte::get().saturating_mul(bytes.into()))
+	}
+
+	/// Check that `who` can afford the deposit for a tweet storing `content` and return it.
+	fn ensure_can_reserve(who: &T::AccountId, content: &impl Encode) -> Result<BalanceOf<T, I>, Error<T, I>> {
+		let deposit = Self::tweet_deposit_for(content);
+		ensure!(T::Currency::can_reserve(who, deposit), Error::<T, I>::InsufficientBalance);
+		Ok(deposit)
+	}
 
-			let new_id = Self::alloc_id().ok_or(Error::<T>::NoAvailableTweetId)?;
-			let comment = Tweet {
+	/// Run `f` in a storage transaction, discarding all of its writes if it fails.
+	fn transactional<R>(f: impl FnOnce() -> Result<R, DispatchError>) -> Result<R, DispatchError> {
+		with_transaction(|| {
+			let result = f();
+			if result.is_ok() {
+				TransactionOutcome::Commit(result)
+			} else {
+				TransactionOutcome::Rollback(result)
+			}
+		})
+	}
+
+	/// Ensure `who` can interact with `tweet`, followers-only tweets being reserved to their
+	/// author and its followers.
+	fn ensure_visible(tweet: &TweetOf<T, I>, who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			tweet.visibility == Visibility::Public
+				|| tweet.author == *who
+				|| Self::is_follower_or_subscriber(who, &tweet.author),
+			Error::<T, I>::NotVisible
+		);
+		Ok(())
+	}
+
+	/// Whether `who` follows `author` or has a running subscription to it.
+	fn is_follower_or_subscriber(who: &T::AccountId, author: &T::AccountId) -> bool {
+		<Following<T, I>>::contains_key(who, author)
+			|| Self::is_subscriber(author, who, <frame_system::Module<T>>::block_number())
+	}
+
+	/// Whether the report of `who` against `tweet` counts toward `AutoHideThreshold`.
+	fn report_counts(tweet: &TweetOf<T, I>, who: &T::AccountId) -> bool {
+		if tweet.author == *who {
+			return false;
+		}
+		let min_age = T::MinReporterAge::get();
+		let now = <frame_system::Module<T>>::block_number();
+		min_age.is_zero() || Self::first_tweet_at(who).map_or(false, |first| first.saturating_add(min_age) <= now)
+	}
+
+	/// Ensure the reply policy of `tweet` lets `who` comment on it.
+	fn ensure_can_reply(tweet: &TweetOf<T, I>, who: &T::AccountId) -> DispatchResult {
+		let mentioned = || <TweetMentions<T, I>>::contains_key(tweet.id, who);
+		let allowed = match tweet.reply_policy {
+			ReplyPolicy::Everyone => true,
+			ReplyPolicy::Followers => Self::is_follower_or_subscriber(who, &tweet.author) || mentioned(),
+			ReplyPolicy::Mentioned => mentioned(),
+		};
+		ensure!(allowed || tweet.author == *who, Error::<T, I>::ReplyNotAllowed);
+		Ok(())
+	}
+
+	/// Ensure a quote of `original` is at most `MaxQuoteDepth` quotes away from a tweet quoting
+	/// nothing, returning that number of quotes.
+	fn ensure_quote_depth(original: &TweetOf<T, I>) -> Result<u32, DispatchError> {
+		let max_depth = T::MaxQuoteDepth::get();
+		let mut depth = 1;
+		ensure!(depth <= max_depth, Error::<T, I>::QuoteChainTooDeep);
+		let mut quoted = original.quote_tweet_id;
+		while let Some(id) = quoted {
+			depth += 1;
+			ensure!(depth <= max_depth, Error::<T, I>::QuoteChainTooDeep);
+			quoted = Self::tweets(id).and_then(|tweet| tweet.quote_tweet_id);
+		}
+		Ok(depth)
+	}
+
+	/// Ensure `who` can have `tweets` more tweets without going over `MaxTweetsPerAccount`.
+	fn ensure_tweet_capacity(who: &T::AccountId, tweets: u32) -> DispatchResult {
+		let count = Self::tweet_count_of(who).saturating_add(tweets);
+		ensure!(count <= T::MaxTweetsPerAccount::get(), Error::<T, I>::TooManyTweets);
+		Ok(())
+	}
+
+	/// Check a sponsored tweet against the state of the current block, from the cheapest checks
+	/// to the signature, so that it passes every check of `post_tweet` once included.
+	fn check_sponsored(payload: &SponsoredTweetOf<T>, signature: &T::Signature) -> Result<(), InvalidTransaction> {
+		if payload.valid_until < <frame_system::Module<T>>::block_number() {
+			return Err(InvalidTransaction::Stale);
+		}
+		let nonce = Self::sponsored_nonce(&payload.author);
+		if payload.nonce < nonce {
+			return Err(InvalidTransaction::Stale);
+		}
+		if payload.nonce > nonce {
+			return Err(InvalidTransaction::Future);
+		}
+		if Self::sponsored_in_block() >= T::MaxSponsoredPerBlock::get() {
+			return Err(InvalidTransaction::ExhaustsResources);
+		}
+		let text = Self::ensure_text(&payload.author, payload.text.clone())
+			.map_err(|_| InvalidTransaction::Custom(INVALID_SPONSORED_TEXT))?;
+		Self::ensure_hashtags(&text).map_err(|_| InvalidTransaction::Custom(INVALID_SPONSORED_TEXT))?;
+		Self::ensure_not_duplicate(&payload.author, &text)
+			.map_err(|_| InvalidTransaction::Custom(INVALID_SPONSORED_TEXT))?;
+		if Self::ensure_tweet_capacity(&payload.author, 1).is_err() || Self::ensure_rate_limit(&payload.author, 1).is_err() {
+			return Err(InvalidTransaction::Custom(SPONSORED_LIMIT_REACHED));
+		}
+		if !signature.verify(&Self::sponsored_signing_payload(payload)[..], &payload.author) {
+			return Err(InvalidTransaction::BadProof);
+		}
+		Ok(())
+	}
+
+	/// The bytes the author of `payload` signs: the payload after the prefix of this instance
+	/// and the genesis hash of the chain, so that the signature is not valid on another instance
+	/// or chain.
+	pub fn sponsored_signing_payload(payload: &SponsoredTweetOf<T>) -> Vec<u8> {
+		let genesis_hash = <frame_system::Module<T>>::block_hash(T::BlockNumber::zero());
+		(I::PREFIX, genesis_hash, payload).encode()
+	}
+
+	/// Whether the fee is paid for a tweet `who` just posted, counting it against the
+	/// `FreeTweetsPerDay` allowance of `who` if it holds `MinBalanceForFreeTweets`.
+	fn tweet_pays_fee(who: &T::AccountId) -> Pays {
+		if T::Currency::free_balance(who) < T::MinBalanceForFreeTweets::get() {
+			return Pays::Yes;
+		}
+		let day = <frame_system::Module<T>>::block_number() / T::DayLength::get().max(One::one());
+		<FreeTweetsUsed<T, I>>::mutate(who, |(last_day, used)| {
+			if *last_day != day {
+				*last_day = day;
+				*used = 0;
+			}
+			if *used < T::FreeTweetsPerDay::get() {
+				*used += 1;
+				Pays::No
+			} else {
+				Pays::Yes
+			}
+		})
+	}
+
+	/// Ensure `who` can post `tweets` more tweets in the current period, returning its rate once
+	/// it has. A new period starts `RateLimitPeriod` blocks after the previous one started.
+	fn ensure_rate_limit(who: &T::AccountId, tweets: u32) -> Result<(T::BlockNumber, u32), DispatchError> {
+		let now = <frame_system::Module<T>>::block_number();
+		let (mut start, mut count) = Self::tweet_rate(who);
+		if now >= start.saturating_add(T::RateLimitPeriod::get()) {
+			start = now;
+			count = 0;
+		}
+		let count = count.saturating_add(tweets);
+		ensure!(count <= T::MaxTweetsPerPeriod::get(), Error::<T, I>::RateLimited);
+		Ok((start, count))
+	}
+
+	/// Reserve the deposit for tweet `id` from `author`, after `ensure_can_reserve` passed.
+	fn reserve_deposit(author: &T::AccountId, id: TweetId, deposit: BalanceOf<T, I>) -> DispatchResult {
+		T::Currency::reserve(author, deposit)?;
+		<TweetDeposits<T, I>>::insert(id, deposit);
+		Ok(())
+	}
+
+	/// Maximum length of the text of a tweet, in characters.
+	pub fn text_limit() -> u32 {
+		<TextLimit<I>>::get().unwrap_or_else(T::MaxTextLen::get)
+	}
+
+	/// Check that `text` is not empty, passes `validate_text` and has at most `MaxTextBytes`
+	/// bytes and `TextLimit` characters, or `MaxTextLenPremium` if `author` is premium and it is
+	/// more. Reposts, the only tweets without text, do not go through it.
+	fn ensure_text(author: &T::AccountId, text: Vec<u8>) -> Result<TextOf<T, I>, Error<T, I>> {
+		ensure!(!text.is_empty(), Error::<T, I>::EmptyTweet);
+		Self::validate_text(&text)?;
+		let chars = core::str::from_utf8(&text).map_err(|_| Error::<T, I>::InvalidUtf8)?.chars().count();
+		let text_limit = Self::text_limit();
+		let max_len = if Self::is_premium(author, <frame_system::Module<T>>::block_number()) {
+			T::MaxTextLenPremium::get().max(text_limit)
+		} else {
+			text_limit
+		};
+		ensure!(chars <= max_len as usize, Error::<T, I>::TweetTooLong);
+		TextOf::<T, I>::try_from(text).map_err(|_| Error::<T, I>::TweetTooLong)
+	}
+
+	/// Check that `text` is UTF-8 without control characters other than `\n`, bidirectional
+	/// embeddings, overrides and isolates, or more than `MaxZeroWidthRun` zero-width characters
+	/// in a row.
+	fn validate_text(text: &[u8]) -> Result<(), Error<T, I>> {
+		let text = core::str::from_utf8(text).map_err(|_| Error::<T, I>::InvalidUtf8)?;
+		let mut zero_width_run = 0;
+		for c in text.chars() {
+			let control = c.is_control() && c != '\n';
+			let bidi = matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}');
+			ensure!(!control && !bidi, Error::<T, I>::InvalidCharacters);
+			if matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}') {
+				zero_width_run += 1;
+				ensure!(zero_width_run <= MaxZeroWidthRun::get(), Error::<T, I>::InvalidCharacters);
+			} else {
+				zero_width_run = 0;
+			}
+		}
+		Ok(())
+	}
+
+	/// Post a tweet of `author`, as `new_tweet` does, to `community` if given, returning its id
+	/// and its number of hashtags. No deposit is reserved unless `with_deposit`.
+	#[allow(clippy::too_many_arguments)]
+	fn post_tweet(
+		author: T::AccountId,
+		text: Vec<u8>,
+		mentions: Vec<T::AccountId>,
+		comments_enabled: bool,
+		visibility: Visibility,
+		media: Vec<Vec<u8>>,
+		lang: Option<LanguageCode>,
+		reply_policy: ReplyPolicy,
+		community: Option<CommunityId>,
+		with_deposit: bool,
+	) -> Result<(TweetId, u32), DispatchError> {
+		let text = Self::ensure_text(&author, text)?;
+		Self::ensure_lang(lang)?;
+		let reply_policy = reply_policy.max(Self::settings(&author).reply_policy);
+		let content = Self::ensure_not_duplicate(&author, &text)?;
+		let hashtags = Self::ensure_hashtags(&text)?;
+		ensure!(mentions.len() <= T::MaxMentions::get() as usize, Error::<T, I>::TooManyMentions);
+		let media = Self::ensure_media(media)?;
+		let media_count = media.len() as u32;
+		let deposit = if with_deposit {
+			Self::ensure_can_reserve(&author, &(&text, &media))?
+		} else {
+			Zero::zero()
+		};
+		Self::ensure_tweet_capacity(&author, 1)?;
+		let rate = Self::ensure_rate_limit(&author, 1)?;
+		let hashtag_count = hashtags.len() as u32;
+
+		let new_id = Self::transactional(|| {
+			let new_id = Self::alloc_id().ok_or(Error::<T, I>::NoAvailableTweetId)?;
+			let tweet = Tweet {
 				id: new_id,
 				create_at: <frame_system::Module<T>>::block_number(),
 				quote_tweet_id: None,
+				parent_id: None,
+				thread_prev: None,
 				text,
-				comments: vec![],
 				author: author.clone(),
+				comments_enabled,
+				visibility,
+				media,
+				lang,
+				reply_policy,
+				hidden: false,
+				conversation_id: new_id,
+				revision: 0,
+				community,
 			};
 
-			<Tweets<T>>::try_mutate_exists(&tweet_id, |maybe_tweet| -> DispatchResult {
-				let tweet = maybe_tweet.as_mut().ok_or(Error::<T>::TweetNotFound)?;
-				tweet.comments.push(new_id);
-				Ok(())
-			})?;
-			<Accounts<T>>::mutate(&author, |tweets| {
-				tweets.push(new_id);
-			});
-			<Tweets<T>>::insert(new_id, comment.clone());
+			Self::reserve_deposit(&author, new_id, deposit)?;
+			<TweetRate<T, I>>::insert(&author, rate);
+			Self::push_account_tweet(&author, new_id);
+			Self::index_hashtags(new_id, &hashtags);
+			Self::count_hashtags(&hashtags);
+			Self::notify_hashtag_followers(&author, new_id, &hashtags);
+			Self::index_lang(new_id, lang);
+			if reply_policy != ReplyPolicy::Everyone {
+				for who in &mentions {
+					<TweetMentions<T, I>>::insert(new_id, who, ());
+				}
+			}
+			if let Some((window, hash)) = content {
+				<RecentContent<T, I>>::insert(window, hash, new_id);
+			}
+			if let Some(community) = community {
+				<CommunityTweets<I>>::insert(community, new_id, ());
+			}
+			<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+			<Tweets<T, I>>::insert(new_id, tweet);
+			Ok(new_id)
+		})?;
+
+		Self::deposit_event(RawEvent::Tweeted(author.clone(), new_id, lang, T::Identity::is_judged(&author)));
+		Self::deposit_media_event(new_id, media_count);
+		Self::notify_mentions(&author, new_id, mentions);
+		Self::on_tweet_created(&author, new_id, TweetKind::Tweet);
+		Ok((new_id, hashtag_count))
+	}
 
-			Self::deposit_event(RawEvent::Tweeted(comment));
+	/// Ensure `author` did not post `text` in the current or the previous `DedupWindow`,
+	/// returning the window and the hash to record it under, or `None` if the check is disabled.
+	fn ensure_not_duplicate(author: &T::AccountId, text: &[u8]) -> Result<Option<ContentKeyOf<T>>, Error<T, I>> {
+		let length = T::DedupWindow::get();
+		if length.is_zero() {
+			return Ok(None);
 		}
+		let window = <frame_system::Module<T>>::block_number() / length;
+		let hash = (author, text).using_encoded(blake2_256);
+		ensure!(
+			!<RecentContent<T, I>>::contains_key(window, hash)
+				&& (window.is_zero() || !<RecentContent<T, I>>::contains_key(window - One::one(), hash)),
+			Error::<T, I>::DuplicateTweet
+		);
+		Ok(Some((window, hash)))
+	}
+
+	/// Check that `lang`, if given, is two lowercase ASCII letters.
+	fn ensure_lang(lang: Option<LanguageCode>) -> Result<(), Error<T, I>> {
+		ensure!(
+			lang.map_or(true, |code| code.iter().all(u8::is_ascii_lowercase)),
+			Error::<T, I>::InvalidLanguageCode
+		);
+		Ok(())
+	}
+
+	/// Check that `word` has between 1 and `MaxMutedWordLen` bytes, returning it in lowercase.
+	fn ensure_muted_word(mut word: Vec<u8>) -> Result<MutedWord, Error<T, I>> {
+		ensure!(!word.is_empty(), Error::<T, I>::InvalidMutedWord);
+		word.make_ascii_lowercase();
+		MutedWord::try_from(word).map_err(|_| Error::<T, I>::InvalidMutedWord)
+	}
+
+	/// Check that `tag`, without its leading `#` if any, is a hashtag of at most
+	/// `MaxFollowedHashtagLen` bytes, returning it in lowercase without the `#`.
+	fn ensure_followed_hashtag(mut tag: Vec<u8>) -> Result<Vec<u8>, Error<T, I>> {
+		if tag.first() == Some(&b'#') {
+			tag.remove(0);
+		}
+		ensure!(
+			!tag.is_empty() && tag.len() <= MaxFollowedHashtagLen::get() as usize &&
+				tag.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_'),
+			Error::<T, I>::InvalidHashtag
+		);
+		tag.make_ascii_lowercase();
+		Ok(tag)
+	}
+
+	/// Weight of notifying the followers of the hashtags of a tweet with `hashtags` hashtags.
+	fn hashtag_fanout_weight(hashtags: u32) -> Weight {
+		if hashtags == 0 {
+			0
+		} else {
+			T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get())
+		}
+	}
+
+	/// Notify the followers of `tags` of the new tweet `id` of `author`, each of them once. At
+	/// most `MaxHashtagFanout` followers are looked at, in no particular order.
+	fn notify_hashtag_followers(author: &T::AccountId, id: TweetId, tags: &[Vec<u8>]) {
+		let mut notified: Vec<T::AccountId> = Vec::new();
+		let followers = tags.iter()
+			.flat_map(|tag| <HashtagFollowers<T, I>>::iter_prefix(tag).map(|(follower, ())| follower))
+			.take(T::MaxHashtagFanout::get() as usize);
+		for follower in followers {
+			if !notified.contains(&follower) {
+				Self::notify(&follower, author, Notification::HashtagTweeted(author.clone(), id));
+				notified.push(follower);
+			}
+		}
+	}
+
+	/// Whether `text` contains one of the words muted by `who`.
+	fn contains_muted_word(who: &T::AccountId, text: &[u8]) -> bool {
+		let words = Self::muted_words(who);
+		if words.is_empty() {
+			return false;
+		}
+		let text = text.to_ascii_lowercase();
+		words.iter().any(|word| text.windows(word.len()).any(|window| window == &word[..]))
+	}
+
+	/// Check that there are at most `MaxAttachments` media, each identified by at most
+	/// `MaxCidLen` bytes.
+	fn ensure_media(media: Vec<Vec<u8>>) -> Result<MediaOf<T, I>, Error<T, I>> {
+		ensure!(media.len() <= T::MaxAttachments::get() as usize, Error::<T, I>::TooManyAttachments);
+		let media = media.into_iter()
+			.map(|cid| MediaCid::try_from(cid).map_err(|_| Error::<T, I>::CidTooLong))
+			.collect::<Result<Vec<_>, _>>()?;
+		MediaOf::<T, I>::try_from(media).map_err(|_| Error::<T, I>::TooManyAttachments)
+	}
+
+	fn deposit_media_event(id: TweetId, count: u32) {
+		if count > 0 {
+			Self::deposit_event(RawEvent::MediaAttached(id, count));
+		}
+	}
+
+	/// Extract the hashtags of `text`, failing if there are more than `MaxHashtagsPerTweet`.
+	fn ensure_hashtags(text: &[u8]) -> Result<Vec<Vec<u8>>, Error<T, I>> {
+		let tags = Self::hashtags(text);
+		ensure!(tags.len() <= T::MaxHashtagsPerTweet::get() as usize, Error::<T, I>::TooManyHashtags);
+		Ok(tags)
+	}
+
+	fn index_hashtags(id: TweetId, tags: &[Vec<u8>]) {
+		for tag in tags {
+			<HashtagTweets<I>>::insert(tag, id, ());
+		}
+	}
+
+	/// Count a new tweet with `tags` in the hashtag usage of the current window.
+	fn count_hashtags(tags: &[Vec<u8>]) {
+		let window = Self::window_index(<frame_system::Module<T>>::block_number());
+		for tag in tags {
+			<HashtagUsage<T, I>>::mutate(window, tag, |count| *count = count.saturating_add(1));
+		}
+	}
+
+	fn unindex_hashtags(id: TweetId, text: &[u8]) {
+		for tag in Self::hashtags(text) {
+			<HashtagTweets<I>>::remove(tag, id);
+		}
+	}
+
+	/// Index tweet `id` under its language, if it has one.
+	fn index_lang(id: TweetId, lang: Option<LanguageCode>) {
+		if let Some(lang) = lang {
+			<TweetsByLang<I>>::insert(lang, id, ());
+		}
+	}
+
+	/// Notify the accounts mentioned in tweet `id`, skipping `author`, repeated mentions,
+	/// accounts blocking `author` and accounts not followed by `author` that turned off
+	/// `AccountSettings::mentions_from_non_followers`.
+	fn notify_mentions(author: &T::AccountId, id: TweetId, mentions: Vec<T::AccountId>) {
+		let mut notified: Vec<T::AccountId> = Vec::new();
+		for who in mentions {
+			if notified.contains(&who)
+				|| (!Self::settings(&who).mentions_from_non_followers && !<Following<T, I>>::contains_key(author, &who))
+				|| !Self::notify(&who, author, Notification::Mentioned(author.clone(), id))
+			{
+				continue;
+			}
+			Self::deposit_event(RawEvent::Mentioned(who.clone(), id));
+			notified.push(who);
+		}
+	}
+
+	/// Add `notification` about something `from` did to the inbox of `who`, dropping the oldest
+	/// notification if it is full.
+	///
+	/// Returns whether `who` was notified: nothing is recorded for the actions of `who` itself
+	/// or of the accounts it blocks.
+	fn notify(who: &T::AccountId, from: &T::AccountId, notification: Notification<T::AccountId>) -> bool {
+		if who == from || <Blocked<T, I>>::contains_key(who, from) {
+			return false;
+		}
+		<Notifications<T, I>>::mutate(who, |inbox| {
+			if inbox.len() >= NotificationsOf::<T, I>::bound() && !inbox.is_empty() {
+				inbox.remove(0);
+			}
+			let _ = inbox.try_push(notification);
+		});
+		<NotificationCount<T, I>>::mutate(who, |count| *count = count.saturating_add(1));
+		true
+	}
+
+	/// Number of notifications in the inbox of `who` not marked as read.
+	pub fn unread_count(who: &T::AccountId) -> u32 {
+		let unread = Self::notification_count(who).saturating_sub(Self::notifications_read_up_to(who));
+		unread.min(Self::notifications(who).len() as u64) as u32
+	}
+
+	/// Record the new tweet `id` of `author` in `RecentTweets`, unless it is a comment and
+	/// `RecentSkipsComments` is set, write it to the off-chain index and pass it on to
+	/// `TweetHooks`.
+	fn on_tweet_created(author: &T::AccountId, id: TweetId, kind: TweetKind) {
+		if kind != TweetKind::Comment || !T::RecentSkipsComments::get() {
+			Self::push_recent(id);
+		}
+		Self::index_offchain(id);
+		T::TweetHooks::on_tweet(author, id, kind);
+	}
+
+	/// Key of the tweet `id` in the off-chain index.
+	pub fn offchain_key(id: TweetId) -> Vec<u8> {
+		(b"twitter/tweet", I::PREFIX, id).encode()
+	}
+
+	/// Write the author, block and text of the tweet `id` to the off-chain index, for search
+	/// services to read without decoding the chain state.
+	///
+	/// The entries only reach the off-chain database of nodes running with off-chain indexing
+	/// enabled.
+	fn index_offchain(id: TweetId) {
+		if let Some(tweet) = Self::tweets(id) {
+			let entry = IndexedTweet {
+				author: tweet.author,
+				block: <frame_system::Module<T>>::block_number(),
+				text: tweet.text.into_inner(),
+			};
+			sp_io::offchain_index::set(&Self::offchain_key(id), &entry.encode());
+		}
+	}
+
+	/// Number of the tweets posted in `block` found in the off-chain index, and the hash of
+	/// their entries. Only available to off-chain workers.
+	pub fn indexed_digest(block: T::BlockNumber) -> (u32, [u8; 32]) {
+		let mut ids: Vec<TweetId> = <TweetsByBlock<T, I>>::iter_prefix(block).map(|(id, ())| id).collect();
+		ids.sort();
+		let entries: Vec<IndexedTweet<T::AccountId, T::BlockNumber>> = ids.into_iter()
+			.filter_map(|id| StorageValueRef::persistent(&Self::offchain_key(id)).get().flatten())
+			.collect();
+		(entries.len() as u32, blake2_256(&entries.encode()))
+	}
+
+	/// Append `id` to `RecentTweets`, dropping the oldest tweet if it is full.
+	fn push_recent(id: TweetId) {
+		<RecentTweets<T, I>>::mutate(|recent| {
+			if recent.len() >= RecentTweetsOf::<T, I>::bound() && !recent.is_empty() {
+				recent.remove(0);
+			}
+			let _ = recent.try_push(id);
+		});
+	}
+
+	/// Append a tweet created in the current block to the list of tweets posted by `author`.
+	fn push_account_tweet(author: &T::AccountId, id: TweetId) {
+		let now = <frame_system::Module<T>>::block_number();
+		<AccountTweetCount<T, I>>::mutate(author, |count| {
+			if *count == 0 {
+				<FirstTweetAt<T, I>>::insert(author, now);
+			}
+			<AccountTweets<T, I>>::insert(author, *count, id);
+			*count += 1;
+		});
+		<TweetsByBlock<T, I>>::insert(now, id, ());
+		<TweetCount<I>>::mutate(|count| *count = count.saturating_add(1));
+		<TweetCountOf<T, I>>::mutate(author, |count| *count = count.saturating_add(1));
+	}
+
+	/// Take `who` out of a community it is a member of but does not own.
+	fn remove_from_community(community_id: CommunityId, who: T::AccountId) -> DispatchResult {
+		let community = Self::communities(community_id).ok_or(Error::<T, I>::CommunityNotFound)?;
+		ensure!(community.owner != who, Error::<T, I>::OwnerCannotLeave);
+		ensure!(<Members<T, I>>::contains_key(community_id, &who), Error::<T, I>::NotAMember);
+
+		<Members<T, I>>::remove(community_id, &who);
+		<MemberCount<I>>::mutate(community_id, |count| *count = count.saturating_sub(1));
+
+		Self::deposit_event(RawEvent::CommunityLeft(who, community_id));
+		Ok(())
+	}
+
+	/// Clear the `GlobalAnnouncement` if it is `tweet_id`.
+	fn clear_announcement(tweet_id: TweetId) {
+		if Self::global_announcement() == Some(tweet_id) {
+			<GlobalAnnouncement<I>>::kill();
+			Self::deposit_event(RawEvent::AnnouncementCleared);
+		}
+	}
+
+	/// Whether a follower count of `count` is a power of ten, from 10 up. Falling under one and
+	/// reaching it again counts again.
+	fn is_follower_milestone(count: u32) -> bool {
+		let mut rest = count;
+		while rest >= 10 && rest % 10 == 0 {
+			rest /= 10;
+		}
+		count >= 10 && rest == 1
+	}
+
+	/// Whether `who` can interact with `tweet`: it is not gated, `who` is its author or `who`
+	/// unlocked it.
+	fn has_unlocked(tweet: &TweetOf<T, I>, who: &T::AccountId) -> bool {
+		!<GatePrice<T, I>>::contains_key(tweet.id)
+			|| tweet.author == *who
+			|| <Unlocked<T, I>>::contains_key(tweet.id, who)
+	}
+
+	/// Whether `tweet` was minted and is owned by another account than its author.
+	fn is_sold(tweet: &TweetOf<T, I>) -> bool {
+		Self::tweet_owner(tweet.id).map_or(false, |owner| owner != tweet.author)
+	}
+
+	/// Remove a tweet together with everything indexed under it.
+	///
+	/// Its entry in `AccountTweets` is kept, `accounts` skips it.
+	fn remove_tweet(tweet: &TweetOf<T, I>) {
+		<Tweets<T, I>>::remove(tweet.id);
+		<TweetsByBlock<T, I>>::remove(tweet.create_at, tweet.id);
+		<ConversationTweets<I>>::remove(tweet.conversation_id, tweet.id);
+		if let Some(community) = tweet.community {
+			<CommunityTweets<I>>::remove(community, tweet.id);
+		}
+		if let Some((hash, _)) = <TweetCommitment<T, I>>::take(tweet.id) {
+			<RevealedTweet<I>>::remove(hash);
+		}
+		// A tombstone was already uncounted when it was deleted.
+		if !<Deleted<I>>::take(tweet.id) {
+			Self::uncount_tweet(tweet);
+		}
+		Self::unindex_hashtags(tweet.id, &tweet.text);
+		if let Some(lang) = tweet.lang {
+			<TweetsByLang<I>>::remove(lang, tweet.id);
+		}
+		if Self::pinned_tweet(&tweet.author) == Some(tweet.id) {
+			<PinnedTweet<T, I>>::remove(&tweet.author);
+		}
+		T::Currency::unreserve(&tweet.author, <TweetDeposits<T, I>>::take(tweet.id));
+		Self::clear_likes(tweet.id);
+		Self::clear_reactions(tweet.id);
+		<Comments<I>>::remove_prefix(tweet.id);
+		if let (Some(parent), Some(index)) = (tweet.parent_id, <CommentIndex<I>>::take(tweet.id)) {
+			<Comments<I>>::remove(parent, index);
+		}
+		<NextCommentIndex<I>>::remove(tweet.id);
+		<CommentCount<I>>::remove(tweet.id);
+		<RetweetCount<I>>::remove(tweet.id);
+		<RetweetPreview<T, I>>::remove(tweet.id);
+		<QuoteSnapshots<I>>::remove(tweet.id);
+		<Reports<T, I>>::remove_prefix(tweet.id);
+		<ReportCount<I>>::remove(tweet.id);
+		<Censored<I>>::remove(tweet.id);
+		<Polls<T, I>>::remove(tweet.id);
+		<PollResults<I>>::remove(tweet.id);
+		<TweetOwner<T, I>>::remove(tweet.id);
+		Self::clear_announcement(tweet.id);
+		<TipTotal<T, I>>::remove(tweet.id);
+		Self::clear_gate(tweet.id);
+		sp_io::offchain_index::clear(&Self::offchain_key(tweet.id));
+		if let Some(expires_at) = <TweetExpiry<T, I>>::take(tweet.id) {
+			<ExpiringAt<T, I>>::remove(expires_at, tweet.id);
+		}
+		<PollVotes<T, I>>::remove_prefix(tweet.id);
+		Self::clear_notes(tweet.id);
+		<TweetMentions<T, I>>::remove_prefix(tweet.id);
+		<EditHistory<T, I>>::remove_prefix(tweet.id);
+	}
+
+	/// Drop the full text, price and unlocks of a gated tweet.
+	fn clear_gate(tweet_id: TweetId) {
+		<GatedContent<T, I>>::remove(tweet_id);
+		<GatePrice<T, I>>::remove(tweet_id);
+		<Unlocked<T, I>>::remove_prefix(tweet_id);
+	}
+
+	/// Turn `tweet` into a tombstone with blank text and no media, language or community,
+	/// dropping its deposit, pin, likes, reactions, poll, owner, reports, notes, mentions, edit
+	/// history, pending expiry, announcement and gated text. Its comments and links to other
+	/// tweets are kept.
+	fn delete_to_tombstone(mut tweet: TweetOf<T, I>) {
+		Self::uncount_tweet(&tweet);
+		Self::unindex_hashtags(tweet.id, &tweet.text);
+		if let Some(lang) = tweet.lang.take() {
+			<TweetsByLang<I>>::remove(lang, tweet.id);
+		}
+		if let Some(community) = tweet.community.take() {
+			<CommunityTweets<I>>::remove(community, tweet.id);
+		}
+		if Self::pinned_tweet(&tweet.author) == Some(tweet.id) {
+			<PinnedTweet<T, I>>::remove(&tweet.author);
+		}
+		T::Currency::unreserve(&tweet.author, <TweetDeposits<T, I>>::take(tweet.id));
+		Self::clear_likes(tweet.id);
+		Self::clear_reactions(tweet.id);
+		<Reports<T, I>>::remove_prefix(tweet.id);
+		<ReportCount<I>>::remove(tweet.id);
+		<Polls<T, I>>::remove(tweet.id);
+		<PollResults<I>>::remove(tweet.id);
+		<TweetOwner<T, I>>::remove(tweet.id);
+		Self::clear_announcement(tweet.id);
+		Self::clear_gate(tweet.id);
+		sp_io::offchain_index::clear(&Self::offchain_key(tweet.id));
+		<PollVotes<T, I>>::remove_prefix(tweet.id);
+		if let Some(expires_at) = <TweetExpiry<T, I>>::take(tweet.id) {
+			<ExpiringAt<T, I>>::remove(expires_at, tweet.id);
+		}
+		Self::clear_notes(tweet.id);
+		<TweetMentions<T, I>>::remove_prefix(tweet.id);
+		<EditHistory<T, I>>::remove_prefix(tweet.id);
+		<Deleted<I>>::insert(tweet.id, true);
+
+		tweet.text = Default::default();
+		tweet.media = Default::default();
+		tweet.comments_enabled = false;
+		<Tweets<T, I>>::insert(tweet.id, tweet);
+	}
+
+	/// Stop counting `tweet` in the tweet counts and in the comment, retweet and repost counts
+	/// of the tweets it refers to.
+	fn uncount_tweet(tweet: &TweetOf<T, I>) {
+		<TweetCount<I>>::mutate(|count| *count = count.saturating_sub(1));
+		<TweetCountOf<T, I>>::mutate(&tweet.author, |count| *count = count.saturating_sub(1));
+		if let Some(parent) = tweet.parent_id {
+			if <Tweets<T, I>>::contains_key(parent) {
+				<CommentCount<I>>::mutate(parent, |count| *count = count.saturating_sub(1));
+			}
+		}
+		if let Some(original) = tweet.quote_tweet_id {
+			if <Tweets<T, I>>::contains_key(original) {
+				<RetweetCount<I>>::mutate(original, |count| *count = count.saturating_sub(1));
+			}
+			if Self::reposts(original, &tweet.author) == Some(tweet.id) {
+				<Reposts<T, I>>::remove(original, &tweet.author);
+				<RepostCount<I>>::mutate(original, |count| *count = count.saturating_sub(1));
+			} else {
+				<Retweeted<T, I>>::remove(original, &tweet.author);
+			}
+			let still_retweeted = <Retweeted<T, I>>::contains_key(original, &tweet.author)
+				|| <Reposts<T, I>>::contains_key(original, &tweet.author);
+			if !still_retweeted && <Tweets<T, I>>::contains_key(original) {
+				<RetweetPreview<T, I>>::mutate(original, |preview| preview.retain(|account| account != &tweet.author));
+			}
+		}
+	}
+
+	/// Remove up to `MaxExpirationsPerBlock` ephemeral tweets that expired at or before `now`,
+	/// oldest first, scanning at most as many blocks.
+	fn expire_tweets(now: T::BlockNumber) -> Weight {
+		let mut block = ExpiryCursor::<T, I>::get();
+		let mut scanned = 0;
+		let mut removed = 0;
+		while block <= now && scanned < T::MaxExpirationsPerBlock::get() {
+			let limit = T::MaxExpirationsPerBlock::get() - removed;
+			let expired: Vec<TweetId> = <ExpiringAt<T, I>>::iter_prefix(block)
+				.take(limit as usize)
+				.map(|(id, ())| id)
+				.collect();
+			scanned += 1;
+			for id in &expired {
+				if let Some(tweet) = Self::tweets(id) {
+					Self::remove_tweet(&tweet);
+					Self::deposit_event(RawEvent::TweetExpired(*id));
+				}
+			}
+			removed += expired.len() as u32;
+			if removed == T::MaxExpirationsPerBlock::get() {
+				break;
+			}
+			block += One::one();
+		}
+		ExpiryCursor::<T, I>::put(block);
+
+		T::WeightInfo::expire_tweets(removed).saturating_add(T::DbWeight::get().reads(scanned))
+	}
+
+	/// Remove up to `limit` tweets created before block `before`, oldest first, scanning at most
+	/// as many blocks. Pinned tweets and tweets holding a deposit are kept in `TweetsByBlock`,
+	/// and `PruneKept` moves past them.
+	///
+	/// Returns the number of tweets removed and kept, and the number of blocks scanned.
+	fn prune_tweets_before(before: T::BlockNumber, limit: u32) -> (u32, u32, u32) {
+		let mut block = PruneCursor::<T, I>::get();
+		let mut kept = PruneKept::<I>::get();
+		let mut scanned = 0;
+		let mut pruned = 0;
+		let mut skipped = 0;
+		while block < before && scanned < limit && pruned + skipped < limit {
+			let left = limit - pruned - skipped;
+			// Entries are iterated in the order of their keys, those up to the last kept one
+			// were handled by earlier calls.
+			let kept_key = kept.map(|id| Twox64Concat::hash(&id.encode()));
+			let ids: Vec<TweetId> = <TweetsByBlock<T, I>>::iter_prefix(block)
+				.map(|(id, ())| id)
+				.filter(|id| kept_key.as_ref().map_or(true, |key| Twox64Concat::hash(&id.encode()) > *key))
+				.take(left as usize)
+				.collect();
+			scanned += 1;
+			for id in &ids {
+				match Self::tweets(id) {
+					Some(tweet) if Self::pinned_tweet(&tweet.author) != Some(*id)
+						&& Self::tweet_deposit(id).is_zero() =>
+					{
+						Self::remove_tweet(&tweet);
+						pruned += 1;
+					}
+					Some(_) => {
+						kept = Some(*id);
+						skipped += 1;
+					}
+					None => {
+						<TweetsByBlock<T, I>>::remove(block, id);
+						skipped += 1;
+					}
+				}
+			}
+			if (ids.len() as u32) < left {
+				block += One::one();
+				kept = None;
+			}
+		}
+		PruneCursor::<T, I>::put(block);
+		PruneKept::<I>::set(kept);
+
+		(pruned, skipped, scanned)
+	}
+
+	/// Remove up to `MaxHashtagPrunesPerBlock` hashtag usage counts of the windows more than
+	/// `KeepWindows` before the one of `now`, oldest first, scanning at most as many windows.
+	fn prune_hashtag_usage(now: T::BlockNumber) -> Weight {
+		let max = T::MaxHashtagPrunesPerBlock::get();
+		let current = Self::window_index(now);
+		let keep = T::BlockNumber::from(T::KeepWindows::get());
+		let mut window = HashtagUsageCursor::<T, I>::get();
+		let mut scanned = 0;
+		let mut removed = 0;
+		while window.saturating_add(keep) < current && scanned < max && removed < max {
+			let limit = max - removed;
+			let tags: Vec<Vec<u8>> = <HashtagUsage<T, I>>::iter_prefix(window)
+				.take(limit as usize)
+				.map(|(tag, _)| tag)
+				.collect();
+			scanned += 1;
+			for tag in &tags {
+				<HashtagUsage<T, I>>::remove(window, tag);
+			}
+			removed += tags.len() as u32;
+			if (tags.len() as u32) < limit {
+				window += One::one();
+			}
+		}
+		HashtagUsageCursor::<T, I>::put(window);
+
+		T::WeightInfo::prune_hashtag_usage(removed).saturating_add(T::DbWeight::get().reads(scanned as Weight))
+	}
+
+	/// Remove up to `MaxContentPrunesPerBlock` content hashes of the windows before the previous
+	/// `DedupWindow`, oldest first, scanning at most as many windows.
+	fn prune_recent_content(now: T::BlockNumber) -> Weight {
+		let length = T::DedupWindow::get();
+		if length.is_zero() {
+			return 0;
+		}
+		let max = T::MaxContentPrunesPerBlock::get();
+		let current = now / length;
+		let mut window = RecentContentCursor::<T, I>::get();
+		let mut scanned = 0;
+		let mut removed = 0;
+		while window.saturating_add(One::one()) < current && scanned < max && removed < max {
+			let limit = max - removed;
+			let hashes: Vec<[u8; 32]> = <RecentContent<T, I>>::iter_prefix(window)
+				.take(limit as usize)
+				.map(|(hash, _)| hash)
+				.collect();
+			scanned += 1;
+			for hash in &hashes {
+				<RecentContent<T, I>>::remove(window, hash);
+			}
+			removed += hashes.len() as u32;
+			if (hashes.len() as u32) < limit {
+				window += One::one();
+			}
+		}
+		RecentContentCursor::<T, I>::put(window);
+
+		T::WeightInfo::prune_recent_content(removed).saturating_add(T::DbWeight::get().reads(scanned as Weight))
 	}
-}
 
-impl<T: Trait> Module<T> {
+	/// Publish the tweets scheduled at `now`, in the order they were scheduled.
+	///
+	/// A tweet that cannot get an id or would take its author over `MaxTweetsPerAccount` is
+	/// dropped and its deposit returned.
+	fn publish_scheduled(now: T::BlockNumber) -> Weight {
+		let mut scheduled: Vec<_> = <ScheduledTweets<T, I>>::drain_prefix(now).collect();
+		scheduled.sort_by_key(|(index, _)| *index);
+		<ScheduledCount<T, I>>::remove(now);
+
+		let count = scheduled.len() as u32;
+		for (_, (author, text, deposit)) in scheduled {
+			let new_id = match Self::ensure_tweet_capacity(&author, 1).ok().and_then(|()| Self::alloc_id()) {
+				Some(id) => id,
+				None => {
+					T::Currency::unreserve(&author, deposit);
+					continue;
+				}
+			};
+			let hashtags = Self::hashtags(&text);
+			let tweet = Tweet {
+				id: new_id,
+				create_at: now,
+				quote_tweet_id: None,
+				parent_id: None,
+				thread_prev: None,
+				text,
+				author: author.clone(),
+				comments_enabled: true,
+				visibility: Visibility::Public,
+				media: Default::default(),
+				lang: None,
+				reply_policy: Self::settings(&author).reply_policy,
+				hidden: false,
+				conversation_id: new_id,
+				revision: 0,
+				community: None,
+			};
+
+			<TweetDeposits<T, I>>::insert(new_id, deposit);
+			Self::push_account_tweet(&author, new_id);
+			Self::index_hashtags(new_id, &hashtags);
+			Self::count_hashtags(&hashtags);
+			Self::notify_hashtag_followers(&author, new_id, &hashtags);
+			<ConversationTweets<I>>::insert(tweet.conversation_id, new_id, ());
+			<Tweets<T, I>>::insert(new_id, tweet);
+
+			Self::on_tweet_created(&author, new_id, TweetKind::Tweet);
+			let has_identity = T::Identity::is_judged(&author);
+			Self::deposit_event(RawEvent::Tweeted(author, new_id, None, has_identity));
+		}
+
+		T::WeightInfo::publish_scheduled(count)
+			.saturating_add(T::WeightInfo::notify_hashtag_followers(T::MaxHashtagFanout::get()).saturating_mul(count as Weight))
+	}
+
+	/// Drop the commitments expiring at `now`, forfeiting their deposits. At most
+	/// `MaxCommitsPerBlock` expire at a block.
+	fn expire_commitments(now: T::BlockNumber) -> Weight {
+		let expired: Vec<[u8; 32]> = <CommitmentsExpiringAt<T, I>>::drain_prefix(now).map(|(hash, ())| hash).collect();
+		<CommitCount<T, I>>::remove(now.saturating_sub(T::RevealWindow::get()).saturating_sub(One::one()));
+		for hash in &expired {
+			if let Some((committer, _, deposit)) = <Commitments<T, I>>::take(hash) {
+				let _ = T::Currency::slash_reserved(&committer, deposit);
+				Self::deposit_event(RawEvent::CommitmentExpired(*hash));
+			}
+		}
+
+		T::WeightInfo::expire_commitments(expired.len() as u32)
+	}
+
+	/// Store a public tweet of `author` with the id `id` created in the current block, as
+	/// genesis and `force_create_tweet` do, without deposit, rate limit or notifications.
+	fn insert_system_tweet(id: TweetId, author: &T::AccountId, text: Vec<u8>) -> Result<(), Error<T, I>> {
+		let text = Self::ensure_text(author, text)?;
+		let hashtags = Self::ensure_hashtags(&text)?;
+		let tweet = Tweet {
+			id,
+			create_at: <frame_system::Module<T>>::block_number(),
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text,
+			author: author.clone(),
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: Default::default(),
+			lang: None,
+			reply_policy: ReplyPolicy::Everyone,
+			hidden: false,
+			conversation_id: id,
+			revision: 0,
+			community: None,
+		};
+		Self::push_account_tweet(author, id);
+		Self::index_hashtags(id, &hashtags);
+		<ConversationTweets<I>>::insert(tweet.conversation_id, id, ());
+		<Tweets<T, I>>::insert(id, tweet);
+		Ok(())
+	}
+
+	/// Take the next tweet id. Calls take it inside `transactional`, so that failing ones leave
+	/// `NextTweetId` as it was.
 	fn alloc_id() -> Option<TweetId> {
 		let next = Self::next_tweet_id();
 
 		let new_next = next.checked_add(1)?;
-		NextTweetId::put(new_next);
+		<NextTweetId<I>>::put(new_next);
+
+		Some(next)
+	}
+}
+
+impl<T: Trait<I>, I: Instance> TweetProvider<T::AccountId, T::BlockNumber> for Module<T, I> {
+	fn get(id: TweetId) -> Option<TweetInfo<T::AccountId, T::BlockNumber>> {
+		Self::tweets(id).map(|tweet| TweetInfo {
+			id: tweet.id,
+			author: tweet.author,
+			text: tweet.text.into_inner(),
+			created_at: tweet.create_at,
+			quoted: tweet.quote_tweet_id,
+			parent: tweet.parent_id,
+			conversation_id: tweet.conversation_id,
+			quote_edited: Self::quote_is_stale(tweet.id).unwrap_or(false),
+		})
+	}
+
+	fn author_of(id: TweetId) -> Option<T::AccountId> {
+		Self::tweets(id).map(|tweet| tweet.author)
+	}
+
+	fn exists(id: TweetId) -> bool {
+		<Tweets<T, I>>::contains_key(id)
+	}
+}
 
-		return Some(next);
+impl<T: Trait<I>, I: Instance> ValidateUnsigned for Module<T, I> {
+	type Call = Call<T, I>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::submit_sponsored_tweet(payload, signature) = call {
+			Self::check_sponsored(payload, signature)?;
+			let now = <frame_system::Module<T>>::block_number();
+			let longevity = UniqueSaturatedInto::<u64>::unique_saturated_into(payload.valid_until.saturating_sub(now));
+			ValidTransaction::with_tag_prefix(I::PREFIX)
+				.and_provides((&payload.author, payload.nonce))
+				.longevity(longevity.saturating_add(1))
+				.propagate(true)
+				.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
 	}
 }
@@ -0,0 +1,886 @@
+//! Migrations of the storage of pallet_twitter from the older `Releases`.
+
+use super::*;
+
+/// Layout of `Tweet` before comments recorded their parent.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV1<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) comments: Vec<TweetId>,
+	pub(crate) author: AccountId,
+}
+
+/// Layout of `Tweet` before its text was bounded.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV2<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) comments: Vec<TweetId>,
+	pub(crate) author: AccountId,
+}
+
+/// Layout of `Tweet` before comments moved to the `Comments` map.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV3<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) comments: Vec<TweetId>,
+	pub(crate) author: AccountId,
+}
+
+/// Layout of `Tweet` from `Releases::V4` until comments could be disabled.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV4<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) author: AccountId,
+}
+
+/// Layout of `Tweet` from `Releases::V6` until it had a visibility.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV5<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+}
+
+/// Layout of `Tweet` from `Releases::V8` until it had media attached.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV6<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+}
+
+/// Layout of `Tweet` from `Releases::V10` until tweets could form threads.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV7<AccountId, BlockNumber> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) text: Vec<u8>,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Vec<Vec<u8>>,
+}
+
+/// Layout of `Tweet` from `Releases::V11` until it had a language.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV8<AccountId, BlockNumber, Text, Media> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) thread_prev: Option<TweetId>,
+	pub(crate) text: Text,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Media,
+}
+
+/// Layout of `Tweet` from `Releases::V15` until replies could be restricted.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV9<AccountId, BlockNumber, Text, Media> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) thread_prev: Option<TweetId>,
+	pub(crate) text: Text,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Media,
+	pub(crate) lang: Option<LanguageCode>,
+}
+
+/// Layout of `Tweet` from `Releases::V17` until it could be hidden.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV10<AccountId, BlockNumber, Text, Media> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) thread_prev: Option<TweetId>,
+	pub(crate) text: Text,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Media,
+	pub(crate) lang: Option<LanguageCode>,
+	pub(crate) reply_policy: ReplyPolicy,
+}
+
+/// Layout of `Tweet` from `Releases::V18` until it recorded its conversation.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV11<AccountId, BlockNumber, Text, Media> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) thread_prev: Option<TweetId>,
+	pub(crate) text: Text,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Media,
+	pub(crate) lang: Option<LanguageCode>,
+	pub(crate) reply_policy: ReplyPolicy,
+	pub(crate) hidden: bool,
+}
+
+/// Layout of `Tweet` from `Releases::V21` until its edits were counted.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV12<AccountId, BlockNumber, Text, Media> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) thread_prev: Option<TweetId>,
+	pub(crate) text: Text,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Media,
+	pub(crate) lang: Option<LanguageCode>,
+	pub(crate) reply_policy: ReplyPolicy,
+	pub(crate) hidden: bool,
+	pub(crate) conversation_id: TweetId,
+}
+
+/// Layout of `Tweet` from `Releases::V22` until tweets could be posted to communities.
+#[derive(Encode, Decode)]
+pub(crate) struct TweetV13<AccountId, BlockNumber, Text, Media> {
+	pub(crate) id: TweetId,
+	pub(crate) create_at: BlockNumber,
+	pub(crate) quote_tweet_id: Option<TweetId>,
+	pub(crate) parent_id: Option<TweetId>,
+	pub(crate) thread_prev: Option<TweetId>,
+	pub(crate) text: Text,
+	pub(crate) author: AccountId,
+	pub(crate) comments_enabled: bool,
+	pub(crate) visibility: Visibility,
+	pub(crate) media: Media,
+	pub(crate) lang: Option<LanguageCode>,
+	pub(crate) reply_policy: ReplyPolicy,
+	pub(crate) hidden: bool,
+	pub(crate) conversation_id: TweetId,
+	pub(crate) revision: u32,
+}
+
+/// Storage prefix of the pallet before it was named `Twitter`.
+const OLD_STORAGE_PREFIX: &[u8] = b"TemplateModule";
+
+/// `TweetV8` with the bounded text and media of `TweetOf`.
+type TweetV8Of<T, I> = TweetV8<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+/// `TweetV9` with the bounded text and media of `TweetOf`.
+type TweetV9Of<T, I> = TweetV9<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+/// `TweetV10` with the bounded text and media of `TweetOf`.
+type TweetV10Of<T, I> = TweetV10<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+/// `TweetV11` with the bounded text and media of `TweetOf`.
+type TweetV11Of<T, I> = TweetV11<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+/// `TweetV12` with the bounded text and media of `TweetOf`.
+type TweetV12Of<T, I> = TweetV12<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+/// `TweetV13` with the bounded text and media of `TweetOf`.
+type TweetV13Of<T, I> = TweetV13<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	TextOf<T, I>,
+	MediaOf<T, I>,
+>;
+
+impl<T: Trait<I>, I: Instance> Module<T, I> {
+	/// Bring the storage up to the latest `Releases`, one release at a time.
+	pub(crate) fn migrate() -> Weight {
+		let mut weight = Self::migrate_storage_prefix();
+		if Self::storage_version() == Releases::V1 {
+			weight += Self::migrate_from_v1();
+		}
+		if Self::storage_version() == Releases::V2 {
+			weight += Self::migrate_from_v2();
+		}
+		if Self::storage_version() == Releases::V3 {
+			weight += Self::migrate_from_v3();
+		}
+		if Self::storage_version() == Releases::V4 {
+			weight += Self::migrate_from_v4();
+		}
+		if Self::storage_version() == Releases::V5 {
+			weight += Self::migrate_from_v5();
+		}
+		if Self::storage_version() == Releases::V6 {
+			weight += Self::migrate_from_v6();
+		}
+		if Self::storage_version() == Releases::V7 {
+			weight += Self::migrate_from_v7();
+		}
+		if Self::storage_version() == Releases::V8 {
+			weight += Self::migrate_from_v8();
+		}
+		if Self::storage_version() == Releases::V9 {
+			weight += Self::migrate_from_v9();
+		}
+		if Self::storage_version() == Releases::V10 {
+			weight += Self::migrate_from_v10();
+		}
+		if Self::storage_version() == Releases::V11 {
+			weight += Self::migrate_from_v11();
+		}
+		if Self::storage_version() == Releases::V12 {
+			weight += Self::migrate_from_v12();
+		}
+		if Self::storage_version() == Releases::V13 {
+			weight += Self::migrate_from_v13();
+		}
+		if Self::storage_version() == Releases::V14 {
+			weight += Self::migrate_from_v14();
+		}
+		if Self::storage_version() == Releases::V15 {
+			weight += Self::migrate_from_v15();
+		}
+		if Self::storage_version() == Releases::V16 {
+			weight += Self::migrate_from_v16();
+		}
+		if Self::storage_version() == Releases::V17 {
+			weight += Self::migrate_from_v17();
+		}
+		if Self::storage_version() == Releases::V18 {
+			weight += Self::migrate_from_v18();
+		}
+		if Self::storage_version() == Releases::V19 {
+			weight += Self::migrate_from_v19();
+		}
+		if Self::storage_version() == Releases::V20 {
+			weight += Self::migrate_from_v20();
+		}
+		if Self::storage_version() == Releases::V21 {
+			weight += Self::migrate_from_v21();
+		}
+		if Self::storage_version() == Releases::V22 {
+			weight += Self::migrate_from_v22();
+		}
+		if Self::storage_version() == Releases::V23 {
+			weight += Self::migrate_from_v23();
+		}
+		if Self::storage_version() == Releases::V24 {
+			weight += Self::migrate_from_v24();
+		}
+		if Self::storage_version() == Releases::V25 {
+			weight += Self::migrate_from_v25();
+		}
+		if Self::storage_version() == Releases::V26 {
+			weight += Self::migrate_from_v26();
+		}
+		weight
+	}
+
+	/// Iterate the raw `Tweets` storage, decoding each value as `Old`.
+	fn stored_tweets<Old: Decode>() -> StorageIterator<Old> {
+		StorageIterator::new(
+			<Tweets<T, I> as StoragePrefixedMap<TweetOf<T, I>>>::module_prefix(),
+			<Tweets<T, I> as StoragePrefixedMap<TweetOf<T, I>>>::storage_prefix(),
+		)
+	}
+
+	/// Re-encode every value of `Tweets` from layout `Old` to layout `New`.
+	///
+	/// Returns the number of translated tweets.
+	fn translate_tweets<Old: Decode, New: Encode>(f: impl Fn(Old) -> New) -> Weight {
+		let mut count = 0;
+		for (hash, old) in Self::stored_tweets::<Old>() {
+			put_storage_value(
+				<Tweets<T, I> as StoragePrefixedMap<TweetOf<T, I>>>::module_prefix(),
+				<Tweets<T, I> as StoragePrefixedMap<TweetOf<T, I>>>::storage_prefix(),
+				&hash,
+				f(old),
+			);
+			count += 1;
+		}
+		count
+	}
+
+	/// Move every storage item from the `TemplateModule` prefix, left over from the node
+	/// template, to this pallet's own prefix. Does nothing once no key is left under the old one,
+	/// or for instances other than the default one, which never used it.
+	fn migrate_storage_prefix() -> Weight {
+		if I::PREFIX != DefaultInstance::PREFIX {
+			return 0;
+		}
+		let old = Twox128::hash(OLD_STORAGE_PREFIX);
+		let new = Twox128::hash(<Tweets<T, I> as StoragePrefixedMap<TweetOf<T, I>>>::module_prefix());
+		let mut count = 0;
+		let mut key = old.to_vec();
+		while let Some(next) = sp_io::storage::next_key(&key).filter(|next| next.starts_with(&old)) {
+			if let Some(value) = unhashed::get_raw(&next) {
+				unhashed::put_raw(&[&new[..], &next[old.len()..]].concat(), &value);
+			}
+			unhashed::kill(&next);
+			count += 1;
+			key = next;
+		}
+
+		T::DbWeight::get().reads_writes(count + 1, 2 * count)
+	}
+
+	/// Add `parent_id` to every stored tweet, derived from the `comments` lists.
+	fn migrate_from_v1() -> Weight {
+		let parents: BTreeMap<TweetId, TweetId> = Self::stored_tweets::<TweetV1<T::AccountId, T::BlockNumber>>()
+			.flat_map(|(_, tweet)| {
+				let parent = tweet.id;
+				tweet.comments.into_iter().map(move |comment| (comment, parent))
+			})
+			.collect();
+
+		let count = Self::translate_tweets(|old: TweetV1<T::AccountId, T::BlockNumber>| TweetV2 {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: parents.get(&old.id).copied(),
+			text: old.text,
+			comments: old.comments,
+			author: old.author,
+		});
+		<StorageVersion<I>>::put(Releases::V2);
+
+		T::DbWeight::get().reads_writes(2 * count, count + 1)
+	}
+
+	/// Truncate the text of every stored tweet to `MaxTextLen`.
+	fn migrate_from_v2() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV2<T::AccountId, T::BlockNumber>| {
+			let mut text = old.text;
+			text.truncate(T::MaxTextLen::get() as usize);
+			TweetV3 {
+				id: old.id,
+				create_at: old.create_at,
+				quote_tweet_id: old.quote_tweet_id,
+				parent_id: old.parent_id,
+				text,
+				comments: old.comments,
+				author: old.author,
+			}
+		});
+		<StorageVersion<I>>::put(Releases::V3);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Move the `comments` list of every stored tweet into the `Comments` map.
+	fn migrate_from_v3() -> Weight {
+		let moved = Cell::new(0);
+		let count = Self::translate_tweets(|old: TweetV3<T::AccountId, T::BlockNumber>| {
+			for (index, comment) in old.comments.iter().enumerate() {
+				<Comments<I>>::insert(old.id, index as u32, comment);
+			}
+			<CommentCount<I>>::insert(old.id, old.comments.len() as u32);
+			moved.set(moved.get() + old.comments.len() as Weight);
+
+			TweetV4 {
+				id: old.id,
+				create_at: old.create_at,
+				quote_tweet_id: old.quote_tweet_id,
+				parent_id: old.parent_id,
+				text: old.text,
+				author: old.author,
+			}
+		});
+		<StorageVersion<I>>::put(Releases::V4);
+
+		T::DbWeight::get().reads_writes(count, 2 * count + moved.get() + 1)
+	}
+
+	/// Move the tweet list of every account from `Accounts` into the `AccountTweets` map.
+	fn migrate_from_v4() -> Weight {
+		let mut count = 0;
+		let mut moved = 0;
+		let accounts = StorageKeyIterator::<T::AccountId, Vec<TweetId>, Blake2_128Concat>::new(
+			<Tweets<T, I> as StoragePrefixedMap<TweetOf<T, I>>>::module_prefix(),
+			b"Accounts",
+		).drain();
+		for (who, tweets) in accounts {
+			for (index, id) in tweets.iter().enumerate() {
+				<AccountTweets<T, I>>::insert(&who, index as u64, id);
+			}
+			<AccountTweetCount<T, I>>::insert(&who, tweets.len() as u64);
+			count += 1;
+			moved += tweets.len() as Weight;
+		}
+		<StorageVersion<I>>::put(Releases::V5);
+
+		T::DbWeight::get().reads_writes(count, 2 * count + moved + 1)
+	}
+
+	/// Enable comments on every stored tweet.
+	fn migrate_from_v5() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV4<T::AccountId, T::BlockNumber>| TweetV5 {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			text: old.text,
+			author: old.author,
+			comments_enabled: true,
+		});
+		<StorageVersion<I>>::put(Releases::V6);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Move the comment indices to `NextCommentIndex`, then count the existing comments and
+	/// retweets of every tweet.
+	fn migrate_from_v6() -> Weight {
+		let mut moved = 0;
+		for (id, next_index) in <CommentCount<I>>::drain() {
+			<NextCommentIndex<I>>::insert(id, next_index);
+			moved += 1;
+		}
+
+		let mut count = 0;
+		for (_, tweet) in Self::stored_tweets::<TweetV5<T::AccountId, T::BlockNumber>>() {
+			if let Some(parent) = tweet.parent_id.filter(|id| <Tweets<T, I>>::contains_key(*id)) {
+				<CommentCount<I>>::mutate(parent, |count| *count = count.saturating_add(1));
+			}
+			if let Some(original) = tweet.quote_tweet_id.filter(|id| <Tweets<T, I>>::contains_key(*id)) {
+				<RetweetCount<I>>::mutate(original, |count| *count = count.saturating_add(1));
+			}
+			count += 1;
+		}
+		<StorageVersion<I>>::put(Releases::V7);
+
+		T::DbWeight::get().reads_writes(moved + 5 * count, 2 * moved + 2 * count + 1)
+	}
+
+	/// Make every stored tweet public.
+	fn migrate_from_v7() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV5<T::AccountId, T::BlockNumber>| TweetV6 {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: Visibility::Public,
+		});
+		<StorageVersion<I>>::put(Releases::V8);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Count the stored tweets, in total and by author.
+	fn migrate_from_v8() -> Weight {
+		let mut count: u128 = 0;
+		for (_, tweet) in Self::stored_tweets::<TweetV6<T::AccountId, T::BlockNumber>>() {
+			<TweetCountOf<T, I>>::mutate(&tweet.author, |count| *count = count.saturating_add(1));
+			count = count.saturating_add(1);
+		}
+		<TweetCount<I>>::put(count);
+		<StorageVersion<I>>::put(Releases::V9);
+
+		let count = count as Weight;
+		T::DbWeight::get().reads_writes(2 * count, count + 2)
+	}
+
+	/// Add an empty `media` list to every stored tweet.
+	fn migrate_from_v9() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV6<T::AccountId, T::BlockNumber>| TweetV7 {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: Vec::new(),
+		});
+		<StorageVersion<I>>::put(Releases::V10);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Mark every stored tweet as not continuing a thread.
+	fn migrate_from_v10() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV7<T::AccountId, T::BlockNumber>| TweetV8 {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			thread_prev: None,
+			text: TextOf::<T, I>::truncate_from(old.text),
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: MediaOf::<T, I>::truncate_from(
+				old.media.into_iter().map(MediaCid::truncate_from).collect(),
+			),
+		});
+		<StorageVersion<I>>::put(Releases::V11);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Turn the mentions in `Notifications` into `Notification::Mentioned`, dropping those of
+	/// deleted tweets, and start counting notifications.
+	fn migrate_from_v11() -> Weight {
+		let count = Cell::new(0);
+		let read = Cell::new(0);
+		<Notifications<T, I>>::translate(|who, ids: Vec<TweetId>| {
+			count.set(count.get() + 1);
+			read.set(read.get() + ids.len() as Weight);
+			let inbox: Vec<_> = ids.into_iter()
+				.filter_map(|id| Self::tweets(id).map(|tweet| Notification::Mentioned(tweet.author, id)))
+				.collect();
+			<NotificationCount<T, I>>::insert(&who, inbox.len() as u64);
+			Some(NotificationsOf::<T, I>::truncate_from(inbox))
+		});
+		<StorageVersion<I>>::put(Releases::V12);
+
+		T::DbWeight::get().reads_writes(count.get() + read.get(), 2 * count.get() + 1)
+	}
+
+	/// Rank the existing tweets in `TopTweets` by their `LikeCount`, skipping tombstones and
+	/// censored tweets.
+	fn migrate_from_v12() -> Weight {
+		let mut count = 0;
+		for (id, likes) in <LikeCount<I>>::iter() {
+			count += 1;
+			if <Tweets<T, I>>::contains_key(id) && !Self::is_deleted(id) {
+				Self::rank_tweet(id, likes);
+			}
+		}
+		<StorageVersion<I>>::put(Releases::V13);
+
+		T::DbWeight::get().reads_writes(5 * count, count + 1)
+	}
+
+	/// Index the likes of each account in `AccountLikes`.
+	fn migrate_from_v13() -> Weight {
+		let mut count = 0;
+		for (tweet_id, who, liked) in <Likes<T, I>>::iter() {
+			count += 1;
+			if liked {
+				<AccountLikes<T, I>>::insert(&who, tweet_id, ());
+				<AccountLikeCount<T, I>>::mutate(&who, |count| *count = count.saturating_add(1));
+			}
+		}
+		<StorageVersion<I>>::put(Releases::V14);
+
+		T::DbWeight::get().reads_writes(2 * count, 2 * count + 1)
+	}
+
+	/// Add `Tweet::lang`, unknown for the existing tweets.
+	fn migrate_from_v14() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV8Of<T, I>| TweetV9Of::<T, I> {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			thread_prev: old.thread_prev,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: old.media,
+			lang: None,
+		});
+		<StorageVersion<I>>::put(Releases::V15);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Index the existing tweets in `TweetsByBlock`.
+	fn migrate_from_v15() -> Weight {
+		let mut count = 0;
+		for (_, tweet) in Self::stored_tweets::<TweetV9Of<T, I>>() {
+			count += 1;
+			<TweetsByBlock<T, I>>::insert(tweet.create_at, tweet.id, ());
+		}
+		<StorageVersion<I>>::put(Releases::V16);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Add `Tweet::reply_policy`, letting everyone reply to the existing tweets.
+	fn migrate_from_v16() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV9Of<T, I>| TweetV10Of::<T, I> {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			thread_prev: old.thread_prev,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: old.media,
+			lang: old.lang,
+			reply_policy: ReplyPolicy::Everyone,
+		});
+		<StorageVersion<I>>::put(Releases::V17);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Add `Tweet::hidden`, leaving the existing tweets visible whatever their reports.
+	fn migrate_from_v17() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV10Of<T, I>| TweetV11Of::<T, I> {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			thread_prev: old.thread_prev,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: old.media,
+			lang: old.lang,
+			reply_policy: old.reply_policy,
+			hidden: false,
+		});
+		<StorageVersion<I>>::put(Releases::V18);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Record in `FirstTweetAt` the block of the oldest stored tweet of each account.
+	fn migrate_from_v18() -> Weight {
+		let mut count = 0;
+		for (_, tweet) in Self::stored_tweets::<TweetV11Of<T, I>>() {
+			count += 1;
+			<FirstTweetAt<T, I>>::mutate(&tweet.author, |first| {
+				if first.map_or(true, |first| tweet.create_at < first) {
+					*first = Some(tweet.create_at);
+				}
+			});
+		}
+		<StorageVersion<I>>::put(Releases::V19);
+
+		T::DbWeight::get().reads_writes(2 * count, count + 1)
+	}
+
+	/// Fill `LikePreview` and `RetweetPreview` from the existing likes, retweets and reposts,
+	/// in storage order.
+	fn migrate_from_v19() -> Weight {
+		let mut count = 0;
+		for (tweet_id, who, liked) in <Likes<T, I>>::iter() {
+			count += 1;
+			if liked {
+				<LikePreview<T, I>>::mutate(tweet_id, |preview| Self::add_to_preview(preview, &who));
+			}
+		}
+		let retweeters = <Retweeted<T, I>>::iter().map(|(original, who, _)| (original, who));
+		let reposters = <Reposts<T, I>>::iter().map(|(original, who, _)| (original, who));
+		for (original, who) in retweeters.chain(reposters) {
+			count += 1;
+			<RetweetPreview<T, I>>::mutate(original, |preview| Self::add_to_preview(preview, &who));
+		}
+		<StorageVersion<I>>::put(Releases::V20);
+
+		T::DbWeight::get().reads_writes(2 * count, count + 1)
+	}
+
+	/// Add `Tweet::conversation_id` and index the existing tweets in `ConversationTweets`.
+	///
+	/// Comments and continued threads join the conversation of the tweet they follow, retweets
+	/// that of the tweet they quote if `QuotesJoinConversation` is set, and other tweets start
+	/// their own. A tweet following one that is no longer stored joins the conversation started
+	/// by the missing tweet.
+	fn migrate_from_v20() -> Weight {
+		let mut reads = 0;
+		let follows: BTreeMap<TweetId, TweetId> = Self::stored_tweets::<TweetV11Of<T, I>>()
+			.filter_map(|(_, tweet)| {
+				reads += 1;
+				let quoted = tweet.quote_tweet_id.filter(|original| {
+					reads += 1;
+					T::QuotesJoinConversation::get() && Self::reposts(original, &tweet.author) != Some(tweet.id)
+				});
+				tweet.parent_id.or(tweet.thread_prev).or(quoted).map(|followed| (tweet.id, followed))
+			})
+			.collect();
+		let conversation_of = |mut id: TweetId| {
+			while let Some(followed) = follows.get(&id) {
+				id = *followed;
+			}
+			id
+		};
+
+		let count = Self::translate_tweets(|old: TweetV11Of<T, I>| {
+			let conversation_id = conversation_of(old.id);
+			<ConversationTweets<I>>::insert(conversation_id, old.id, ());
+			TweetV12Of::<T, I> {
+				id: old.id,
+				create_at: old.create_at,
+				quote_tweet_id: old.quote_tweet_id,
+				parent_id: old.parent_id,
+				thread_prev: old.thread_prev,
+				text: old.text,
+				author: old.author,
+				comments_enabled: old.comments_enabled,
+				visibility: old.visibility,
+				media: old.media,
+				lang: old.lang,
+				reply_policy: old.reply_policy,
+				hidden: old.hidden,
+				conversation_id,
+			}
+		});
+		<StorageVersion<I>>::put(Releases::V21);
+
+		T::DbWeight::get().reads_writes(reads + count, 2 * count + 1)
+	}
+
+	/// Add `Tweet::revision`, the existing tweets having no `EditHistory`.
+	fn migrate_from_v21() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV12Of<T, I>| TweetV13Of::<T, I> {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			thread_prev: old.thread_prev,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: old.media,
+			lang: old.lang,
+			reply_policy: old.reply_policy,
+			hidden: old.hidden,
+			conversation_id: old.conversation_id,
+			revision: 0,
+		});
+		<StorageVersion<I>>::put(Releases::V22);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Add `Tweet::community`, the existing tweets being posted to none.
+	fn migrate_from_v22() -> Weight {
+		let count = Self::translate_tweets(|old: TweetV13Of<T, I>| TweetOf::<T, I> {
+			id: old.id,
+			create_at: old.create_at,
+			quote_tweet_id: old.quote_tweet_id,
+			parent_id: old.parent_id,
+			thread_prev: old.thread_prev,
+			text: old.text,
+			author: old.author,
+			comments_enabled: old.comments_enabled,
+			visibility: old.visibility,
+			media: old.media,
+			lang: old.lang,
+			reply_policy: old.reply_policy,
+			hidden: old.hidden,
+			conversation_id: old.conversation_id,
+			revision: old.revision,
+			community: None,
+		});
+		<StorageVersion<I>>::put(Releases::V23);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Move `NextTweetId` past the ids reserved for system tweets. Tweets already posted with
+	/// reserved ids keep them.
+	fn migrate_from_v23() -> Weight {
+		<NextTweetId<I>>::mutate(|next| *next = (*next).max(T::FirstUserTweetId::get()));
+		<StorageVersion<I>>::put(Releases::V24);
+
+		T::DbWeight::get().reads_writes(1, 2)
+	}
+
+	/// Record the index of every comment in `CommentIndex` and drop the `Comments` entries of
+	/// the comments that were removed.
+	fn migrate_from_v24() -> Weight {
+		let comments: Vec<_> = <Comments<I>>::iter().collect();
+		let count = comments.len() as Weight;
+		for (parent, index, id) in comments {
+			if <Tweets<T, I>>::contains_key(id) {
+				<CommentIndex<I>>::insert(id, index);
+			} else {
+				<Comments<I>>::remove(parent, index);
+			}
+		}
+		<StorageVersion<I>>::put(Releases::V25);
+
+		T::DbWeight::get().reads_writes(2 * count, count + 1)
+	}
+
+	/// Record every follow in `Followers` as well.
+	fn migrate_from_v25() -> Weight {
+		let mut count = 0;
+		for (who, target, ()) in <Following<T, I>>::iter() {
+			<Followers<T, I>>::insert(&target, &who, ());
+			count += 1;
+		}
+		<StorageVersion<I>>::put(Releases::V26);
+
+		T::DbWeight::get().reads_writes(count, count + 1)
+	}
+
+	/// Start an `ExpiryCursor` never moved at the current block, so that the first
+	/// `on_initialize` does not scan every block since genesis. Tweets only expire after the
+	/// block they are posted in, so no expiry is skipped.
+	fn migrate_from_v26() -> Weight {
+		if ExpiryCursor::<T, I>::get().is_zero() {
+			ExpiryCursor::<T, I>::put(<frame_system::Module<T>>::block_number());
+		}
+		<StorageVersion<I>>::put(Releases::V27);
+
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+}
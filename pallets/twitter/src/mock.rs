@@ -1,15 +1,29 @@
-use crate::{Module, Trait};
+use crate::{Module, Trait, GenesisConfig, Instance1, OnTweetCreated, IdentityProvider, TweetId, TweetKind};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, traits::Get, weights::Weight};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, UintAuthorityId}, Perbill,
 };
 use frame_system as system;
+use std::cell::RefCell;
 
 impl_outer_origin! {
 	pub enum Origin for Test {}
 }
 
+mod twitter {
+	pub use crate::{Event, Instance1};
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		twitter<T>,
+		twitter Instance1<T>,
+	}
+}
+
 // Configure a mock runtime to test the pallet.
 
 #[derive(Clone, Eq, PartialEq)]
@@ -19,6 +33,53 @@ parameter_types! {
 	pub const MaximumBlockWeight: Weight = 1024;
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+	pub const EditWindow: u64 = 10;
+	pub const MaxEdits: u32 = 2;
+	pub const MaxTextBytes: u32 = 560;
+	pub const MaxHashtagsPerTweet: u32 = 3;
+	pub const MaxMentions: u32 = 2;
+	pub const MaxAttachments: u32 = 2;
+	pub const RateLimitPeriod: u64 = 5;
+	pub const FreeTweetsPerDay: u32 = 2;
+	pub const DayLength: u64 = 10;
+	pub const MinBalanceForFreeTweets: u64 = 50;
+	pub const MaxSponsoredPerBlock: u32 = 2;
+	pub const MaxNotifications: u32 = 3;
+	pub const RecentBufferSize: u32 = 3;
+	pub const MaxBlocked: u32 = 2;
+	pub const MaxMutedWords: u32 = 2;
+	pub const MaxDelegates: u32 = 2;
+	pub const MaxBookmarks: u32 = 2;
+	pub const MaxThreadLen: u32 = 3;
+	pub const MaxExpirationsPerBlock: u32 = 2;
+	pub const MaxScheduledPerBlock: u32 = 2;
+	pub const MaxQuoteDepth: u32 = 3;
+	pub const MaxLeaderboardSize: u32 = 3;
+	pub const TrendingWindow: u64 = 10;
+	pub const KeepWindows: u32 = 1;
+	pub const MaxHashtagPrunesPerBlock: u32 = 3;
+	pub const MaxContentPrunesPerBlock: u32 = 3;
+	pub const MaxCommentsPerTweet: u32 = 5;
+	pub const MaxFollowedHashtags: u32 = 2;
+	pub const MaxHashtagFanout: u32 = 2;
+	pub const MaxNotesPerTweet: u32 = 2;
+	pub const MaxCommunities: u32 = 2;
+	pub const MaxMembersTracked: u32 = 3;
+	pub const RevealWindow: u64 = 5;
+	pub const MaxCommitsPerBlock: u32 = 2;
+	pub const AnonymousAccount: u64 = 99;
+	pub const MinNoteRatings: u32 = 2;
+	pub const NoteShowThreshold: Perbill = Perbill::from_percent(60);
+	pub const ExistentialDeposit: u64 = 1;
+	pub const TweetDepositBase: u64 = 10;
+	pub const ProfileDeposit: u64 = 25;
+	pub const HandleDeposit: u64 = 30;
+	pub const PremiumFee: u64 = 40;
+	pub const PremiumPeriod: u64 = 10;
+	pub const SubscriptionPeriod: u64 = 10;
+	pub const CommitDeposit: u64 = 10;
+	pub const MaxTextLenPremium: u32 = 400;
+	pub const AutoHideThreshold: u32 = 3;
 }
 
 impl system::Trait for Test {
@@ -32,7 +93,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -43,19 +104,476 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+impl pallet_balances::Trait for Test {
+	type MaxLocks = ();
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static MAX_TEXT_LEN: RefCell<u32> = RefCell::new(140);
+	static SLASH_CENSORED_DEPOSIT: RefCell<bool> = RefCell::new(true);
+	static MAX_TWEETS_PER_PERIOD: RefCell<u32> = RefCell::new(100);
+	static MAX_TWEETS_PER_ACCOUNT: RefCell<u32> = RefCell::new(100);
+	static DEDUP_WINDOW: RefCell<u64> = RefCell::new(0);
+	pub static TWEET_DEPOSIT_PER_BYTE: RefCell<u64> = RefCell::new(0);
+	static MIN_REPORTER_AGE: RefCell<u64> = RefCell::new(0);
+	static RECENT_SKIPS_COMMENTS: RefCell<bool> = RefCell::new(false);
+	static QUOTES_JOIN_CONVERSATION: RefCell<bool> = RefCell::new(false);
+	static FIRST_USER_TWEET_ID: RefCell<TweetId> = RefCell::new(0);
+	static CREATED_TWEETS: RefCell<Vec<(u64, TweetId, TweetKind)>> = RefCell::new(vec![]);
+}
+
+pub struct MaxTextLen;
+impl Get<u32> for MaxTextLen {
+	fn get() -> u32 {
+		MAX_TEXT_LEN.with(|v| *v.borrow())
+	}
+}
+
+pub struct MaxTweetsPerPeriod;
+impl Get<u32> for MaxTweetsPerPeriod {
+	fn get() -> u32 {
+		MAX_TWEETS_PER_PERIOD.with(|v| *v.borrow())
+	}
+}
+
+pub struct MaxTweetsPerAccount;
+impl Get<u32> for MaxTweetsPerAccount {
+	fn get() -> u32 {
+		MAX_TWEETS_PER_ACCOUNT.with(|v| *v.borrow())
+	}
+}
+
+pub struct DedupWindow;
+impl Get<u64> for DedupWindow {
+	fn get() -> u64 {
+		DEDUP_WINDOW.with(|v| *v.borrow())
+	}
+}
+
+pub struct TweetDepositPerByte;
+impl Get<u64> for TweetDepositPerByte {
+	fn get() -> u64 {
+		TWEET_DEPOSIT_PER_BYTE.with(|v| *v.borrow())
+	}
+}
+
+pub struct FirstUserTweetId;
+impl Get<TweetId> for FirstUserTweetId {
+	fn get() -> TweetId {
+		FIRST_USER_TWEET_ID.with(|v| *v.borrow())
+	}
+}
+
+/// Records every created tweet, see `created_tweets`.
+pub struct RecordTweets;
+impl OnTweetCreated<u64> for RecordTweets {
+	fn on_tweet(author: &u64, id: TweetId, kind: TweetKind) {
+		CREATED_TWEETS.with(|v| v.borrow_mut().push((*author, id, kind)));
+	}
+}
+
+/// Tweets reported to `TweetHooks` so far, by `(author, id, kind)`.
+pub fn created_tweets() -> Vec<(u64, TweetId, TweetKind)> {
+	CREATED_TWEETS.with(|v| v.borrow().clone())
+}
+
+/// Account 1 has a judged identity named "alice", account 2 one named "bob" without judgement.
+pub struct Identities;
+impl IdentityProvider<u64> for Identities {
+	fn display_name(who: &u64) -> Option<Vec<u8>> {
+		match who {
+			1 => Some(b"alice".to_vec()),
+			2 => Some(b"bob".to_vec()),
+			_ => None,
+		}
+	}
+
+	fn is_judged(who: &u64) -> bool {
+		*who == 1
+	}
+}
+
+pub struct MinReporterAge;
+impl Get<u64> for MinReporterAge {
+	fn get() -> u64 {
+		MIN_REPORTER_AGE.with(|v| *v.borrow())
+	}
+}
+
+pub struct RecentSkipsComments;
+impl Get<bool> for RecentSkipsComments {
+	fn get() -> bool {
+		RECENT_SKIPS_COMMENTS.with(|v| *v.borrow())
+	}
+}
+
+pub struct QuotesJoinConversation;
+impl Get<bool> for QuotesJoinConversation {
+	fn get() -> bool {
+		QUOTES_JOIN_CONVERSATION.with(|v| *v.borrow())
+	}
+}
+
+pub struct SlashCensoredDeposit;
+impl Get<bool> for SlashCensoredDeposit {
+	fn get() -> bool {
+		SLASH_CENSORED_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+
 impl Trait for Test {
-	type Event = ();
+	type Event = TestEvent;
+	type MaxTextLen = MaxTextLen;
+	type MaxTextBytes = MaxTextBytes;
+	type EditWindow = EditWindow;
+	type MaxEdits = MaxEdits;
+	type MaxHashtagsPerTweet = MaxHashtagsPerTweet;
+	type MaxMentions = MaxMentions;
+	type MaxAttachments = MaxAttachments;
+	type RateLimitPeriod = RateLimitPeriod;
+	type MaxTweetsPerPeriod = MaxTweetsPerPeriod;
+	type FreeTweetsPerDay = FreeTweetsPerDay;
+	type DayLength = DayLength;
+	type MinBalanceForFreeTweets = MinBalanceForFreeTweets;
+	type MaxSponsoredPerBlock = MaxSponsoredPerBlock;
+	type Signature = TestSignature;
+	type Signer = UintAuthorityId;
+	type MaxTweetsPerAccount = MaxTweetsPerAccount;
+	type MaxNotifications = MaxNotifications;
+	type RecentBufferSize = RecentBufferSize;
+	type RecentSkipsComments = RecentSkipsComments;
+	type MaxBlocked = MaxBlocked;
+	type MaxMutedWords = MaxMutedWords;
+	type MaxDelegates = MaxDelegates;
+	type MaxBookmarks = MaxBookmarks;
+	type MaxThreadLen = MaxThreadLen;
+	type MaxExpirationsPerBlock = MaxExpirationsPerBlock;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type MaxQuoteDepth = MaxQuoteDepth;
+	type MaxLeaderboardSize = MaxLeaderboardSize;
+	type TrendingWindow = TrendingWindow;
+	type KeepWindows = KeepWindows;
+	type MaxHashtagPrunesPerBlock = MaxHashtagPrunesPerBlock;
+	type DedupWindow = DedupWindow;
+	type MaxContentPrunesPerBlock = MaxContentPrunesPerBlock;
+	type MaxCommentsPerTweet = MaxCommentsPerTweet;
+	type MaxFollowedHashtags = MaxFollowedHashtags;
+	type MaxHashtagFanout = MaxHashtagFanout;
+	type MaxNotesPerTweet = MaxNotesPerTweet;
+	type MaxCommunities = MaxCommunities;
+	type MaxMembersTracked = MaxMembersTracked;
+	type RevealWindow = RevealWindow;
+	type MaxCommitsPerBlock = MaxCommitsPerBlock;
+	type AnonymousAccount = AnonymousAccount;
+	type FirstUserTweetId = FirstUserTweetId;
+	type MinNoteRatings = MinNoteRatings;
+	type NoteShowThreshold = NoteShowThreshold;
+	type Currency = Balances;
+	type TweetDepositBase = TweetDepositBase;
+	type TweetDepositPerByte = TweetDepositPerByte;
+	type ProfileDeposit = ProfileDeposit;
+	type HandleDeposit = HandleDeposit;
+	type PremiumFee = PremiumFee;
+	type PremiumPeriod = PremiumPeriod;
+	type SubscriptionPeriod = SubscriptionPeriod;
+	type CommitDeposit = CommitDeposit;
+	type MaxTextLenPremium = MaxTextLenPremium;
+	type AutoHideThreshold = AutoHideThreshold;
+	type MinReporterAge = MinReporterAge;
+	type ModerationOrigin = system::EnsureRoot<u64>;
+	type ForceRemoveOrigin = system::EnsureRoot<u64>;
+	type AnnouncementOrigin = system::EnsureRoot<u64>;
+	type SlashCensoredDeposit = SlashCensoredDeposit;
+	type QuotesJoinConversation = QuotesJoinConversation;
+	type VerifyOrigin = system::EnsureRoot<u64>;
+	type PruneOrigin = system::EnsureRoot<u64>;
+	type TweetHooks = RecordTweets;
+	type Identity = Identities;
+	type WeightInfo = ();
 }
 
-pub type TemplateModule = Module<Test>;
+// A second, independent feed.
+impl Trait<Instance1> for Test {
+	type Event = TestEvent;
+	type MaxTextLen = MaxTextLen;
+	type MaxTextBytes = MaxTextBytes;
+	type EditWindow = EditWindow;
+	type MaxEdits = MaxEdits;
+	type MaxHashtagsPerTweet = MaxHashtagsPerTweet;
+	type MaxMentions = MaxMentions;
+	type MaxAttachments = MaxAttachments;
+	type RateLimitPeriod = RateLimitPeriod;
+	type MaxTweetsPerPeriod = MaxTweetsPerPeriod;
+	type FreeTweetsPerDay = FreeTweetsPerDay;
+	type DayLength = DayLength;
+	type MinBalanceForFreeTweets = MinBalanceForFreeTweets;
+	type MaxSponsoredPerBlock = MaxSponsoredPerBlock;
+	type Signature = TestSignature;
+	type Signer = UintAuthorityId;
+	type MaxTweetsPerAccount = MaxTweetsPerAccount;
+	type MaxNotifications = MaxNotifications;
+	type RecentBufferSize = RecentBufferSize;
+	type RecentSkipsComments = RecentSkipsComments;
+	type MaxBlocked = MaxBlocked;
+	type MaxMutedWords = MaxMutedWords;
+	type MaxDelegates = MaxDelegates;
+	type MaxBookmarks = MaxBookmarks;
+	type MaxThreadLen = MaxThreadLen;
+	type MaxExpirationsPerBlock = MaxExpirationsPerBlock;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type MaxQuoteDepth = MaxQuoteDepth;
+	type MaxLeaderboardSize = MaxLeaderboardSize;
+	type TrendingWindow = TrendingWindow;
+	type KeepWindows = KeepWindows;
+	type MaxHashtagPrunesPerBlock = MaxHashtagPrunesPerBlock;
+	type DedupWindow = DedupWindow;
+	type MaxContentPrunesPerBlock = MaxContentPrunesPerBlock;
+	type MaxCommentsPerTweet = MaxCommentsPerTweet;
+	type MaxFollowedHashtags = MaxFollowedHashtags;
+	type MaxHashtagFanout = MaxHashtagFanout;
+	type MaxNotesPerTweet = MaxNotesPerTweet;
+	type MaxCommunities = MaxCommunities;
+	type MaxMembersTracked = MaxMembersTracked;
+	type RevealWindow = RevealWindow;
+	type MaxCommitsPerBlock = MaxCommitsPerBlock;
+	type AnonymousAccount = AnonymousAccount;
+	type FirstUserTweetId = FirstUserTweetId;
+	type MinNoteRatings = MinNoteRatings;
+	type NoteShowThreshold = NoteShowThreshold;
+	type Currency = Balances;
+	type TweetDepositBase = TweetDepositBase;
+	type TweetDepositPerByte = TweetDepositPerByte;
+	type ProfileDeposit = ProfileDeposit;
+	type HandleDeposit = HandleDeposit;
+	type PremiumFee = PremiumFee;
+	type PremiumPeriod = PremiumPeriod;
+	type SubscriptionPeriod = SubscriptionPeriod;
+	type CommitDeposit = CommitDeposit;
+	type MaxTextLenPremium = MaxTextLenPremium;
+	type AutoHideThreshold = AutoHideThreshold;
+	type MinReporterAge = MinReporterAge;
+	type ModerationOrigin = system::EnsureRoot<u64>;
+	type ForceRemoveOrigin = system::EnsureRoot<u64>;
+	type AnnouncementOrigin = system::EnsureRoot<u64>;
+	type SlashCensoredDeposit = SlashCensoredDeposit;
+	type QuotesJoinConversation = QuotesJoinConversation;
+	type VerifyOrigin = system::EnsureRoot<u64>;
+	type PruneOrigin = system::EnsureRoot<u64>;
+	type TweetHooks = ();
+	type Identity = ();
+	type WeightInfo = ();
+}
+
+/// Pallet paying the authors of tweets a reward once per tweet, written against
+/// `TweetProvider` alone the way a pallet of another crate would be.
+pub mod rewards {
+	use frame_support::{decl_module, decl_storage, StorageMap, traits::{Currency, Get}};
+	use crate::TweetProvider;
+
+	pub trait Trait: frame_system::Trait {
+		type Currency: Currency<Self::AccountId>;
+		type Tweets: TweetProvider<Self::AccountId, Self::BlockNumber>;
+		type Reward: Get<<Self::Currency as Currency<Self::AccountId>>::Balance>;
+	}
+
+	decl_storage! {
+		trait Store for Module<T: Trait> as Rewards {
+			Rewarded get(fn rewarded): map hasher(twox_64_concat) u128 => bool;
+		}
+	}
+
+	decl_module! {
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	}
+
+	impl<T: Trait> Module<T> {
+		/// Pay the author of tweet `id` unless it is a comment or was already rewarded,
+		/// returning the author paid.
+		pub fn reward(id: u128) -> Option<T::AccountId> {
+			if !T::Tweets::exists(id) || Self::rewarded(id) {
+				return None;
+			}
+			let tweet = T::Tweets::get(id)?;
+			if tweet.parent.is_some() || tweet.text.is_empty() {
+				return None;
+			}
+			T::Currency::deposit_creating(&tweet.author, T::Reward::get());
+			Rewarded::insert(id, true);
+			T::Tweets::author_of(id)
+		}
+	}
+}
+
+parameter_types! {
+	pub const Reward: u64 = 7;
+}
+
+impl rewards::Trait for Test {
+	type Currency = Balances;
+	type Tweets = Twitter;
+	type Reward = Reward;
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Twitter = Module<Test>;
+pub type Announcements = Module<Test, Instance1>;
+pub type Rewards = rewards::Module<Test>;
+
+pub struct ExtBuilder {
+	max_text_len: u32,
+	slash_censored_deposit: bool,
+	max_tweets_per_period: u32,
+	max_tweets_per_account: u32,
+	dedup_window: u64,
+	tweet_deposit_per_byte: u64,
+	min_reporter_age: u64,
+	recent_skips_comments: bool,
+	quotes_join_conversation: bool,
+	first_user_tweet_id: TweetId,
+	tweets: Vec<(u64, Vec<u8>)>,
+	system_tweets: Vec<(TweetId, u64, Vec<u8>)>,
+	follows: Vec<(u64, u64)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			max_text_len: 140,
+			slash_censored_deposit: true,
+			max_tweets_per_period: 100,
+			max_tweets_per_account: 100,
+			dedup_window: 0,
+			tweet_deposit_per_byte: 0,
+			min_reporter_age: 0,
+			recent_skips_comments: false,
+			quotes_join_conversation: false,
+			first_user_tweet_id: 0,
+			tweets: vec![],
+			system_tweets: vec![],
+			follows: vec![],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn max_text_len(mut self, max_text_len: u32) -> Self {
+		self.max_text_len = max_text_len;
+		self
+	}
+
+	pub fn slash_censored_deposit(mut self, slash_censored_deposit: bool) -> Self {
+		self.slash_censored_deposit = slash_censored_deposit;
+		self
+	}
+
+	pub fn max_tweets_per_period(mut self, max_tweets_per_period: u32) -> Self {
+		self.max_tweets_per_period = max_tweets_per_period;
+		self
+	}
+
+	pub fn max_tweets_per_account(mut self, max_tweets_per_account: u32) -> Self {
+		self.max_tweets_per_account = max_tweets_per_account;
+		self
+	}
+
+	pub fn dedup_window(mut self, dedup_window: u64) -> Self {
+		self.dedup_window = dedup_window;
+		self
+	}
+
+	pub fn tweet_deposit_per_byte(mut self, tweet_deposit_per_byte: u64) -> Self {
+		self.tweet_deposit_per_byte = tweet_deposit_per_byte;
+		self
+	}
+
+	pub fn min_reporter_age(mut self, min_reporter_age: u64) -> Self {
+		self.min_reporter_age = min_reporter_age;
+		self
+	}
+
+	pub fn recent_skips_comments(mut self, recent_skips_comments: bool) -> Self {
+		self.recent_skips_comments = recent_skips_comments;
+		self
+	}
+
+	pub fn quotes_join_conversation(mut self, quotes_join_conversation: bool) -> Self {
+		self.quotes_join_conversation = quotes_join_conversation;
+		self
+	}
+
+	pub fn first_user_tweet_id(mut self, first_user_tweet_id: TweetId) -> Self {
+		self.first_user_tweet_id = first_user_tweet_id;
+		self
+	}
+
+	pub fn tweets(mut self, tweets: Vec<(u64, Vec<u8>)>) -> Self {
+		self.tweets = tweets;
+		self
+	}
+
+	pub fn system_tweets(mut self, system_tweets: Vec<(TweetId, u64, Vec<u8>)>) -> Self {
+		self.system_tweets = system_tweets;
+		self
+	}
+
+	pub fn follows(mut self, follows: Vec<(u64, u64)>) -> Self {
+		self.follows = follows;
+		self
+	}
+
+	fn set_associated_consts(&self) {
+		MAX_TEXT_LEN.with(|v| *v.borrow_mut() = self.max_text_len);
+		SLASH_CENSORED_DEPOSIT.with(|v| *v.borrow_mut() = self.slash_censored_deposit);
+		MAX_TWEETS_PER_PERIOD.with(|v| *v.borrow_mut() = self.max_tweets_per_period);
+		MAX_TWEETS_PER_ACCOUNT.with(|v| *v.borrow_mut() = self.max_tweets_per_account);
+		DEDUP_WINDOW.with(|v| *v.borrow_mut() = self.dedup_window);
+		TWEET_DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = self.tweet_deposit_per_byte);
+		MIN_REPORTER_AGE.with(|v| *v.borrow_mut() = self.min_reporter_age);
+		RECENT_SKIPS_COMMENTS.with(|v| *v.borrow_mut() = self.recent_skips_comments);
+		QUOTES_JOIN_CONVERSATION.with(|v| *v.borrow_mut() = self.quotes_join_conversation);
+		FIRST_USER_TWEET_ID.with(|v| *v.borrow_mut() = self.first_user_tweet_id);
+		CREATED_TWEETS.with(|v| v.borrow_mut().clear());
+	}
+
+	// Build genesis storage according to the mock runtime.
+	pub fn build(self) -> sp_io::TestExternalities {
+		self.set_associated_consts();
+		let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(1, 100), (2, 100), (3, 100)],
+		}.assimilate_storage(&mut t).unwrap();
+		GenesisConfig::<Test> {
+			tweets: self.tweets,
+			system_tweets: self.system_tweets,
+			follows: self.follows,
+			text_limit: None,
+			phantom: Default::default(),
+		}.assimilate_storage(&mut t).unwrap();
+		GenesisConfig::<Test, Instance1>::default().assimilate_storage(&mut t).unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		// Events are not recorded at genesis.
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
 
-// Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	ExtBuilder::default().build()
+}
+
+pub fn last_event() -> TestEvent {
+	System::events().pop().expect("Event expected").event
 }
@@ -1,23 +1,4974 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Call, DefaultInstance, SponsoredTweet, Instance1, RawEvent, TweetKind, Reaction, Handle, Notification, Notifications, PollOption, Reason, ModerationAction, CommentCount, NextCommentIndex, CommentIndex, ExpiryCursor, NextTweetId, RetweetCount, Releases, StorageVersion, RecentContent, LikeCount, AccountLikes, AccountLikeCount, EditHistory, LikePreview, RetweetPreview, TopTweets, Tweets, TweetsByBlock, TweetMentions, FirstTweetAt, Reports, Settings, Followers, IndexedTweet, MediaCid, TextOf, Visibility, ReplyPolicy, TweetProvider, Community, CommunityName, AccountSettings, AccountExportOf, TweetId, mock::*};
+use crate::migrations::{TweetV1, TweetV2, TweetV3, TweetV4, TweetV5, TweetV6, TweetV7, TweetV8, TweetV9, TweetV10, TweetV11, TweetV12, TweetV13};
+use frame_support::{
+	assert_ok, assert_noop, Blake2_128Concat, Twox128, StorageHasher, StorageMap, StorageDoubleMap, IterableStorageDoubleMap, StorageValue, weights::GetDispatchInfo,
+	storage::{unhashed, migration::{get_storage_value, put_storage_value}}, traits::{Currency, OffchainWorker, OnFinalize, OnInitialize, OnRuntimeUpgrade}, weights::Pays,
+	unsigned::ValidateUnsigned,
+};
+use codec::{Encode, Decode};
+use sp_std::convert::TryFrom;
+use sp_core::offchain::{OffchainExt, OffchainStorage, testing::TestOffchainExt};
+use sp_runtime::{
+	traits::BadOrigin, testing::TestSignature,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
+
+type Error<T> = crate::Error<T, DefaultInstance>;
+
+#[test]
+fn new_tweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(1, 0, None, true)));
+
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.author, 1);
+		assert_eq!(tweet.text, b"hello".to_vec());
+		assert_eq!(Twitter::accounts(&1), vec![0]);
+		assert_eq!(Twitter::next_tweet_id(), 1);
+	});
+}
+
+#[test]
+fn retweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Retweeted(2, 1, 0, None)));
+
+		let retweet = Twitter::tweets(1).unwrap();
+		assert_eq!(retweet.author, 2);
+		assert_eq!(retweet.quote_tweet_id, Some(0));
+		assert_eq!(Twitter::accounts(&2), vec![1]);
+	});
+}
+
+#[test]
+fn comment_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Commented(2, 1, 0, None)));
+
+		assert_eq!(Twitter::comments_of(0), vec![1]);
+		let comment = Twitter::tweets(1).unwrap();
+		assert_eq!(comment.author, 2);
+		assert_eq!(comment.parent_id, Some(0));
+		assert_eq!(comment.quote_tweet_id, None);
+		assert_eq!(Twitter::accounts(&2), vec![1]);
+	});
+}
+
+#[test]
+fn retweet_and_comment_fail_for_missing_tweet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(
+			Twitter::retweet(Origin::signed(2), 42, b"quoted".to_vec(), vec![], None),
+			Error::<Test>::TweetNotFound
+		);
+		assert_noop!(
+			Twitter::comment(Origin::signed(2), b"reply".to_vec(), 42, None),
+			Error::<Test>::TweetNotFound
+		);
+		// Failed calls must not burn a tweet id.
+		assert_eq!(Twitter::next_tweet_id(), 1);
+	});
+}
+
+#[test]
+fn delete_tweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"second".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		let tombstone = Twitter::tweets(0).unwrap();
+		assert_eq!(tombstone.author, 1);
+		assert!(tombstone.text.is_empty());
+		assert!(Twitter::is_deleted(0));
+		assert!(!Twitter::is_deleted(1));
+		assert_eq!(Twitter::accounts(&1), vec![1]);
+		assert_eq!(Twitter::tweet_count(), 1);
+		assert_eq!(Twitter::tweet_count_of(&1), 1);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetDeleted(0, 1)));
+
+		assert_noop!(Twitter::delete_tweet(Origin::signed(1), 0), Error::<Test>::TweetDeleted);
+	});
+}
+
+#[test]
+fn tweet_accessors_expose_stored_fields() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(4);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], false, Visibility::Public, vec![b"bafy".to_vec()], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![], None));
+
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.id(), 0);
+		assert_eq!(tweet.author(), &1);
+		assert_eq!(tweet.text(), b"hello");
+		assert_eq!(tweet.created_at(), 4);
+		assert!(!tweet.comments_enabled());
+		assert_eq!(tweet.media().len(), 1);
+		assert_eq!(Twitter::tweets(1).unwrap().quoted(), Some(0));
+	});
+}
+
+#[test]
+fn delete_tweet_fails_for_missing_tweet_or_other_author() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::delete_tweet(Origin::signed(1), 42), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::delete_tweet(Origin::signed(2), 0), Error::<Test>::NotAuthor);
+	});
+}
+
+#[test]
+fn delete_tweet_leaves_a_tombstone_for_comments_and_retweets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::retweet(Origin::signed(3), 0, b"quoted".to_vec(), vec![], None));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		// The comment and the retweet survive, their references to the original resolve to the tombstone.
+		assert_eq!(Twitter::tweets(1).unwrap().author, 2);
+		let retweet = Twitter::tweets(2).unwrap();
+		assert_eq!(Twitter::tweets(retweet.quote_tweet_id.unwrap()).unwrap().author, 1);
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::comments_page_of(0, 0, 10)), vec![1]);
+
+		assert_noop!(
+			Twitter::comment(Origin::signed(2), b"again".to_vec(), 0, None),
+			Error::<Test>::TweetDeleted
+		);
+		assert_noop!(
+			Twitter::retweet(Origin::signed(2), 0, b"again".to_vec(), vec![], None),
+			Error::<Test>::TweetDeleted
+		);
+		assert_noop!(Twitter::repost(Origin::signed(2), 0), Error::<Test>::TweetDeleted);
+		assert_noop!(Twitter::like(Origin::signed(2), 0), Error::<Test>::TweetDeleted);
+		assert_noop!(Twitter::edit_tweet(Origin::signed(1), 0, b"back".to_vec()), Error::<Test>::TweetDeleted);
+		assert_noop!(Twitter::pin_tweet(Origin::signed(1), 0), Error::<Test>::TweetDeleted);
+	});
+}
+
+#[test]
+fn deleted_thread_segments_keep_the_thread_linked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()]));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+
+		let last = Twitter::tweets(2).unwrap();
+		let middle = Twitter::tweets(last.thread_prev.unwrap()).unwrap();
+		assert!(middle.text.is_empty());
+		assert_eq!(middle.thread_prev, Some(0));
+		assert_eq!(Twitter::accounts(&1), vec![0, 2]);
+	});
+}
+
+#[test]
+fn edit_tweet_works_within_window() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"helo".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		// Editing exactly at `create_at + EditWindow` is still allowed.
+		System::set_block_number(1 + EditWindow::get());
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"hello".to_vec()));
+
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"hello".to_vec());
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetEdited(0)));
+	});
+}
+
+#[test]
+fn edit_history_keeps_the_replaced_texts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"one".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(2);
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"two".to_vec()));
+		System::set_block_number(3);
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"three".to_vec()));
+
+		let history = || Twitter::edit_history(0).into_iter().map(|(at, text)| (at, text.into_inner())).collect::<Vec<_>>();
+		assert_eq!(Twitter::tweets(0).unwrap().revision(), 2);
+		assert_eq!(history(), vec![(2, b"one".to_vec()), (3, b"two".to_vec())]);
+		// Every kept text is paid for.
+		assert_eq!(Twitter::tweet_deposit(0), 3 * TweetDepositBase::get());
+		assert_eq!(Balances::reserved_balance(1), 3 * TweetDepositBase::get());
+
+		assert_noop!(
+			Twitter::edit_tweet(Origin::signed(1), 0, b"four".to_vec()),
+			Error::<Test>::TooManyEdits
+		);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(history(), vec![]);
+		assert!(!<EditHistory<Test>>::contains_key(0, 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn edit_tweet_needs_a_deposit_for_the_replaced_text() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"one".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		Balances::make_free_balance_be(&1, TweetDepositBase::get() - 1);
+
+		assert_noop!(
+			Twitter::edit_tweet(Origin::signed(1), 0, b"two".to_vec()),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn edit_tweet_fails_after_window() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"helo".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		System::set_block_number(2 + EditWindow::get());
+		assert_noop!(
+			Twitter::edit_tweet(Origin::signed(1), 0, b"hello".to_vec()),
+			Error::<Test>::EditWindowExpired
+		);
+	});
+}
+
+#[test]
+fn edit_tweet_checks_author_and_length() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"helo".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(
+			Twitter::edit_tweet(Origin::signed(2), 0, b"hello".to_vec()),
+			Error::<Test>::NotAuthor
+		);
+		assert_noop!(
+			Twitter::edit_tweet(Origin::signed(1), 42, b"hello".to_vec()),
+			Error::<Test>::TweetNotFound
+		);
+		assert_noop!(
+			Twitter::edit_tweet(Origin::signed(1), 0, vec![b'a'; 141]),
+			Error::<Test>::TweetTooLong
+		);
+	});
+}
+
+#[test]
+fn like_and_unlike_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Liked(2, 0)));
+		assert_ok!(Twitter::like(Origin::signed(3), 0));
+		assert!(Twitter::likes(0, 2));
+		assert_eq!(Twitter::like_count(0), 2);
+
+		assert_ok!(Twitter::unlike(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Unliked(2, 0)));
+		assert_ok!(Twitter::unlike(Origin::signed(3), 0));
+		assert!(!Twitter::likes(0, 2));
+		assert_eq!(Twitter::like_count(0), 0);
+	});
+}
+
+#[test]
+fn like_preview_keeps_the_first_likers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for who in 2..=11 {
+			assert_ok!(Twitter::like(Origin::signed(who), 0));
+		}
+		assert_eq!(Twitter::like_count(0), 10);
+		assert_eq!(Twitter::like_preview(0), (2..=9).collect::<Vec<u64>>());
+
+		// Likers outside of the preview leave it as is.
+		assert_ok!(Twitter::unlike(Origin::signed(10), 0));
+		assert_eq!(Twitter::like_preview(0), (2..=9).collect::<Vec<u64>>());
+		// Likers in the preview are not replaced with the remaining likers, only with new ones.
+		assert_ok!(Twitter::unlike(Origin::signed(3), 0));
+		assert_eq!(Twitter::like_preview(0), vec![2, 4, 5, 6, 7, 8, 9]);
+		assert_ok!(Twitter::like(Origin::signed(12), 0));
+		assert_eq!(Twitter::like_preview(0), vec![2, 4, 5, 6, 7, 8, 9, 12]);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert!(!<LikePreview<Test>>::contains_key(0));
+	});
+}
+
+#[test]
+fn retweet_preview_keeps_the_first_retweeters() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_eq!(Twitter::retweet_count(0), 3);
+		assert_eq!(Twitter::retweet_preview(0), vec![2, 3]);
+
+		// An account stays in the preview while it has a retweet or repost left.
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 3));
+		assert_eq!(Twitter::retweet_preview(0), vec![2, 3]);
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 1));
+		assert_eq!(Twitter::retweet_preview(0), vec![3]);
+		assert_ok!(Twitter::undo_retweet(Origin::signed(3), 2));
+		assert_eq!(Twitter::retweet_preview(0), Vec::<u64>::new());
+	});
+}
+
+#[test]
+fn like_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::like(Origin::signed(2), 42), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::unlike(Origin::signed(2), 0), Error::<Test>::NotLiked);
+
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_noop!(Twitter::like(Origin::signed(2), 0), Error::<Test>::AlreadyLiked);
+		assert_eq!(Twitter::like_count(0), 1);
+	});
+}
+
+#[test]
+fn delete_tweet_clears_likes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		assert!(!Twitter::likes(0, 2));
+		assert_eq!(Twitter::like_count(0), 0);
+	});
+}
+
+#[test]
+fn reactions_keep_counts_consistent() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::react(Origin::signed(2), 0, Reaction::Love));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Reacted(2, 0, Reaction::Love)));
+		assert_ok!(Twitter::react(Origin::signed(3), 0, Reaction::Love));
+		assert_eq!(Twitter::reaction_counts(0), [0, 2, 0, 0, 0]);
+
+		// Switching back and forth moves the reaction between counters.
+		assert_ok!(Twitter::react(Origin::signed(2), 0, Reaction::Sad));
+		assert_eq!(Twitter::reaction_counts(0), [0, 1, 0, 1, 0]);
+		assert_ok!(Twitter::react(Origin::signed(2), 0, Reaction::Love));
+		assert_eq!(Twitter::reaction_counts(0), [0, 2, 0, 0, 0]);
+		assert_ok!(Twitter::react(Origin::signed(2), 0, Reaction::Angry));
+		assert_eq!(Twitter::reactions(0, 2), Some(Reaction::Angry));
+		assert_eq!(Twitter::reaction_counts(0), [0, 1, 0, 0, 1]);
+		// Reactions do not count as likes.
+		assert_eq!(Twitter::like_count(0), 0);
+
+		assert_ok!(Twitter::remove_reaction(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ReactionRemoved(2, 0, Reaction::Angry)));
+		assert_eq!(Twitter::reactions(0, 2), None);
+		assert_eq!(Twitter::reaction_counts(0), [0, 1, 0, 0, 0]);
+		assert_ok!(Twitter::remove_reaction(Origin::signed(3), 0));
+		assert_eq!(Twitter::reaction_counts(0), [0; 5]);
+	});
+}
+
+#[test]
+fn reaction_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::react(Origin::signed(2), 42, Reaction::Like), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::remove_reaction(Origin::signed(2), 0), Error::<Test>::NoReaction);
+
+		assert_ok!(Twitter::react(Origin::signed(2), 0, Reaction::Laugh));
+		assert_noop!(Twitter::react(Origin::signed(2), 0, Reaction::Laugh), Error::<Test>::AlreadyReacted);
+		assert_eq!(Twitter::reaction_counts(0), [0, 0, 1, 0, 0]);
+	});
+}
+
+#[test]
+fn delete_tweet_clears_reactions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::react(Origin::signed(2), 0, Reaction::Like));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		assert_eq!(Twitter::reactions(0, 2), None);
+		assert_eq!(Twitter::reaction_counts(0), [0; 5]);
+		assert_noop!(Twitter::remove_reaction(Origin::signed(2), 0), Error::<Test>::NoReaction);
+	});
+}
+
+#[test]
+fn top_tweets_follow_likes() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..5 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+
+		assert_ok!(Twitter::like(Origin::signed(2), 2));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		// Equally liked tweets are ranked oldest first.
+		assert_eq!(Twitter::top_tweets(), vec![(0, 1), (2, 1)]);
+
+		assert_ok!(Twitter::like(Origin::signed(3), 2));
+		assert_ok!(Twitter::like(Origin::signed(2), 3));
+		assert_eq!(Twitter::top_tweets(), vec![(2, 2), (0, 1), (3, 1)]);
+
+		// The board is full, an older tweet evicts a newer one with as many likes.
+		assert_ok!(Twitter::like(Origin::signed(2), 1));
+		assert_eq!(Twitter::top_tweets(), vec![(2, 2), (0, 1), (1, 1)]);
+		assert_ok!(Twitter::like(Origin::signed(2), 4));
+		assert_eq!(Twitter::top_tweets(), vec![(2, 2), (0, 1), (1, 1)]);
+		assert_ok!(Twitter::like(Origin::signed(3), 4));
+		assert_eq!(Twitter::top_tweets(), vec![(2, 2), (4, 2), (0, 1)]);
+
+		assert_ok!(Twitter::unlike(Origin::signed(2), 2));
+		assert_eq!(Twitter::top_tweets(), vec![(4, 2), (0, 1), (2, 1)]);
+		assert_ok!(Twitter::unlike(Origin::signed(2), 0));
+		assert_eq!(Twitter::top_tweets(), vec![(4, 2), (2, 1)]);
+	});
+}
+
+#[test]
+fn top_tweets_drop_deleted_and_censored_tweets() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::like(Origin::signed(2), 1));
+		assert_ok!(Twitter::like(Origin::signed(3), 1));
+		assert_ok!(Twitter::like(Origin::signed(2), 2));
+		assert_eq!(Twitter::top_tweets(), vec![(1, 2), (0, 1), (2, 1)]);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+		assert_eq!(Twitter::top_tweets(), vec![(0, 1), (2, 1)]);
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 2));
+		assert_eq!(Twitter::top_tweets(), vec![(0, 1)]);
+		assert_ok!(Twitter::like(Origin::signed(3), 2));
+		assert_eq!(Twitter::top_tweets(), vec![(0, 1)]);
+	});
+}
+
+#[test]
+fn follow_and_unfollow_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::follow(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Followed(1, 2)));
+		assert_ok!(Twitter::follow(Origin::signed(3), 2));
+
+		assert_eq!(Twitter::following(1, 2), Some(()));
+		assert_eq!(Twitter::follower_count(2), 2);
+		assert_eq!(Twitter::following_count(1), 1);
+
+		assert_ok!(Twitter::unfollow(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Unfollowed(1, 2)));
+		assert_eq!(Twitter::following(1, 2), None);
+		assert_eq!(Twitter::follower_count(2), 1);
+		assert_eq!(Twitter::following_count(1), 0);
+	});
+}
+
+#[test]
+fn follow_errors() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::follow(Origin::signed(1), 1), Error::<Test>::CannotFollowSelf);
+		assert_noop!(Twitter::unfollow(Origin::signed(1), 2), Error::<Test>::NotFollowing);
+
+		assert_ok!(Twitter::follow(Origin::signed(1), 2));
+		assert_noop!(Twitter::follow(Origin::signed(1), 2), Error::<Test>::AlreadyFollowing);
+		// Following is directional.
+		assert_noop!(Twitter::unfollow(Origin::signed(2), 1), Error::<Test>::NotFollowing);
+	});
+}
+
+#[test]
+fn migrate_to_v2_sets_parent_of_comments() {
+	new_test_ext().execute_with(|| {
+		let old = |id, comments| TweetV1::<u64, u64> {
+			id,
+			create_at: 1,
+			quote_tweet_id: None,
+			text: b"old".to_vec(),
+			comments,
+			author: 1,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old(0, vec![1]));
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(1), &old(1, vec![]));
+		<StorageVersion>::put(Releases::V1);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::tweets(0).unwrap().parent_id, None);
+		assert_eq!(Twitter::comments_of(0), vec![1]);
+		let comment = Twitter::tweets(1).unwrap();
+		assert_eq!(comment.parent_id, Some(0));
+		assert_eq!(comment.text, b"old".to_vec());
+	});
+}
+
+#[test]
+fn repost_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Reposted(2, 1, 0)));
+
+		let repost = Twitter::tweets(1).unwrap();
+		assert_eq!(repost.quote_tweet_id, Some(0));
+		assert!(repost.text.is_empty());
+		assert_eq!(Twitter::accounts(&2), vec![1]);
+		assert_eq!(Twitter::reposts(0, 2), Some(1));
+		assert_eq!(Twitter::repost_count(0), 1);
+	});
+}
+
+#[test]
+fn repost_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::repost(Origin::signed(2), 42), Error::<Test>::TweetNotFound);
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_noop!(Twitter::repost(Origin::signed(2), 0), Error::<Test>::AlreadyReposted);
+		assert_eq!(Twitter::repost_count(0), 1);
+	});
+}
+
+#[test]
+fn repost_own_tweet_is_allowed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::repost(Origin::signed(1), 0));
+		assert_eq!(Twitter::accounts(&1), vec![0, 1]);
+		assert_eq!(Twitter::repost_count(0), 1);
+	});
+}
+
+#[test]
+fn deleting_repost_allows_reposting_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 1));
+		assert_eq!(Twitter::reposts(0, 2), None);
+		assert_eq!(Twitter::repost_count(0), 0);
+
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_eq!(Twitter::repost_count(0), 1);
+	});
+}
+
+#[test]
+fn undo_retweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::RetweetUndone(1, 0)));
+		assert_eq!(Twitter::tweets(1), None);
+		assert_eq!(Twitter::accounts(&2), vec![2]);
+
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 2));
+		assert_eq!(Twitter::accounts(&2), Vec::<u128>::new());
+		assert_eq!(Twitter::repost_count(0), 0);
+		assert!(Twitter::tweets(0).is_some());
+	});
+}
+
+#[test]
+fn undo_retweet_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+
+		assert_noop!(Twitter::undo_retweet(Origin::signed(2), 42), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::undo_retweet(Origin::signed(1), 1), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::undo_retweet(Origin::signed(1), 0), Error::<Test>::NotARetweet);
+	});
+}
+
+#[test]
+fn retweeting_twice_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+		assert_eq!(Twitter::retweeted(0, 2), Some(()));
+
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"again".to_vec(), vec![], None), Error::<Test>::AlreadyRetweeted);
+		// Other accounts, and reposts, are not affected.
+		assert_ok!(Twitter::retweet(Origin::signed(3), 0, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_eq!(Twitter::retweet_count(0), 3);
+
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 1));
+		assert_eq!(Twitter::retweeted(0, 2), None);
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"again".to_vec(), vec![], None));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 4));
+		assert_eq!(Twitter::retweeted(0, 2), None);
+	});
+}
+
+#[test]
+fn quote_chains_are_limited_by_max_quote_depth() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		// Each retweet quotes the previous one, the last one is `MaxQuoteDepth` quotes deep.
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, b"one".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(1), 1));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 2, b"three".to_vec(), vec![], None));
+		assert_eq!(Twitter::tweets(3).unwrap().quote_tweet_id, Some(2));
+
+		assert_noop!(Twitter::retweet(Origin::signed(2), 3, b"four".to_vec(), vec![], None), Error::<Test>::QuoteChainTooDeep);
+		assert_noop!(Twitter::repost(Origin::signed(2), 3), Error::<Test>::QuoteChainTooDeep);
+		// Quoting a tweet higher in the chain is still allowed.
+		assert_ok!(Twitter::retweet(Origin::signed(2), 2, b"three".to_vec(), vec![], None));
+	});
+}
+
+#[test]
+fn shorter_quote_chains_are_refunded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		let declared = Call::<Test>::retweet(0, b"hi".to_vec(), vec![], None).get_dispatch_info().weight;
+		let shallow = Twitter::retweet(Origin::signed(2), 0, b"hi".to_vec(), vec![], None).unwrap();
+		let deep = Twitter::retweet(Origin::signed(3), 1, b"hi".to_vec(), vec![], None).unwrap();
+		assert!(shallow.actual_weight.unwrap() < deep.actual_weight.unwrap());
+		assert!(deep.actual_weight.unwrap() < declared);
+	});
+}
+
+#[test]
+fn text_length_is_limited_by_max_text_len() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; 140], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 141], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+		assert_noop!(
+			Twitter::retweet(Origin::signed(1), 0, vec![b'a'; 141], vec![], None),
+			Error::<Test>::TweetTooLong
+		);
+		assert_noop!(
+			Twitter::comment(Origin::signed(1), vec![b'a'; 141], 0, None),
+			Error::<Test>::TweetTooLong
+		);
+	});
+}
+
+#[test]
+fn max_text_len_is_configurable() {
+	ExtBuilder::default().max_text_len(280).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; 280], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, vec![b'a'; 280], vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(1), vec![b'a'; 280], 0, None));
+
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 281], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+	});
+}
+
+#[test]
+fn text_limit_can_be_changed_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Twitter::text_limit(), 140);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; 140], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::set_text_limit(Origin::signed(1), 100), BadOrigin);
+		assert_noop!(Twitter::set_text_limit(Origin::root(), 0), Error::<Test>::InvalidTextLimit);
+		assert_noop!(Twitter::set_text_limit(Origin::root(), MaxTextBytes::get() + 1), Error::<Test>::InvalidTextLimit);
+		assert_ok!(Twitter::set_text_limit(Origin::root(), 100));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TextLimitChanged(100)));
+
+		// Lengths that were valid are rejected, stored tweets are kept.
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 101], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+		assert_noop!(Twitter::retweet(Origin::signed(1), 0, vec![b'a'; 101], vec![], None), Error::<Test>::TweetTooLong);
+		assert_noop!(Twitter::comment(Origin::signed(1), vec![b'a'; 101], 0, None), Error::<Test>::TweetTooLong);
+		assert_ok!(Twitter::comment(Origin::signed(1), vec![b'a'; 100], 0, None));
+		assert_eq!(Twitter::tweets(0).unwrap().text.len(), 140);
+	});
+}
+
+#[test]
+fn text_bound_is_enforced_by_the_type() {
+	new_test_ext().execute_with(|| {
+		assert!(TextOf::<Test>::try_from(vec![b'a'; 560]).is_ok());
+		assert!(TextOf::<Test>::try_from(vec![b'a'; 561]).is_err());
+
+		// A bounded text is encoded like a plain `Vec`, but over-long values are not decoded.
+		assert!(TextOf::<Test>::decode(&mut &vec![b'a'; 560].encode()[..]).is_ok());
+		assert!(TextOf::<Test>::decode(&mut &vec![b'a'; 561].encode()[..]).is_err());
+	});
+}
+
+#[test]
+fn migrate_from_v2_bounds_text() {
+	new_test_ext().execute_with(|| {
+		let old = |id, text| TweetV2::<u64, u64> {
+			id,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			text,
+			comments: vec![],
+			author: 1,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old(0, b"short".to_vec()));
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(1), &old(1, vec![b'a'; 200]));
+		<StorageVersion>::put(Releases::V2);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"short".to_vec());
+		assert_eq!(Twitter::tweets(1).unwrap().text, vec![b'a'; 140]);
+	});
+}
+
+#[test]
+fn comments_are_indexed_in_order() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"second".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"nested".to_vec(), 1, None));
+
+		assert_eq!(Twitter::comment_count(0), 2);
+		assert_eq!(Twitter::comments(0, 1), Some(2));
+		assert_eq!(Twitter::comments_of(0), vec![1, 2]);
+		assert_eq!(Twitter::comments_of(1), vec![3]);
+	});
+}
+
+#[test]
+fn migrate_from_v3_moves_comments() {
+	new_test_ext().execute_with(|| {
+		let old = |id, parent_id, comments| TweetV3::<u64, u64> {
+			id,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id,
+			text: b"old".to_vec(),
+			comments,
+			author: 1,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old(0, None, vec![1, 2]));
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(1), &old(1, Some(0), vec![]));
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(2), &old(2, Some(0), vec![]));
+		<StorageVersion>::put(Releases::V3);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::comments_of(0), vec![1, 2]);
+		assert_eq!(Twitter::comment_count(0), 2);
+		assert_eq!(Twitter::tweets(2).unwrap().parent_id, Some(0));
+
+		// New comments are appended after the migrated ones.
+		<NextTweetId>::put(3);
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_eq!(Twitter::comments_of(0), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+fn migrate_from_v4_moves_account_tweets() {
+	new_test_ext().execute_with(|| {
+		let key = Blake2_128Concat::hash(&1u64.encode());
+		put_storage_value(b"TemplateModule", b"Accounts", &key, vec![2u128, 0]);
+		for _ in 0..3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(2), b"old".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		<StorageVersion>::put(Releases::V4);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(get_storage_value::<Vec<u128>>(b"TemplateModule", b"Accounts", &key), None);
+		assert_eq!(Twitter::account_tweet_count(1), 2);
+		assert_eq!(Twitter::account_tweets(1, 0), Some(2));
+		assert_eq!(Twitter::accounts(&1), vec![2, 0]);
+
+		// New tweets are appended after the migrated ones.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"new".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::accounts(&1), vec![2, 0, 3]);
+	});
+}
+
+#[test]
+fn migrate_storage_prefix_moves_every_item() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV4::<u64, u64> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			text: b"old".to_vec(),
+			author: 1,
+		};
+		let account = Blake2_128Concat::hash(&1u64.encode());
+		let tweet = Blake2_128Concat::hash(&0u128.encode());
+		put_storage_value(b"TemplateModule", b"Accounts", &account, vec![0u128]);
+		put_storage_value(b"TemplateModule", b"Tweets", &tweet, old);
+		put_storage_value(b"TemplateModule", b"NextTweetId", &[], 1u128);
+		put_storage_value(b"TemplateModule", b"StorageVersion", &[], Releases::V4);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::next_tweet_id(), 1);
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"old".to_vec());
+		assert_eq!(Twitter::accounts(&1), vec![0]);
+		assert_eq!(get_storage_value::<u128>(b"TemplateModule", b"NextTweetId", &[]), None);
+		let old_prefix = Twox128::hash(b"TemplateModule");
+		assert!(sp_io::storage::next_key(&old_prefix).map_or(true, |key| !key.starts_with(&old_prefix)));
+
+		// Later upgrades find nothing left to move.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"new".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		Twitter::on_runtime_upgrade();
+		assert_eq!(Twitter::accounts(&1), vec![0, 1]);
+	});
+}
+
+#[test]
+fn tweets_reserve_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+
+		assert_eq!(Balances::reserved_balance(1), 20);
+		assert_eq!(Balances::free_balance(1), 80);
+		assert_eq!(Balances::reserved_balance(2), 20);
+		assert_eq!(Twitter::tweet_deposit(1), 10);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 2));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(Balances::free_balance(2), 90);
+		assert_eq!(Twitter::tweet_deposit(1), 0);
+	});
+}
+
+#[test]
+fn tweeting_requires_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		let _ = Balances::deposit_creating(&4, 5);
+
+		assert_noop!(Twitter::new_tweet(Origin::signed(4), b"hi".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::InsufficientBalance);
+		assert_noop!(Twitter::retweet(Origin::signed(4), 0, b"hi".to_vec(), vec![], None), Error::<Test>::InsufficientBalance);
+		assert_noop!(Twitter::repost(Origin::signed(4), 0), Error::<Test>::InsufficientBalance);
+		assert_noop!(Twitter::comment(Origin::signed(4), b"hi".to_vec(), 0, None), Error::<Test>::InsufficientBalance);
+		assert_eq!(Twitter::next_tweet_id(), 1);
+	});
+}
+
+#[test]
+fn unused_hashtag_weight_is_refunded() {
+	new_test_ext().execute_with(|| {
+		let long = [&b"#one #two #three "[..], &[b'a'; 123][..]].concat();
+		let declared = Call::<Test>::new_tweet(long.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone).get_dispatch_info().weight;
+
+		let short = Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone).unwrap();
+		let untagged = Twitter::new_tweet(Origin::signed(1), vec![b'a'; 140], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone).unwrap();
+		let tagged = Twitter::new_tweet(Origin::signed(1), long, vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone).unwrap();
+		assert!(short.actual_weight.unwrap() < untagged.actual_weight.unwrap());
+		assert!(untagged.actual_weight.unwrap() < tagged.actual_weight.unwrap());
+		assert_eq!(tagged.actual_weight, Some(declared));
+
+		let declared = Call::<Test>::retweet(0, b"hi".to_vec(), vec![], None).get_dispatch_info().weight;
+		let retweet = Twitter::retweet(Origin::signed(2), 0, b"hi".to_vec(), vec![], None).unwrap();
+		assert!(retweet.actual_weight.unwrap() < declared);
+
+		let declared = Call::<Test>::comment(b"hi".to_vec(), 0, None).get_dispatch_info().weight;
+		let comment = Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, None).unwrap();
+		assert!(comment.actual_weight.unwrap() < declared);
+		let tagged = Twitter::comment(Origin::signed(2), b"#hi #there #you".to_vec(), 0, None).unwrap();
+		assert!(comment.actual_weight.unwrap() < tagged.actual_weight.unwrap());
+	});
+}
+
+#[test]
+fn tweet_deposits_scale_with_text_and_media() {
+	ExtBuilder::default().tweet_deposit_per_byte(1).build().execute_with(|| {
+		// Two bytes of text, their length prefix and the empty media list.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweet_deposit(0), 14);
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; 50], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweet_deposit(1), 62);
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hi".to_vec(), vec![], true, Visibility::Public, vec![b"bafy".to_vec()], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweet_deposit(2), 19);
+
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_eq!(Twitter::tweet_deposit(3), 11);
+		assert_eq!(Balances::reserved_balance(1), 76);
+		assert_eq!(Balances::reserved_balance(2), 30);
+
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 20], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn deleting_a_tweet_returns_the_deposit_it_reserved() {
+	ExtBuilder::default().tweet_deposit_per_byte(1).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Balances::reserved_balance(1), 17);
+
+		TWEET_DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = 5);
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn hashtags_are_parsed_from_text() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Twitter::hashtags(b"#Rust and #rust, #sub_strate!"), vec![b"rust".to_vec(), b"sub_strate".to_vec()]);
+		assert_eq!(Twitter::hashtags(b"no#tag # #, ##x"), vec![b"x".to_vec()]);
+		assert!(Twitter::hashtags(b"plain text").is_empty());
+	});
+}
+
+#[test]
+fn hashtag_index_follows_tweets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello #Polkadot".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"#polkadot #kusama".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"#kusama too".to_vec(), 0, None));
+
+		assert_eq!(Twitter::hashtag_tweets(b"polkadot"), vec![0, 1]);
+		assert_eq!(Twitter::hashtag_tweets(b"KUSAMA"), vec![1, 2]);
+
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"hello #kusama".to_vec()));
+		assert_eq!(Twitter::hashtag_tweets(b"polkadot"), vec![1]);
+		assert_eq!(Twitter::hashtag_tweets(b"kusama"), vec![0, 1, 2]);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 1));
+		assert_eq!(Twitter::hashtag_tweets(b"polkadot"), Vec::<u128>::new());
+		assert_eq!(Twitter::hashtag_tweets(b"kusama"), vec![0, 2]);
+	});
+}
+
+#[test]
+fn hashtag_usage_is_counted_per_window() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#dot #ksm".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"#DOT".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"#ksm #dot #eth".to_vec(), 0, None));
+		// Edits do not count as new uses.
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"#eth".to_vec()));
+		assert_eq!(Twitter::trending(0, 2), vec![(b"dot".to_vec(), 3), (b"ksm".to_vec(), 2)]);
+
+		System::set_block_number(12);
+		assert_eq!(Twitter::window_index(12), 1);
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"#sub #eth".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_eq!(Twitter::trending(1, 5), vec![(b"eth".to_vec(), 1), (b"sub".to_vec(), 1)]);
+		assert_eq!(Twitter::trending(0, 5), vec![(b"dot".to_vec(), 3), (b"ksm".to_vec(), 2), (b"eth".to_vec(), 1)]);
+	});
+}
+
+#[test]
+fn old_hashtag_usage_is_pruned() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#a #b #c".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#d".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(12);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#a".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		// The previous window is kept.
+		Twitter::on_initialize(19);
+		assert_eq!(Twitter::trending(0, 10).len(), 4);
+
+		// At most `MaxHashtagPrunesPerBlock` counts are removed in a block.
+		Twitter::on_initialize(20);
+		assert_eq!(Twitter::trending(0, 10).len(), 1);
+		Twitter::on_initialize(21);
+		assert_eq!(Twitter::trending(0, 10), vec![]);
+		assert_eq!(Twitter::trending(1, 10), vec![(b"a".to_vec(), 1)]);
+	});
+}
+
+#[test]
+fn too_many_hashtags_fail() {
+	new_test_ext().execute_with(|| {
+		// Repeated tags count once.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#a #b #c #a".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		let text = b"#a #b #c #d".to_vec();
+		assert_noop!(Twitter::new_tweet(Origin::signed(1), text.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::TooManyHashtags);
+		assert_noop!(Twitter::retweet(Origin::signed(1), 0, text.clone(), vec![], None), Error::<Test>::TooManyHashtags);
+		assert_noop!(Twitter::comment(Origin::signed(1), text.clone(), 0, None), Error::<Test>::TooManyHashtags);
+		assert_noop!(Twitter::edit_tweet(Origin::signed(1), 0, text), Error::<Test>::TooManyHashtags);
+	});
+}
+
+#[test]
+fn mentions_notify_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi @2".to_vec(), vec![2, 1], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Mentioned(2, 0)));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi again".to_vec(), vec![2, 2], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_eq!(Twitter::notifications(2), vec![Notification::Mentioned(1, 0), Notification::Mentioned(1, 1)]);
+		// Mentioning yourself is ignored.
+		assert!(Twitter::notifications(1).is_empty());
+
+		assert_ok!(Twitter::clear_notifications(Origin::signed(2)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::NotificationsCleared(2)));
+		assert!(Twitter::notifications(2).is_empty());
+	});
+}
+
+#[test]
+fn notifications_keep_the_newest() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..4 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![2], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_eq!(
+			Twitter::notifications(2),
+			vec![Notification::Mentioned(1, 1), Notification::Mentioned(1, 2), Notification::Mentioned(1, 3)],
+		);
+		assert_eq!(Twitter::notification_count(2), 4);
+		assert_eq!(Twitter::unread_count(&2), 3);
+	});
+}
+
+#[test]
+fn too_many_mentions_fail() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![2, 3, 4], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TooManyMentions
+		);
+	});
+}
+
+#[test]
+fn set_and_clear_profile_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::set_profile(Origin::signed(1), b"Alice".to_vec(), b"hi".to_vec(), None));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ProfileSet(1)));
+		assert_eq!(Balances::reserved_balance(1), 25);
+
+		// Updating keeps the deposit reserved once.
+		assert_ok!(Twitter::set_profile(Origin::signed(1), b"Alice".to_vec(), b"bio".to_vec(), Some(b"Qm".to_vec())));
+		assert_eq!(Balances::reserved_balance(1), 25);
+		let profile = Twitter::profiles(1).unwrap();
+		assert_eq!(profile.bio, b"bio".to_vec());
+		assert_eq!(profile.avatar_cid.unwrap(), b"Qm".to_vec());
+
+		assert_ok!(Twitter::clear_profile(Origin::signed(1)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ProfileCleared(1)));
+		assert_eq!(Twitter::profiles(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(Twitter::clear_profile(Origin::signed(1)), Error::<Test>::NoProfile);
+	});
+}
+
+#[test]
+fn identities_come_from_the_provider() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Twitter::display_name_of(&1), Some(b"alice".to_vec()));
+		assert_eq!(Twitter::display_name_of(&2), Some(b"bob".to_vec()));
+		assert_eq!(Twitter::display_name_of(&3), None);
+		// An instance without provider knows no identity.
+		assert_eq!(Announcements::display_name_of(&1), None);
+
+		// Only judged identities are flagged.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(1, 0, None, true)));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(2, 1, None, false)));
+	});
+}
+
+#[test]
+fn set_profile_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::set_profile(Origin::signed(1), vec![b'n'; 64], vec![b'b'; 256], Some(vec![b'c'; 64])));
+		assert_noop!(
+			Twitter::set_profile(Origin::signed(1), vec![b'n'; 65], vec![], None),
+			Error::<Test>::DisplayNameTooLong
+		);
+		assert_noop!(
+			Twitter::set_profile(Origin::signed(1), vec![], vec![b'b'; 257], None),
+			Error::<Test>::BioTooLong
+		);
+		assert_noop!(
+			Twitter::set_profile(Origin::signed(1), vec![], vec![], Some(vec![b'c'; 65])),
+			Error::<Test>::AvatarCidTooLong
+		);
+		assert_noop!(Twitter::set_profile(Origin::signed(4), vec![], vec![], None), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn claim_and_release_handle_work() {
+	new_test_ext().execute_with(|| {
+		let alice = Handle::try_from(b"alice".to_vec()).unwrap();
+		assert_ok!(Twitter::claim_handle(Origin::signed(1), b"alice".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::HandleClaimed(1, alice.clone())));
+		assert_eq!(Twitter::handles(&alice), Some(1));
+		assert_eq!(Twitter::handle_of(1), Some(alice.clone()));
+		assert_eq!(Balances::reserved_balance(1), 30);
+
+		assert_noop!(Twitter::claim_handle(Origin::signed(2), b"alice".to_vec()), Error::<Test>::HandleTaken);
+		assert_noop!(Twitter::claim_handle(Origin::signed(1), b"bob".to_vec()), Error::<Test>::AlreadyHasHandle);
+
+		assert_ok!(Twitter::release_handle(Origin::signed(1)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::HandleReleased(1, alice.clone())));
+		assert_eq!(Twitter::handles(&alice), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(Twitter::release_handle(Origin::signed(1)), Error::<Test>::NoHandle);
+
+		// A released handle can be claimed again.
+		assert_ok!(Twitter::claim_handle(Origin::signed(2), b"alice".to_vec()));
+		assert_eq!(Twitter::handles(&alice), Some(2));
+	});
+}
+
+#[test]
+fn claim_handle_errors() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::claim_handle(Origin::signed(1), b"al".to_vec()), Error::<Test>::InvalidHandle);
+		assert_noop!(Twitter::claim_handle(Origin::signed(1), b"Alice".to_vec()), Error::<Test>::InvalidHandle);
+		assert_noop!(Twitter::claim_handle(Origin::signed(1), b"al-ice".to_vec()), Error::<Test>::InvalidHandle);
+		assert_noop!(Twitter::claim_handle(Origin::signed(1), vec![b'a'; 21]), Error::<Test>::HandleTooLong);
+		assert_noop!(Twitter::claim_handle(Origin::signed(4), b"dave".to_vec()), Error::<Test>::InsufficientBalance);
+		assert_ok!(Twitter::claim_handle(Origin::signed(1), b"al_1ce".to_vec()));
+	});
+}
+
+#[test]
+fn subscribe_and_cancel_premium_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert!(!Twitter::is_premium(&1, 1));
+		assert_ok!(Twitter::subscribe_premium(Origin::signed(1)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PremiumSubscribed(1, 11)));
+		assert_eq!(Balances::reserved_balance(1), 40);
+		assert!(Twitter::is_premium(&1, 10));
+		assert!(!Twitter::is_premium(&1, 11));
+
+		// Renewing restarts the period without reserving the fee again.
+		System::set_block_number(5);
+		assert_ok!(Twitter::subscribe_premium(Origin::signed(1)));
+		assert_eq!(Twitter::premium(1), Some(15));
+		assert_eq!(Balances::reserved_balance(1), 40);
+
+		assert_ok!(Twitter::cancel_premium(Origin::signed(1)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PremiumCancelled(1, 40)));
+		assert_eq!(Twitter::premium(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_noop!(Twitter::cancel_premium(Origin::signed(1)), Error::<Test>::NotPremium);
+		assert_noop!(Twitter::subscribe_premium(Origin::signed(4)), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn premium_accounts_can_post_longer_tweets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let long = vec![b'a'; 300];
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::subscribe_premium(Origin::signed(1)));
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), long.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(1).unwrap().text.len(), 300);
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, long.clone(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(1), long.clone(), 0, None));
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 401], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+
+		// Everyone else keeps the normal limit.
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(2), long.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+		assert_noop!(Twitter::comment(Origin::signed(2), long.clone(), 0, None), Error::<Test>::TweetTooLong);
+
+		// So do accounts whose subscription expired.
+		System::set_block_number(11);
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), long, vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+	});
+}
+
+#[test]
+fn blocked_accounts_cannot_comment_or_notify() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::block_account(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::AccountBlocked(1, 2)));
+
+		assert_noop!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None), Error::<Test>::BlockedByAuthor);
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hey".to_vec(), vec![1, 3], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::notifications(1), vec![Notification::CommentedOn(3, 1)]);
+		assert_eq!(Twitter::notifications(3), vec![Notification::Mentioned(2, 2)]);
+
+		assert_ok!(Twitter::unblock_account(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::AccountUnblocked(1, 2)));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+	});
+}
+
+#[test]
+fn block_account_errors() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::block_account(Origin::signed(1), 1), Error::<Test>::CannotBlockSelf);
+		assert_noop!(Twitter::unblock_account(Origin::signed(1), 2), Error::<Test>::NotBlocked);
+
+		assert_ok!(Twitter::block_account(Origin::signed(1), 2));
+		assert_noop!(Twitter::block_account(Origin::signed(1), 2), Error::<Test>::AlreadyBlocked);
+		assert_ok!(Twitter::block_account(Origin::signed(1), 3));
+		assert_noop!(Twitter::block_account(Origin::signed(1), 4), Error::<Test>::TooManyBlocked);
+
+		assert_ok!(Twitter::unblock_account(Origin::signed(1), 3));
+		assert_eq!(Twitter::blocked_count(1), 1);
+		assert_ok!(Twitter::block_account(Origin::signed(1), 4));
+	});
+}
+
+#[test]
+fn comments_can_be_disabled_by_the_author() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"quiet".to_vec(), vec![], false, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None), Error::<Test>::CommentsDisabled);
+
+		assert_noop!(Twitter::set_comments_enabled(Origin::signed(2), 0, true), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::set_comments_enabled(Origin::signed(1), 42, true), Error::<Test>::TweetNotFound);
+
+		assert_ok!(Twitter::set_comments_enabled(Origin::signed(1), 0, true));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommentsEnabledSet(0, true)));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+
+		assert_ok!(Twitter::set_comments_enabled(Origin::signed(1), 0, false));
+		assert_noop!(Twitter::comment(Origin::signed(1), b"own".to_vec(), 0, None), Error::<Test>::CommentsDisabled);
+		assert_eq!(Twitter::comments_of(0), vec![1]);
+	});
+}
+
+#[test]
+fn migrate_from_v5_enables_comments() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV4::<u64, u64> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			text: b"old".to_vec(),
+			author: 1,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V5);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert!(tweet.comments_enabled);
+		assert_eq!(tweet.text, b"old".to_vec());
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+	});
+}
+
+#[test]
+fn pin_and_unpin_tweet_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"second".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::pin_tweet(Origin::signed(1), 2), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::pin_tweet(Origin::signed(1), 42), Error::<Test>::TweetNotFound);
+
+		assert_ok!(Twitter::pin_tweet(Origin::signed(1), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetPinned(1, 0)));
+		assert_ok!(Twitter::pin_tweet(Origin::signed(1), 1));
+		assert_eq!(Twitter::pinned_tweet(1), Some(1));
+
+		assert_ok!(Twitter::unpin_tweet(Origin::signed(1)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetUnpinned(1)));
+		assert_eq!(Twitter::pinned_tweet(1), None);
+		assert_noop!(Twitter::unpin_tweet(Origin::signed(1)), Error::<Test>::NoPinnedTweet);
+	});
+}
+
+#[test]
+fn deleting_pinned_tweet_clears_pin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"second".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::pin_tweet(Origin::signed(1), 1));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::pinned_tweet(1), Some(1));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+		assert_eq!(Twitter::pinned_tweet(1), None);
+	});
+}
+
+#[test]
+fn bookmark_and_remove_bookmark_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"second".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::bookmark(Origin::signed(2), 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Bookmarked(2, 1)));
+		assert_ok!(Twitter::bookmark(Origin::signed(2), 0));
+		assert_eq!(Twitter::bookmarks_of(&2), vec![0, 1]);
+		assert!(Twitter::bookmarks_of(&1).is_empty());
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+		assert_ok!(Twitter::remove_bookmark(Origin::signed(2), 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::BookmarkRemoved(2, 1)));
+		assert_eq!(Twitter::bookmarks_of(&2), vec![0]);
+		assert_eq!(Twitter::bookmark_count(2), 1);
+	});
+}
+
+#[test]
+fn bookmark_errors() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+
+		assert_noop!(Twitter::bookmark(Origin::signed(2), 42), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::remove_bookmark(Origin::signed(2), 0), Error::<Test>::NotBookmarked);
+
+		assert_ok!(Twitter::bookmark(Origin::signed(2), 0));
+		assert_noop!(Twitter::bookmark(Origin::signed(2), 0), Error::<Test>::AlreadyBookmarked);
+		assert_ok!(Twitter::bookmark(Origin::signed(2), 1));
+		assert_noop!(Twitter::bookmark(Origin::signed(2), 2), Error::<Test>::TooManyBookmarks);
+
+		assert_ok!(Twitter::remove_bookmark(Origin::signed(2), 0));
+		assert_ok!(Twitter::bookmark(Origin::signed(2), 2));
+	});
+}
+
+#[test]
+fn retweet_and_comment_counts_follow_creates_and_deletes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+		assert_eq!(Twitter::retweet_count(0), 2);
+		assert_eq!(Twitter::comment_count(0), 1);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 2));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"second".to_vec(), 0, None));
+		assert_ok!(Twitter::undo_retweet(Origin::signed(3), 3));
+		assert_eq!(Twitter::retweet_count(0), 1);
+		assert_eq!(Twitter::comment_count(0), 1);
+		assert_eq!(Twitter::comments_of(0), vec![2, 4]);
+
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 1));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(3), 4));
+		assert_eq!(Twitter::retweet_count(0), 1);
+		assert_eq!(Twitter::comment_count(0), 0);
+
+		// The tombstone of a deleted original keeps counting what still refers to it.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::retweet_count(0), 1);
+		assert_ok!(Twitter::undo_retweet(Origin::signed(3), 5));
+		assert_eq!(Twitter::retweet_count(0), 0);
+	});
+}
+
+#[test]
+fn failed_retweet_and_comment_leave_counts_unchanged() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+
+		assert_noop!(Twitter::retweet(Origin::signed(4), 0, b"hi".to_vec(), vec![], None), Error::<Test>::InsufficientBalance);
+		assert_noop!(Twitter::repost(Origin::signed(4), 0), Error::<Test>::InsufficientBalance);
+		assert_noop!(Twitter::comment(Origin::signed(4), b"hi".to_vec(), 0, None), Error::<Test>::InsufficientBalance);
+		assert_eq!(Twitter::retweet_count(0), 0);
+		assert_eq!(Twitter::comment_count(0), 1);
+		assert_eq!(Twitter::next_comment_index(0), 1);
+	});
+}
+
+#[test]
+fn counts_saturate() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		<RetweetCount>::insert(0, u32::max_value());
+		<CommentCount>::insert(0, u32::max_value());
+
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_eq!(Twitter::retweet_count(0), u32::max_value());
+		assert_eq!(Twitter::comment_count(0), u32::max_value());
+	});
+}
+
+#[test]
+fn migrate_from_v6_counts_comments_and_retweets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"second".to_vec(), 0, None));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+		// Lay the tweets and counters out as a V6 chain would have, which removed deleted tweets.
+		<Tweets<Test>>::remove(1);
+		<NextCommentIndex>::remove(0);
+		<CommentCount>::insert(0, 2);
+		<RetweetCount>::remove(0);
+		<StorageVersion>::put(Releases::V6);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::next_comment_index(0), 2);
+		assert_eq!(Twitter::comment_count(0), 1);
+		assert_eq!(Twitter::retweet_count(0), 1);
+		assert_eq!(Twitter::comments_of(0), vec![1, 2]);
+	});
+}
+
+/// Check that no tweet was stored since `next_id` was the next tweet id, as after every failed
+/// call below.
+fn assert_no_new_tweet(next_id: u128) {
+	assert_eq!(Twitter::next_tweet_id(), next_id);
+	assert_eq!(Twitter::tweets(next_id), None);
+	assert_eq!(Twitter::accounts(&2), Vec::<u128>::new());
+	assert_eq!(Twitter::comment_count(0), 0);
+}
+
+#[test]
+fn failed_tweets_write_nothing() {
+	ExtBuilder::default().max_tweets_per_period(1).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&4, 5);
+		let tweet = |who, text: &[u8], mentions, media| {
+			Twitter::new_tweet(Origin::signed(who), text.to_vec(), mentions, true, Visibility::Public, media, None, ReplyPolicy::Everyone)
+		};
+
+		assert_noop!(tweet(2, &[b'a'; 141], vec![], vec![]), Error::<Test>::TweetTooLong);
+		assert_noop!(tweet(2, &[0xff], vec![], vec![]), Error::<Test>::InvalidUtf8);
+		assert_noop!(tweet(2, b"#a #b #c #d", vec![], vec![]), Error::<Test>::TooManyHashtags);
+		assert_noop!(tweet(2, b"hi", vec![1, 3, 4], vec![]), Error::<Test>::TooManyMentions);
+		assert_noop!(tweet(2, b"hi", vec![], vec![b"a".to_vec(); 3]), Error::<Test>::TooManyAttachments);
+		assert_noop!(tweet(2, b"hi", vec![], vec![vec![b'a'; 65]]), Error::<Test>::CidTooLong);
+		assert_noop!(tweet(4, b"hi", vec![], vec![]), Error::<Test>::InsufficientBalance);
+		assert_ok!(tweet(2, b"hi", vec![], vec![]));
+		assert_noop!(tweet(2, b"again", vec![], vec![]), Error::<Test>::RateLimited);
+		assert_eq!(Twitter::next_tweet_id(), 1);
+		assert_eq!(Twitter::accounts(&2), vec![0]);
+	});
+}
+
+#[test]
+fn failed_retweets_write_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"secret".to_vec(), vec![], true, Visibility::FollowersOnly, vec![], None, ReplyPolicy::Everyone));
+		let _ = Balances::deposit_creating(&4, 5);
+
+		assert_noop!(Twitter::retweet(Origin::signed(2), 42, b"look".to_vec(), vec![], None), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, vec![b'a'; 141], vec![], None), Error::<Test>::TweetTooLong);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![], None), Error::<Test>::NotVisible);
+		assert_ok!(Twitter::follow(Origin::signed(4), 1));
+		assert_noop!(Twitter::retweet(Origin::signed(4), 0, b"look".to_vec(), vec![], None), Error::<Test>::InsufficientBalance);
+		assert_no_new_tweet(1);
+	});
+}
+
+#[test]
+fn comments_are_bounded_per_tweet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for _ in 0..MaxCommentsPerTweet::get() {
+			assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		}
+		// Deleted comments keep their place.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 1));
+		let next_id = Twitter::next_tweet_id();
+
+		assert_noop!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None), Error::<Test>::TooManyComments);
+		assert_eq!(Twitter::next_tweet_id(), next_id);
+		assert_eq!(Twitter::next_comment_index(0), MaxCommentsPerTweet::get());
+		// Other tweets can still be commented on.
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 2, None));
+	});
+}
+
+#[test]
+fn failed_comments_write_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		let _ = Balances::deposit_creating(&4, 5);
+
+		assert_noop!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 42, None), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"#a #b #c #d".to_vec(), 0, None), Error::<Test>::TooManyHashtags);
+		assert_noop!(Twitter::comment(Origin::signed(4), b"reply".to_vec(), 0, None), Error::<Test>::InsufficientBalance);
+		assert_ok!(Twitter::block_account(Origin::signed(1), 2));
+		assert_noop!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None), Error::<Test>::BlockedByAuthor);
+		assert_ok!(Twitter::set_comments_enabled(Origin::signed(1), 0, false));
+		assert_noop!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None), Error::<Test>::CommentsDisabled);
+		assert_ok!(Twitter::set_comments_enabled(Origin::signed(1), 0, true));
+		<NextCommentIndex>::insert(0, MaxCommentsPerTweet::get());
+		assert_noop!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None), Error::<Test>::TooManyComments);
+		assert_no_new_tweet(1);
+	});
+}
+
+#[test]
+fn failed_transactional_calls_discard_their_writes() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Twitter::transactional(|| {
+				<NextTweetId>::put(5);
+				<CommentCount>::insert(0, 1);
+				Err::<(), _>(Error::<Test>::InsufficientBalance.into())
+			}),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn report_tweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Spam));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetReported(2, 0, Reason::Spam)));
+		assert_ok!(Twitter::report_tweet(Origin::signed(3), 0, Reason::Abuse));
+		assert_eq!(Twitter::reports(0, 2), Some(Reason::Spam));
+		assert_eq!(Twitter::report_count(0), 2);
+
+		assert_noop!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Other), Error::<Test>::AlreadyReported);
+		assert_noop!(Twitter::report_tweet(Origin::signed(2), 42, Reason::Spam), Error::<Test>::TweetNotFound);
+	});
+}
+
+#[test]
+fn resolve_report_requires_moderation_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Illegal));
+
+		assert_noop!(Twitter::resolve_report(Origin::signed(2), 0, ModerationAction::Remove), BadOrigin);
+		assert_noop!(Twitter::resolve_report(Origin::root(), 1, ModerationAction::Dismiss), Error::<Test>::NoReports);
+	});
+}
+
+#[test]
+fn dismissing_reports_keeps_the_tweet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Spam));
+
+		assert_ok!(Twitter::resolve_report(Origin::root(), 0, ModerationAction::Dismiss));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ReportsDismissed(0)));
+		assert!(Twitter::tweets(0).is_some());
+		assert_eq!(Twitter::reports(0, 2), None);
+		assert_eq!(Twitter::report_count(0), 0);
+
+		// The same account can report the tweet again.
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Spam));
+	});
+}
+
+#[test]
+fn removing_a_reported_tweet_deletes_it() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Abuse));
+		assert_ok!(Twitter::report_tweet(Origin::signed(3), 0, Reason::Abuse));
+
+		assert_ok!(Twitter::resolve_report(Origin::root(), 0, ModerationAction::Remove));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetRemovedByModeration(0)));
+		assert!(Twitter::tweets(0).unwrap().text.is_empty());
+		assert!(Twitter::is_deleted(0));
+		assert_eq!(Twitter::tweet_count_of(&1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Twitter::reports(0, 3), None);
+		assert_eq!(Twitter::report_count(0), 0);
+		assert_noop!(Twitter::resolve_report(Origin::root(), 0, ModerationAction::Remove), Error::<Test>::NoReports);
+	});
+}
+
+#[test]
+fn removing_a_reported_tweet_keeps_its_thread() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply to reply".to_vec(), 1, None));
+		assert_ok!(Twitter::retweet(Origin::signed(3), 0, b"look".to_vec(), vec![], None));
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Abuse));
+
+		assert_ok!(Twitter::resolve_report(Origin::root(), 0, ModerationAction::Remove));
+
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		let thread = Twitter::conversation(0, 0, 10);
+		assert_eq!(ids(thread.clone()), vec![0, 1, 2]);
+		assert!(thread[0].text.is_empty());
+		assert_eq!(thread[1].parent_id, Some(0));
+		assert_eq!(thread[2].parent_id, Some(1));
+		assert_eq!(Twitter::comments_of(0), vec![1]);
+		assert_eq!(Twitter::tweets(3).unwrap().quote_tweet_id, Some(0));
+	});
+}
+
+#[test]
+fn force_remove_tweet_requires_force_remove_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::force_remove_tweet(Origin::signed(1), 0), BadOrigin);
+		assert_noop!(Twitter::force_remove_tweet(Origin::signed(2), 0), BadOrigin);
+		assert_noop!(Twitter::force_remove_tweet(Origin::root(), 42), Error::<Test>::TweetNotFound);
+	});
+}
+
+#[test]
+fn global_announcement_is_cleared_with_its_tweet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"news".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::set_global_announcement(Origin::signed(1), Some(0)), BadOrigin);
+		assert_noop!(Twitter::set_global_announcement(Origin::root(), Some(42)), Error::<Test>::TweetNotFound);
+
+		assert_ok!(Twitter::set_global_announcement(Origin::root(), Some(0)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::AnnouncementSet(0)));
+		assert_eq!(Twitter::global_announcement(), Some(0));
+		assert_ok!(Twitter::set_global_announcement(Origin::root(), None));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::AnnouncementCleared));
+		assert_eq!(Twitter::global_announcement(), None);
+
+		// Deleting another tweet leaves the announcement alone.
+		assert_ok!(Twitter::set_global_announcement(Origin::root(), Some(1)));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::global_announcement(), Some(1));
+		assert_noop!(Twitter::set_global_announcement(Origin::root(), Some(0)), Error::<Test>::TweetDeleted);
+
+		System::reset_events();
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+		assert!(System::events().iter().any(|record| record.event == TestEvent::twitter(RawEvent::AnnouncementCleared)));
+		assert_eq!(Twitter::global_announcement(), None);
+	});
+}
+
+#[test]
+fn force_remove_tweet_leaves_a_tombstone() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"illegal #stuff".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::report_tweet(Origin::signed(3), 0, Reason::Illegal));
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetCensored(0)));
+
+		let tombstone = Twitter::tweets(0).unwrap();
+		assert_eq!(tombstone.id, 0);
+		assert_eq!(tombstone.author, 1);
+		assert!(tombstone.text.is_empty());
+		assert_eq!(Twitter::hashtag_tweets(b"stuff"), Vec::<u128>::new());
+		assert_eq!(Twitter::tweets(1).unwrap().parent_id, Some(0));
+		assert_eq!(Twitter::comments_of(0), vec![1]);
+		assert_eq!(Twitter::report_count(0), 0);
+
+		assert_noop!(Twitter::force_remove_tweet(Origin::root(), 0), Error::<Test>::Censored);
+		assert_noop!(Twitter::edit_tweet(Origin::signed(1), 0, b"back".to_vec()), Error::<Test>::Censored);
+		assert_noop!(Twitter::set_comments_enabled(Origin::signed(1), 0, true), Error::<Test>::Censored);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"again".to_vec(), 0, None), Error::<Test>::Censored);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![], None), Error::<Test>::Censored);
+		assert_noop!(Twitter::repost(Origin::signed(2), 0), Error::<Test>::Censored);
+		assert_noop!(Twitter::like(Origin::signed(2), 0), Error::<Test>::Censored);
+		assert_noop!(Twitter::react(Origin::signed(2), 0, Reaction::Angry), Error::<Test>::Censored);
+		assert_noop!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Spam), Error::<Test>::Censored);
+		assert_eq!(Twitter::like_count(0), 0);
+
+		// The author can still delete the tombstone.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert!(Twitter::is_deleted(0));
+	});
+}
+
+#[test]
+fn force_remove_tweet_drops_media_and_poll() {
+	new_test_ext().execute_with(|| {
+		let media = vec![b"cid".to_vec()];
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"look".to_vec(), vec![], true, Visibility::Public, media, None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b"]), 5));
+		assert_ok!(Twitter::vote(Origin::signed(2), 1, 0));
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 0));
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 1));
+
+		assert!(Twitter::tweets(0).unwrap().media.is_empty());
+		assert!(Twitter::polls(1).is_none());
+		assert!(Twitter::poll_results(1).is_none());
+		assert_eq!(Twitter::poll_votes(1, 2), None);
+	});
+}
+
+#[test]
+fn force_remove_tweet_drops_edit_history() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"illegal".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(2);
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"still illegal".to_vec()));
+		assert_eq!(Twitter::edit_history(0).len(), 1);
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 0));
+		assert_eq!(Twitter::tweets(0).unwrap().revision(), 0);
+		assert_eq!(Twitter::edit_history(0), vec![]);
+		assert!(!<EditHistory<Test>>::contains_key(0, 0));
+	});
+}
+
+#[test]
+fn force_remove_tweet_slashes_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::total_issuance(), issuance - 10);
+		assert_eq!(Twitter::tweet_deposit(0), 0);
+	});
+}
+
+#[test]
+fn force_remove_tweet_can_return_deposit() {
+	ExtBuilder::default().slash_censored_deposit(false).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+	});
+}
+
+#[test]
+fn followers_only_tweets_need_a_follower() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::FollowersOnly, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(0).unwrap().visibility, Visibility::FollowersOnly);
+		assert_ok!(Twitter::follow(Origin::signed(2), 1));
+
+		// Follower.
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+
+		// Non-follower.
+		assert_noop!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None), Error::<Test>::NotVisible);
+		assert_noop!(Twitter::like(Origin::signed(3), 0), Error::<Test>::NotVisible);
+		assert_noop!(Twitter::retweet(Origin::signed(3), 0, b"quote".to_vec(), vec![], None), Error::<Test>::NotVisible);
+		assert_noop!(Twitter::repost(Origin::signed(3), 0), Error::<Test>::NotVisible);
+
+		// Author.
+		assert_ok!(Twitter::comment(Origin::signed(1), b"own".to_vec(), 0, None));
+		assert_ok!(Twitter::like(Origin::signed(1), 0));
+
+		// Unfollowing takes the access away again.
+		assert_ok!(Twitter::unfollow(Origin::signed(2), 1));
+		assert_noop!(Twitter::comment(Origin::signed(2), b"again".to_vec(), 0, None), Error::<Test>::NotVisible);
+	});
+}
+
+#[test]
+fn public_tweets_need_no_follower() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::like(Origin::signed(3), 0));
+		assert_ok!(Twitter::retweet(Origin::signed(3), 0, b"quote".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+	});
+}
+
+#[test]
+fn migrate_from_v7_makes_tweets_public() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV5::<u64, u64> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: false,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V7);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.visibility, Visibility::Public);
+		assert!(!tweet.comments_enabled);
+		assert_eq!(tweet.text, b"old".to_vec());
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+	});
+}
+
+fn options(options: &[&[u8]]) -> Vec<Vec<u8>> {
+	options.iter().map(|option| option.to_vec()).collect()
+}
+
+#[test]
+fn new_poll_tweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_poll_tweet(Origin::signed(1), b"best?".to_vec(), options(&[b"tea", b"coffee"]), 5));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PollCreated(1, 0)));
+
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"best?".to_vec());
+		let poll = Twitter::polls(0).unwrap();
+		assert_eq!(poll.options, vec![PollOption::try_from(b"tea".to_vec()).unwrap(), PollOption::try_from(b"coffee".to_vec()).unwrap()]);
+		assert_eq!(poll.votes, vec![0, 0]);
+		assert_eq!(poll.close_at, 5);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn new_poll_tweet_errors() {
+	new_test_ext().execute_with(|| {
+		let too_long = vec![b'a'; 26];
+		assert_noop!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a"]), 5), Error::<Test>::InvalidPollOptions);
+		assert_noop!(
+			Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b", b"c", b"d", b"e"]), 5),
+			Error::<Test>::InvalidPollOptions
+		);
+		assert_noop!(Twitter::new_poll_tweet(Origin::signed(1), vec![], vec![b"a".to_vec(), too_long], 5), Error::<Test>::PollOptionTooLong);
+		assert_noop!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b"]), 0), Error::<Test>::PollClosed);
+		assert_noop!(Twitter::new_poll_tweet(Origin::signed(4), vec![], options(&[b"a", b"b"]), 5), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn vote_tallies_incrementally() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b", b"c"]), 5));
+
+		assert_ok!(Twitter::vote(Origin::signed(1), 0, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::VoteCast(1, 0, 2)));
+		assert_ok!(Twitter::vote(Origin::signed(2), 0, 0));
+		assert_ok!(Twitter::vote(Origin::signed(3), 0, 2));
+		assert_eq!(Twitter::polls(0).unwrap().votes, vec![1, 0, 2]);
+		assert_eq!(Twitter::poll_votes(0, 2), Some(0));
+	});
+}
+
+#[test]
+fn votes_can_change_until_the_poll_closes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b", b"c"]), 5));
+		assert_ok!(Twitter::vote(Origin::signed(1), 0, 0));
+		assert_ok!(Twitter::vote(Origin::signed(2), 0, 0));
+		assert_ok!(Twitter::vote(Origin::signed(2), 0, 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::VoteCast(2, 0, 1)));
+		assert_eq!(Twitter::polls(0).unwrap().votes, vec![1, 1, 0]);
+		assert_eq!(Twitter::poll_votes(0, 2), Some(1));
+		assert_noop!(Twitter::close_poll(Origin::signed(3), 0), Error::<Test>::PollOpen);
+
+		// Votes are rejected once `close_at` is past, closed or not.
+		System::set_block_number(6);
+		assert_noop!(Twitter::vote(Origin::signed(1), 0, 2), Error::<Test>::PollClosed);
+		assert_noop!(Twitter::vote(Origin::signed(3), 0, 2), Error::<Test>::PollClosed);
+
+		assert_ok!(Twitter::close_poll(Origin::signed(3), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PollResult(0, vec![1, 1, 0])));
+		assert_eq!(Twitter::poll_results(0), Some(vec![1, 1, 0]));
+		assert_noop!(Twitter::close_poll(Origin::signed(3), 0), Error::<Test>::PollAlreadyClosed);
+		assert_noop!(Twitter::close_poll(Origin::signed(3), 1), Error::<Test>::NoPoll);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::poll_results(0), None);
+	});
+}
+
+#[test]
+fn vote_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"no poll".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b"]), 5));
+
+		assert_noop!(Twitter::vote(Origin::signed(2), 0, 0), Error::<Test>::NoPoll);
+		assert_noop!(Twitter::vote(Origin::signed(2), 42, 0), Error::<Test>::NoPoll);
+		assert_noop!(Twitter::vote(Origin::signed(2), 1, 2), Error::<Test>::InvalidOption);
+		assert_ok!(Twitter::vote(Origin::signed(2), 1, 1));
+		assert_noop!(Twitter::vote(Origin::signed(2), 1, 1), Error::<Test>::AlreadyVoted);
+
+		// Votes are accepted up to and including `close_at`.
+		System::set_block_number(5);
+		assert_ok!(Twitter::vote(Origin::signed(3), 1, 0));
+		System::set_block_number(6);
+		assert_noop!(Twitter::vote(Origin::signed(1), 1, 0), Error::<Test>::PollClosed);
+	});
+}
+
+#[test]
+fn deleting_a_poll_tweet_removes_the_poll() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_poll_tweet(Origin::signed(1), vec![], options(&[b"a", b"b"]), 5));
+		assert_ok!(Twitter::vote(Origin::signed(2), 0, 1));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::polls(0), None);
+		assert_eq!(Twitter::poll_votes(0, 2), None);
+	});
+}
+
+#[test]
+fn minted_tweets_can_be_transferred() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"unsold".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::mint_tweet(Origin::signed(2), 0), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::transfer_tweet(Origin::signed(1), 0, 2), Error::<Test>::NotOwner);
+
+		assert_ok!(Twitter::mint_tweet(Origin::signed(1), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetMinted(1, 0)));
+		assert_eq!(Twitter::tweet_owner(0), Some(1));
+		assert_noop!(Twitter::mint_tweet(Origin::signed(1), 0), Error::<Test>::AlreadyMinted);
+
+		assert_noop!(Twitter::transfer_tweet(Origin::signed(2), 0, 3), Error::<Test>::NotOwner);
+		assert_noop!(Twitter::transfer_tweet(Origin::signed(1), 0, 1), Error::<Test>::NoChange);
+		assert_ok!(Twitter::transfer_tweet(Origin::signed(1), 0, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetTransferred(0, 1, 2)));
+		assert_eq!(Twitter::tweet_owner(0), Some(2));
+		assert_eq!(Twitter::tweets(0).unwrap().author, 1);
+
+		// Once sold the tweet is out of the reach of its author.
+		assert_noop!(Twitter::delete_tweet(Origin::signed(1), 0), Error::<Test>::CannotDeleteSold);
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 10));
+		assert!(!Twitter::is_deleted(0));
+		assert!(Twitter::is_deleted(1));
+
+		// It can be deleted again once its author owns it back.
+		assert_ok!(Twitter::transfer_tweet(Origin::signed(2), 0, 1));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::tweet_owner(0), None);
+	});
+}
+
+#[test]
+fn tip_moves_funds_to_the_author() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_ok!(Twitter::tip(Origin::signed(2), 0, 15));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tipped(2, 0, 15)));
+		assert_ok!(Twitter::tip(Origin::signed(3), 0, 5));
+		assert_ok!(Twitter::tip(Origin::signed(2), 0, 1));
+
+		assert_eq!(Balances::free_balance(1), 90 + 21);
+		assert_eq!(Balances::free_balance(2), 84);
+		assert_eq!(Balances::free_balance(3), 95);
+		assert_eq!(Twitter::tip_total(0), 21);
+	});
+}
+
+#[test]
+fn tip_errors() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(Twitter::tip(Origin::signed(1), 0, 5), Error::<Test>::CannotTipSelf);
+		assert_noop!(Twitter::tip(Origin::signed(2), 42, 5), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::tip(Origin::signed(2), 0, 0), Error::<Test>::ZeroTip);
+		assert_noop!(Twitter::tip(Origin::signed(2), 0, 200), pallet_balances::Error::<Test, _>::InsufficientBalance);
+		assert_eq!(Twitter::tip_total(0), 0);
+	});
+}
+
+#[test]
+fn set_verified_works() {
+	new_test_ext().execute_with(|| {
+		assert!(!Twitter::is_verified(&2));
+
+		assert_ok!(Twitter::set_verified(Origin::root(), 2, true));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::VerificationChanged(2, true)));
+		assert!(Twitter::is_verified(&2));
+		assert_noop!(Twitter::set_verified(Origin::root(), 2, true), Error::<Test>::NoChange);
+
+		assert_ok!(Twitter::set_verified(Origin::root(), 2, false));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::VerificationChanged(2, false)));
+		assert!(!Twitter::is_verified(&2));
+		assert_noop!(Twitter::set_verified(Origin::root(), 2, false), Error::<Test>::NoChange);
+	});
+}
+
+#[test]
+fn set_verified_requires_verify_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::set_verified(Origin::signed(2), 2, true), BadOrigin);
+		assert_noop!(Twitter::set_verified(Origin::signed(1), 2, true), BadOrigin);
+		assert!(!Twitter::is_verified(&2));
+	});
+}
+
+#[test]
+fn tweets_are_rate_limited() {
+	ExtBuilder::default().max_tweets_per_period(3).build().execute_with(|| {
+		// The first window runs from block 0 to block 4.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, b"quote".to_vec(), vec![], None));
+		System::set_block_number(4);
+		assert_ok!(Twitter::comment(Origin::signed(1), b"reply".to_vec(), 0, None));
+		assert_eq!(Twitter::tweet_rate(1), (0, 3));
+
+		assert_noop!(Twitter::new_tweet(Origin::signed(1), b"more".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::RateLimited);
+		assert_noop!(Twitter::retweet(Origin::signed(1), 2, b"more".to_vec(), vec![], None), Error::<Test>::RateLimited);
+		assert_noop!(Twitter::repost(Origin::signed(1), 0), Error::<Test>::RateLimited);
+		assert_noop!(Twitter::comment(Origin::signed(1), b"more".to_vec(), 0, None), Error::<Test>::RateLimited);
+		// Rate limited calls do not consume a tweet id.
+		assert_eq!(Twitter::next_tweet_id(), 3);
+
+		// Other accounts have their own limit.
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hi".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		// The window resets at block 5.
+		System::set_block_number(5);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"more".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweet_rate(1), (5, 1));
+		assert_eq!(Twitter::next_tweet_id(), 5);
+	});
+}
+
+#[test]
+fn failed_tweets_do_not_count_against_the_rate_limit() {
+	ExtBuilder::default().max_tweets_per_period(1).build().execute_with(|| {
+		assert_noop!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; 141], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::TweetTooLong);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::new_tweet(Origin::signed(1), b"again".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::RateLimited);
+	});
+}
+
+#[test]
+fn free_tweets_are_limited_per_day() {
+	new_test_ext().execute_with(|| {
+		let pays = |result: frame_support::dispatch::DispatchResultWithPostInfo| result.unwrap().pays_fee;
+		assert_noop!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; 141], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::TweetTooLong);
+		assert_eq!(pays(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone)), Pays::No);
+		assert_eq!(pays(Twitter::comment(Origin::signed(1), b"reply".to_vec(), 0, None)), Pays::No);
+		assert_eq!(Twitter::free_tweets_used(1), (0, 2));
+		// Past the allowance tweets pay the fee.
+		assert_eq!(pays(Twitter::retweet(Origin::signed(1), 0, b"quote".to_vec(), vec![], None)), Pays::Yes);
+		assert_eq!(Twitter::free_tweets_used(1), (0, 2));
+
+		// The allowance is renewed the next day.
+		System::set_block_number(DayLength::get());
+		assert_eq!(pays(Twitter::new_tweet(Origin::signed(1), b"tomorrow".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone)), Pays::No);
+		assert_eq!(Twitter::free_tweets_used(1), (1, 1));
+
+		// Accounts left with less than `MinBalanceForFreeTweets` once the deposit is reserved pay.
+		Balances::make_free_balance_be(&3, MinBalanceForFreeTweets::get());
+		assert_eq!(pays(Twitter::new_tweet(Origin::signed(3), b"poor".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone)), Pays::Yes);
+		assert_eq!(Twitter::free_tweets_used(3), (0, 0));
+	});
+}
+
+#[test]
+fn failing_calls_do_not_consume_tweet_ids() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], false, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		let long = vec![b'a'; 141];
+		let payload = SponsoredTweet { author: 1, text: b"hello".to_vec(), nonce: 0, valid_until: 5 };
+		let forged = TestSignature(2, Twitter::sponsored_signing_payload(&payload));
+
+		// Account 4 has nothing to reserve a tweet deposit from.
+		assert!(Twitter::new_tweet(Origin::signed(1), long.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone).is_err());
+		assert!(Twitter::new_tweet(Origin::signed(4), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone).is_err());
+		assert!(Twitter::submit_sponsored_tweet(Origin::none(), payload, forged).is_err());
+		assert!(Twitter::tweet_as(Origin::signed(2), 1, b"hello".to_vec()).is_err());
+		assert!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec(), long.clone()]).is_err());
+		assert!(Twitter::new_thread(Origin::signed(4), vec![b"one".to_vec(), b"two".to_vec()]).is_err());
+		assert!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"hello".to_vec(), 0).is_err());
+		assert!(Twitter::new_ephemeral_tweet(Origin::signed(4), b"hello".to_vec(), 5).is_err());
+		assert!(Twitter::retweet(Origin::signed(2), 42, b"quoted".to_vec(), vec![], None).is_err());
+		assert!(Twitter::retweet(Origin::signed(4), 0, b"quoted".to_vec(), vec![], None).is_err());
+		assert!(Twitter::repost(Origin::signed(2), 42).is_err());
+		assert!(Twitter::repost(Origin::signed(4), 0).is_err());
+		assert!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 42, None).is_err());
+		assert!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None).is_err());
+		assert!(Twitter::new_poll_tweet(Origin::signed(1), b"poll".to_vec(), vec![b"yes".to_vec()], 5).is_err());
+		assert!(Twitter::new_poll_tweet(Origin::signed(4), b"poll".to_vec(), vec![b"yes".to_vec(), b"no".to_vec()], 5).is_err());
+
+		assert_eq!(Twitter::next_tweet_id(), 1);
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"hello".to_vec(), 5));
+		assert!(Twitter::tweets(1).is_some());
+	});
+}
+
+#[test]
+fn sponsored_tweets_are_validated_unsigned() {
+	new_test_ext().execute_with(|| {
+		let sponsored = |author: u64, text: &[u8], nonce: u32, valid_until: u64| {
+			let payload = SponsoredTweet { author, text: text.to_vec(), nonce, valid_until };
+			let signature = TestSignature(author, Twitter::sponsored_signing_payload(&payload));
+			(payload, signature)
+		};
+		let validate = |(payload, signature): (SponsoredTweet<u64, u64>, TestSignature)| {
+			Twitter::validate_unsigned(TransactionSource::External, &Call::submit_sponsored_tweet(payload, signature))
+		};
+
+		let (payload, signature) = sponsored(1, b"hello", 0, 5);
+		assert_ok!(validate((payload.clone(), signature.clone())));
+		assert_eq!(validate((payload.clone(), TestSignature(2, Twitter::sponsored_signing_payload(&payload)))), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(validate(sponsored(1, b"hello", 1, 5)), Err(InvalidTransaction::Future.into()));
+		assert_eq!(validate(sponsored(1, &[b'a'; 141], 0, 5)), Err(InvalidTransaction::Custom(crate::INVALID_SPONSORED_TEXT).into()));
+		assert_eq!(validate(sponsored(1, b"", 0, 5)), Err(InvalidTransaction::Custom(crate::INVALID_SPONSORED_TEXT).into()));
+		assert_noop!(Twitter::submit_sponsored_tweet(Origin::signed(1), payload.clone(), signature.clone()), BadOrigin);
+
+		assert_ok!(Twitter::submit_sponsored_tweet(Origin::none(), payload.clone(), signature.clone()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(1, 0, None, true)));
+		assert_eq!(Twitter::tweets(0).unwrap().author, 1);
+		assert_eq!(Twitter::sponsored_nonce(1), 1);
+
+		// A payload is only accepted once.
+		assert_eq!(validate((payload.clone(), signature.clone())), Err(InvalidTransaction::Stale.into()));
+		assert_noop!(Twitter::submit_sponsored_tweet(Origin::none(), payload, signature), Error::<Test>::InvalidSponsoredTweet);
+
+		// Payloads are not accepted after `valid_until`.
+		System::set_block_number(6);
+		assert_eq!(validate(sponsored(1, b"late", 1, 5)), Err(InvalidTransaction::Stale.into()));
+
+		// At most `MaxSponsoredPerBlock` sponsored tweets are included in a block.
+		let (payload, signature) = sponsored(1, b"second", 1, 10);
+		assert_ok!(Twitter::submit_sponsored_tweet(Origin::none(), payload, signature));
+		assert_eq!(Twitter::sponsored_in_block(), MaxSponsoredPerBlock::get());
+		assert_eq!(validate(sponsored(2, b"third", 0, 10)), Err(InvalidTransaction::ExhaustsResources.into()));
+		Twitter::on_finalize(6);
+		System::set_block_number(7);
+		assert_ok!(validate(sponsored(2, b"third", 0, 10)));
+	});
+}
+
+#[test]
+fn sponsored_signatures_are_bound_to_the_instance() {
+	new_test_ext().execute_with(|| {
+		let payload = SponsoredTweet { author: 1, text: b"hello".to_vec(), nonce: 0, valid_until: 5 };
+		let signature = TestSignature(1, Twitter::sponsored_signing_payload(&payload));
+		assert_ne!(Twitter::sponsored_signing_payload(&payload), Announcements::sponsored_signing_payload(&payload));
+
+		// The same payload and nonce are fresh on the other instance, only the signature is not.
+		assert_eq!(
+			Announcements::validate_unsigned(TransactionSource::External, &Call::submit_sponsored_tweet(payload.clone(), signature.clone())),
+			Err(InvalidTransaction::BadProof.into()),
+		);
+		assert_noop!(
+			Announcements::submit_sponsored_tweet(Origin::none(), payload.clone(), signature.clone()),
+			Error::<Test, Instance1>::InvalidSponsoredTweet,
+		);
+
+		// Nor is a signature of the bare payload.
+		let bare = TestSignature(1, payload.encode());
+		assert_eq!(
+			Twitter::validate_unsigned(TransactionSource::External, &Call::submit_sponsored_tweet(payload.clone(), bare)),
+			Err(InvalidTransaction::BadProof.into()),
+		);
+		assert_ok!(Twitter::submit_sponsored_tweet(Origin::none(), payload, signature));
+	});
+}
+
+#[test]
+fn sponsored_tweets_need_no_balance() {
+	ExtBuilder::default().max_tweets_per_period(2).dedup_window(10).build().execute_with(|| {
+		let sponsored = |text: &[u8], nonce: u32| {
+			let payload = SponsoredTweet { author: 4, text: text.to_vec(), nonce, valid_until: 10 };
+			let signature = TestSignature(4, Twitter::sponsored_signing_payload(&payload));
+			(payload, signature)
+		};
+		let validate = |(payload, signature): (SponsoredTweet<u64, u64>, TestSignature)| {
+			Twitter::validate_unsigned(TransactionSource::External, &Call::submit_sponsored_tweet(payload, signature))
+		};
+		assert_eq!(Balances::total_balance(&4), 0);
+
+		let (payload, signature) = sponsored(b"hello", 0);
+		assert_ok!(validate((payload.clone(), signature.clone())));
+		assert_ok!(Twitter::submit_sponsored_tweet(Origin::none(), payload, signature));
+		assert_eq!(Twitter::tweets(0).unwrap().author, 4);
+		assert_eq!(Twitter::tweet_deposit(0), 0);
+
+		// Everything `new_tweet` checks is checked before the call gets into a block.
+		assert_eq!(validate(sponsored(b"hello", 1)), Err(InvalidTransaction::Custom(crate::INVALID_SPONSORED_TEXT).into()));
+		let (payload, signature) = sponsored(b"again", 1);
+		assert_ok!(Twitter::submit_sponsored_tweet(Origin::none(), payload, signature));
+		Twitter::on_finalize(1);
+		assert_eq!(validate(sponsored(b"more", 2)), Err(InvalidTransaction::Custom(crate::SPONSORED_LIMIT_REACHED).into()));
+		assert_eq!(Twitter::accounts(&4), vec![0, 1]);
+	});
+}
+
+#[test]
+fn tweets_per_account_are_capped() {
+	ExtBuilder::default().max_tweets_per_account(3).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, b"quote".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"reply".to_vec(), 0, None));
+		assert_eq!(Twitter::tweet_count_of(1), 3);
+
+		assert_noop!(Twitter::new_tweet(Origin::signed(1), b"more".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::retweet(Origin::signed(1), 2, b"more".to_vec(), vec![], None), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::repost(Origin::signed(1), 2), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::comment(Origin::signed(1), b"more".to_vec(), 0, None), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::new_thread(Origin::signed(1), vec![b"more".to_vec()]), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"more".to_vec(), 5), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::schedule_tweet(Origin::signed(1), b"more".to_vec(), 5), Error::<Test>::TooManyTweets);
+		assert_noop!(Twitter::new_poll_tweet(Origin::signed(1), b"more".to_vec(), vec![b"a".to_vec(), b"b".to_vec()], 5), Error::<Test>::TooManyTweets);
+		assert_eq!(Twitter::next_tweet_id(), 3);
+		assert_eq!(Twitter::accounts(&1), vec![0, 1, 2]);
+
+		// Other accounts have their own cap.
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hi".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		// Deleting a tweet makes room for a new one.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 2));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"more".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::undo_retweet(Origin::signed(1), 1));
+		assert_noop!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec(), b"two".to_vec()]), Error::<Test>::TooManyTweets);
+		assert_ok!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec()]));
+	});
+}
+
+#[test]
+fn scheduled_tweets_over_the_cap_are_dropped() {
+	ExtBuilder::default().max_tweets_per_account(2).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(1), b"later".to_vec(), 3));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"sooner".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Balances::reserved_balance(1), 30);
+
+		run_to_block(3);
+		assert_eq!(Twitter::accounts(&1), vec![0, 1]);
+		assert_eq!(Balances::reserved_balance(1), 20);
+	});
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Twitter::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn ephemeral_tweets_expire() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story #now".to_vec(), 3));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(1, 0, None, true)));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"forever".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_eq!(Twitter::tweet_expiry(0), Some(4));
+		assert_eq!(Twitter::accounts(&1), vec![0, 1]);
+
+		run_to_block(3);
+		assert!(Twitter::tweets(0).is_some());
+
+		run_to_block(4);
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetExpired(0)));
+		assert!(Twitter::tweets(0).is_none());
+		assert_eq!(Twitter::accounts(&1), vec![1]);
+		assert_eq!(Twitter::hashtag_tweets(b"now"), Vec::<u128>::new());
+		assert_eq!(Twitter::like_count(0), 0);
+		assert_eq!(Twitter::tweet_expiry(0), None);
+		assert_eq!(Balances::reserved_balance(1), 10);
+	});
+}
+
+#[test]
+fn expirations_beyond_the_limit_roll_over() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story".to_vec(), 1));
+		}
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(2), b"story".to_vec(), 2));
+
+		// Only `MaxExpirationsPerBlock` tweets are removed at block 2.
+		run_to_block(2);
+		assert_eq!(Twitter::accounts(&1).len(), 1);
+		assert!(Twitter::tweets(3).is_some());
+
+		// The leftover goes first at block 3, along with the tweet expiring then.
+		run_to_block(3);
+		assert_eq!(Twitter::accounts(&1), Vec::<u128>::new());
+		assert_eq!(Twitter::accounts(&2), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn expiry_scans_a_bounded_number_of_blocks() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(100);
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story".to_vec(), 1));
+		ExpiryCursor::<Test>::put(0);
+
+		// Only `MaxExpirationsPerBlock` blocks are scanned at a time.
+		run_to_block(101);
+		assert_eq!(ExpiryCursor::<Test>::get(), 2);
+		assert!(Twitter::tweets(0).is_some());
+	});
+}
+
+#[test]
+fn migrate_to_v27_starts_expiry_at_the_current_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(100);
+		ExpiryCursor::<Test>::put(0);
+		<StorageVersion>::put(Releases::V26);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(ExpiryCursor::<Test>::get(), 100);
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story".to_vec(), 1));
+		run_to_block(101);
+		assert!(Twitter::tweets(0).is_none());
+	});
+}
+
+#[test]
+fn deleted_ephemeral_tweets_are_not_expired_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story".to_vec(), 2));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		run_to_block(3);
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetDeleted(0, 1)));
+	});
+}
+
+#[test]
+fn new_ephemeral_tweet_errors() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story".to_vec(), 0), Error::<Test>::InvalidTtl);
+		assert_noop!(Twitter::new_ephemeral_tweet(Origin::signed(1), vec![b'a'; 141], 5), Error::<Test>::TweetTooLong);
+		assert_noop!(Twitter::new_ephemeral_tweet(Origin::signed(4), b"story".to_vec(), 5), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn scheduled_tweets_are_published() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(1), b"later #news".to_vec(), 3));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetScheduled(1, 3, 0)));
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(2), b"me too".to_vec(), 3));
+		// The deposit is reserved, but no tweet id is taken yet.
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Twitter::next_tweet_id(), 0);
+
+		run_to_block(2);
+		assert!(Twitter::tweets(0).is_none());
+
+		run_to_block(3);
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(2, 1, None, false)));
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.author, 1);
+		assert_eq!(tweet.text, b"later #news".to_vec());
+		assert_eq!(tweet.create_at, 3);
+		assert_eq!(Twitter::accounts(&1), vec![0]);
+		assert_eq!(Twitter::hashtag_tweets(b"news"), vec![0]);
+		assert_eq!(Twitter::scheduled_tweets(3, 0), None);
+
+		// The deposit now belongs to the tweet.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn schedule_tweet_errors() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::schedule_tweet(Origin::signed(1), b"now".to_vec(), 1), Error::<Test>::PublishInPast);
+		assert_noop!(Twitter::schedule_tweet(Origin::signed(1), vec![b'a'; 141], 5), Error::<Test>::TweetTooLong);
+		assert_noop!(Twitter::schedule_tweet(Origin::signed(4), b"later".to_vec(), 5), Error::<Test>::InsufficientBalance);
+
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(1), b"later".to_vec(), 5));
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(2), b"later".to_vec(), 5));
+		assert_noop!(Twitter::schedule_tweet(Origin::signed(3), b"later".to_vec(), 5), Error::<Test>::TooManyScheduled);
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(3), b"later".to_vec(), 6));
+	});
+}
+
+#[test]
+fn cancel_scheduled_tweet_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(1), b"later".to_vec(), 3));
+
+		assert_noop!(Twitter::cancel_scheduled_tweet(Origin::signed(2), 3, 0), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::cancel_scheduled_tweet(Origin::signed(1), 3, 1), Error::<Test>::NotScheduled);
+
+		assert_ok!(Twitter::cancel_scheduled_tweet(Origin::signed(1), 3, 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ScheduledTweetCancelled(1, 3, 0)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		run_to_block(3);
+		assert!(Twitter::tweets(0).is_none());
+		assert_eq!(Twitter::next_tweet_id(), 0);
+	});
+}
+
+#[test]
+fn tweets_page_of_paginates_existing_tweets() {
+	new_test_ext().execute_with(|| {
+		for text in &[b"a", b"b", b"c", b"d"] {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), text.to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 1));
+
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::tweets_page_of(&1, 0, 10)), vec![0, 2, 3]);
+		assert_eq!(ids(Twitter::tweets_page_of(&1, 0, 2)), vec![0, 2]);
+		assert_eq!(ids(Twitter::tweets_page_of(&1, 2, 2)), vec![3]);
+		assert_eq!(ids(Twitter::tweets_page_of(&1, 3, 2)), Vec::<u128>::new());
+		assert_eq!(ids(Twitter::tweets_page_of(&1, 0, 0)), Vec::<u128>::new());
+		assert_eq!(ids(Twitter::tweets_page_of(&3, 0, 10)), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn account_timeline_is_newest_first() {
+	new_test_ext().execute_with(|| {
+		for text in &[b"a", b"b", b"c", b"d"] {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), text.to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 2));
+
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::account_timeline(&1, 0, 10)), vec![3, 1, 0]);
+		assert_eq!(ids(Twitter::account_timeline(&1, 0, 2)), vec![3, 1]);
+		assert_eq!(ids(Twitter::account_timeline(&1, 2, 2)), vec![0]);
+		// Offsets past the end give an empty page.
+		assert_eq!(ids(Twitter::account_timeline(&1, 3, 2)), Vec::<u128>::new());
+		assert_eq!(ids(Twitter::account_timeline(&1, u32::max_value(), 2)), Vec::<u128>::new());
+		assert_eq!(ids(Twitter::account_timeline(&1, 0, 0)), Vec::<u128>::new());
+		assert_eq!(ids(Twitter::account_timeline(&3, 0, 10)), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn recent_tweets_keep_the_newest() {
+	new_test_ext().execute_with(|| {
+		for text in &[b"a", b"b", b"c", b"d", b"e"] {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), text.to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		// The buffer holds `RecentBufferSize` ids, oldest first.
+		assert_eq!(Twitter::recent_tweets(), vec![2, 3, 4]);
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 4, None));
+		assert_eq!(Twitter::recent_tweets(), vec![3, 4, 5]);
+
+		// Deleted tweets stay in the buffer but are not read back.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 4));
+		assert_eq!(Twitter::recent_tweets(), vec![3, 4, 5]);
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::recent(10)), vec![5, 3]);
+		assert_eq!(ids(Twitter::recent(1)), vec![5]);
+		assert_eq!(ids(Twitter::recent(0)), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn recent_tweets_can_skip_comments() {
+	ExtBuilder::default().recent_skips_comments(true).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"a".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+		assert_eq!(Twitter::recent_tweets(), vec![0, 2]);
+	});
+}
+
+#[test]
+fn tweets_by_ids_skips_missing_and_deleted_tweets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"a".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"b".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		// A deleted comment stays behind as a tombstone.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 2));
+		assert!(Twitter::tweets(2).is_some());
+
+		let ids = Twitter::tweets_by_ids(&[1, 42, 2, 0])
+			.into_iter()
+			.map(|tweet| tweet.map(|tweet| tweet.id))
+			.collect::<Vec<_>>();
+		assert_eq!(ids, vec![Some(1), None, None, Some(0)]);
+		assert!(Twitter::tweets_by_ids(&[]).is_empty());
+	});
+}
+
+#[test]
+fn comments_page_of_paginates_existing_comments() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for _ in 0..4 {
+			assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		}
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 2));
+
+		// Tombstones keep their place in the page.
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::comments_page_of(0, 0, 10)), vec![1, 2, 3, 4]);
+		assert_eq!(ids(Twitter::comments_page_of(0, 1, 1)), vec![2]);
+		assert_eq!(ids(Twitter::comments_page_of(42, 0, 10)), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn comments_page_skips_removed_comments() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for _ in 0..4 {
+			assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		}
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 4));
+		assert_ok!(Twitter::remove_comment(Origin::signed(1), 0, 2));
+		assert_eq!(Twitter::comments(0, 1), None);
+		assert_eq!(Twitter::comment_index(2), None);
+		assert_eq!(Twitter::comment_index(3), Some(2));
+
+		// The removed comment leaves a hole at index 1, the tombstone at index 3 stays.
+		assert_eq!(Twitter::comments_page(0, 0, 2), vec![1]);
+		assert_eq!(Twitter::comments_page(0, 2, 2), vec![3, 4]);
+		assert_eq!(Twitter::comments_page(0, 0, 10), vec![1, 3, 4]);
+		assert_eq!(Twitter::comments_page(0, 4, 10), Vec::<u128>::new());
+		assert_eq!(Twitter::comments_page(42, 0, 10), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn tweet_provider_serves_other_pallets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		let balance = Balances::total_balance(&1);
+
+		assert_eq!(Rewards::reward(0), Some(1));
+		assert_eq!(Balances::total_balance(&1), balance + Reward::get());
+		// Rewarded once per tweet, never for comments or missing tweets.
+		assert_eq!(Rewards::reward(0), None);
+		assert_eq!(Rewards::reward(1), None);
+		assert_eq!(Rewards::reward(42), None);
+		assert_eq!(Balances::total_balance(&1), balance + Reward::get());
+
+		let comment = <Twitter as TweetProvider<_, _>>::get(1).unwrap();
+		assert_eq!((comment.author, comment.text, comment.parent, comment.conversation_id), (2, b"reply".to_vec(), Some(0), 0));
+		assert!(!<Twitter as TweetProvider<u64, u64>>::exists(42));
+	});
+}
+
+#[test]
+fn conversation_lists_a_reply_tree() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"root".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(2);
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(3);
+		assert_ok!(Twitter::comment(Origin::signed(3), b"second".to_vec(), 1, None));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"third".to_vec(), 3, None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"sibling".to_vec(), 0, None));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 3, b"quoted".to_vec(), vec![], None));
+
+		for id in &[0, 1, 3, 4, 5] {
+			assert_eq!(Twitter::tweets(id).unwrap().conversation_id(), 0);
+		}
+		assert_eq!(Twitter::tweets(2).unwrap().conversation_id(), 2);
+		// Retweets start their own conversation unless `QuotesJoinConversation` is set.
+		assert_eq!(Twitter::tweets(6).unwrap().conversation_id(), 6);
+
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::conversation(0, 0, 10)), vec![0, 1, 3, 4, 5]);
+		assert_eq!(ids(Twitter::conversation(0, 1, 2)), vec![1, 3]);
+		assert_eq!(ids(Twitter::conversation(2, 0, 10)), vec![2]);
+		assert_eq!(ids(Twitter::conversation(42, 0, 10)), Vec::<u128>::new());
+
+		// Tombstones keep their place, removed tweets leave the conversation.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(3), 3));
+		assert_eq!(ids(Twitter::conversation(0, 0, 10)), vec![0, 1, 3, 4, 5]);
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 6));
+		assert_eq!(ids(Twitter::conversation(6, 0, 10)), Vec::<u128>::new());
+	});
+}
+
+#[test]
+fn quotes_and_threads_can_join_conversations() {
+	ExtBuilder::default().quotes_join_conversation(true).build().execute_with(|| {
+		assert_ok!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec(), b"two".to_vec()]));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 1, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 2, None));
+		assert_ok!(Twitter::repost(Origin::signed(3), 1));
+
+		let conversation_of = |id| Twitter::tweets(id).unwrap().conversation_id();
+		assert_eq!((0..4).map(conversation_of).collect::<Vec<_>>(), vec![0, 0, 0, 0]);
+		// Reposts never join the conversation of the tweet they share.
+		assert_eq!(conversation_of(4), 4);
+	});
+}
+
+#[test]
+fn pages_are_capped() {
+	ExtBuilder::default().max_tweets_per_period(1000).max_tweets_per_account(1000).build().execute_with(|| {
+		Balances::make_free_balance_be(&1, 10_000);
+		for _ in 0..(crate::MaxPageLen::get() + 1) {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"spam".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+
+		assert_eq!(Twitter::tweets_page_of(&1, 0, u32::max_value()).len(), crate::MaxPageLen::get() as usize);
+		assert_eq!(Twitter::account_timeline(&1, 0, u32::max_value()).len(), crate::MaxPageLen::get() as usize);
+		let ids = (0..u128::from(crate::MaxPageLen::get()) + 1).collect::<Vec<_>>();
+		assert_eq!(Twitter::tweets_by_ids(&ids).len(), crate::MaxPageLen::get() as usize);
+	});
+}
+
+#[test]
+fn tweet_counts_follow_tweets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_eq!(Twitter::tweet_count(), 3);
+		assert_eq!(Twitter::tweet_count_of(&1), 1);
+		assert_eq!(Twitter::tweet_count_of(&2), 2);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 2));
+		assert_eq!(Twitter::tweet_count(), 2);
+		assert_eq!(Twitter::tweet_count_of(&2), 1);
+
+		// Deleted tweets stay indexed by account, but are no longer counted.
+		assert_eq!(Twitter::account_tweet_count(&2), 2);
+	});
+}
+
+#[test]
+fn tweet_counts_include_ephemeral_and_scheduled_tweets() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_ephemeral_tweet(Origin::signed(1), b"story".to_vec(), 1));
+		assert_ok!(Twitter::schedule_tweet(Origin::signed(1), b"later".to_vec(), 3));
+		assert_eq!(Twitter::tweet_count_of(&1), 1);
+
+		run_to_block(3);
+		assert_eq!(Twitter::tweet_count(), 1);
+		assert_eq!(Twitter::tweet_count_of(&1), 1);
+		assert!(Twitter::tweets(1).is_some());
+	});
+}
+
+#[test]
+fn failed_tweets_leave_counts_unchanged() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 141], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong,
+		);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, vec![b'a'; 141], vec![], None), Error::<Test>::TweetTooLong);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 7, None), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::delete_tweet(Origin::signed(2), 0), Error::<Test>::NotAuthor);
+
+		assert_eq!(Twitter::tweet_count(), 1);
+		assert_eq!(Twitter::tweet_count_of(&1), 1);
+		assert_eq!(Twitter::tweet_count_of(&2), 0);
+	});
+}
+
+#[test]
+fn migrate_from_v8_counts_tweets() {
+	new_test_ext().execute_with(|| {
+		for (id, author) in [(0, 1), (1, 1), (2, 2)].iter() {
+			let old = TweetV6::<u64, u64> {
+				id: *id,
+				create_at: 1,
+				quote_tweet_id: None,
+				parent_id: None,
+				text: b"old".to_vec(),
+				author: *author,
+				comments_enabled: true,
+				visibility: Visibility::Public,
+			};
+			unhashed::put(&<Tweets<Test>>::hashed_key_for(id), &old);
+		}
+		<NextTweetId>::put(3);
+		<StorageVersion>::put(Releases::V8);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::tweet_count(), 3);
+		assert_eq!(Twitter::tweet_count_of(&1), 2);
+		assert_eq!(Twitter::tweet_count_of(&2), 1);
+	});
+}
+
+#[test]
+fn tweet_hooks_fire_once_per_created_tweet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+		assert_ok!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec(), b"two".to_vec()]));
+		assert_ok!(Announcements::new_tweet(Origin::signed(1), b"elsewhere".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_eq!(created_tweets(), vec![
+			(1, 0, TweetKind::Tweet),
+			(2, 1, TweetKind::Retweet),
+			(3, 2, TweetKind::Comment),
+			(3, 3, TweetKind::Retweet),
+			(1, 4, TweetKind::Tweet),
+			(1, 5, TweetKind::Tweet),
+		]);
+	});
+}
+
+#[test]
+fn tweet_hooks_do_not_fire_on_failures() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 141], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong
+		);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![], None), Error::<Test>::TweetNotFound);
+		assert_noop!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None), Error::<Test>::TweetNotFound);
+		assert_noop!(
+			Twitter::new_thread(Origin::signed(1), vec![b"ok".to_vec(), vec![b'a'; 141]]),
+			Error::<Test>::TweetTooLong
+		);
+
+		assert_eq!(created_tweets(), vec![]);
+	});
+}
+
+#[test]
+fn instances_keep_separate_feeds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"public".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"again".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Announcements::new_tweet(Origin::signed(1), b"announcement".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter_Instance1(RawEvent::Tweeted(1, 0, None, false)));
+		assert_ok!(Twitter::follow(Origin::signed(2), 1));
+
+		assert_eq!(Twitter::next_tweet_id(), 2);
+		assert_eq!(Announcements::next_tweet_id(), 1);
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"public".to_vec());
+		assert_eq!(Announcements::tweets(0).unwrap().text, b"announcement".to_vec());
+		assert_eq!(Twitter::accounts(&1), vec![0, 1]);
+		assert_eq!(Announcements::accounts(&1), vec![0]);
+		assert_eq!(Announcements::following(2, 1), None);
+		assert_noop!(
+			Announcements::comment(Origin::signed(2), b"reply".to_vec(), 1, None),
+			crate::Error::<Test, Instance1>::TweetNotFound
+		);
+
+		// Both feeds reserve deposits from the same balances.
+		assert_eq!(Balances::reserved_balance(1), 30);
+	});
+}
+
+#[test]
+fn genesis_seeds_tweets_and_follows() {
+	ExtBuilder::default()
+		.tweets(vec![(1, b"first #launch".to_vec()), (2, b"second".to_vec()), (1, b"third".to_vec())])
+		.follows(vec![(2, 1), (3, 1)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Twitter::next_tweet_id(), 3);
+			let tweet = Twitter::tweets(0).unwrap();
+			assert_eq!(tweet.author, 1);
+			assert_eq!(tweet.text, b"first #launch".to_vec());
+			assert_eq!(tweet.create_at, 0);
+			assert_eq!(Twitter::accounts(&1), vec![0, 2]);
+			assert_eq!(Twitter::accounts(&2), vec![1]);
+			assert_eq!(Twitter::hashtag_tweets(b"launch"), vec![0]);
+			assert_eq!(Twitter::tweet_count(), 3);
+			assert_eq!(Twitter::tweet_count_of(&1), 2);
+			assert_eq!(Twitter::tweet_deposit(0), 0);
+
+			assert!(Twitter::following(&2, &1).is_some());
+			assert_eq!(Twitter::follower_count(&1), 2);
+			assert_eq!(Twitter::following_count(&3), 1);
+
+			// New tweets get the next free id.
+			assert_ok!(Twitter::new_tweet(Origin::signed(3), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+			assert_eq!(Twitter::accounts(&3), vec![3]);
+			assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+			assert_eq!(Twitter::tweet_count(), 3);
+		});
+}
+
+#[test]
+#[should_panic(expected = "invalid genesis tweet text")]
+fn genesis_rejects_long_tweets() {
+	ExtBuilder::default().tweets(vec![(1, vec![b'a'; 141])]).build();
+}
+
+#[test]
+#[should_panic(expected = "genesis account follows itself")]
+fn genesis_rejects_self_follows() {
+	ExtBuilder::default().follows(vec![(1, 1)]).build();
+}
+
+#[test]
+fn text_limit_counts_characters() {
+	new_test_ext().execute_with(|| {
+		let emoji = "\u{1F980}".repeat(140).into_bytes();
+		assert_eq!(emoji.len(), 560);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), emoji.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(0).unwrap().text, emoji);
+
+		let chinese = "\u{4F60}\u{597D}".repeat(70).into_bytes();
+		assert_ok!(Twitter::comment(Origin::signed(2), chinese, 0, None));
+
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), "\u{1F980}".repeat(141).into_bytes(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong,
+		);
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a'; 141], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::TweetTooLong,
+		);
+	});
+}
+
+#[test]
+fn text_must_be_utf8() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![b'a', 0xff], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::InvalidUtf8,
+		);
+		// A character cut in half.
+		let mut text = "\u{1F980}".as_bytes().to_vec();
+		text.pop();
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), text, vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::InvalidUtf8,
+		);
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::edit_tweet(Origin::signed(1), 0, vec![0xc3]), Error::<Test>::InvalidUtf8);
+	});
+}
+
+#[test]
+fn media_can_be_attached() {
+	new_test_ext().execute_with(|| {
+		let cids = vec![b"bafybeigdyrzt".to_vec(), vec![b'a'; 64]];
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"look".to_vec(), vec![], true, Visibility::Public, cids.clone(), None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::MediaAttached(0, 2)));
+		let media: Vec<MediaCid> = cids.into_iter().map(|cid| MediaCid::try_from(cid).unwrap()).collect();
+		assert_eq!(Twitter::tweets(0).unwrap().media, media);
+
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"nice".to_vec(), vec![b"cid".to_vec()], None));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::MediaAttached(1, 1)));
+		assert_eq!(Twitter::tweets(1).unwrap().media.len(), 1);
+
+		// No event without media.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"plain".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(1, 2, None, true)));
+		assert!(Twitter::tweets(2).unwrap().media.is_empty());
+	});
+}
+
+#[test]
+fn media_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let three = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), b"look".to_vec(), vec![], true, Visibility::Public, three.clone(), None, ReplyPolicy::Everyone),
+			Error::<Test>::TooManyAttachments,
+		);
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), b"look".to_vec(), vec![], true, Visibility::Public, vec![vec![b'a'; 65]], None, ReplyPolicy::Everyone),
+			Error::<Test>::CidTooLong,
+		);
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), three, None), Error::<Test>::TooManyAttachments);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![vec![b'a'; 65]], None), Error::<Test>::CidTooLong);
+	});
+}
+
+#[test]
+fn migrate_from_v9_adds_media() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV6::<u64, u64> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::FollowersOnly,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V9);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert!(tweet.media.is_empty());
+		assert_eq!(tweet.visibility, Visibility::FollowersOnly);
+		assert_eq!(tweet.text, b"old".to_vec());
+	});
+}
+
+#[test]
+fn new_thread_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		let texts = vec![b"1/3 #story".to_vec(), b"2/3".to_vec(), b"3/3".to_vec()];
+		assert_ok!(Twitter::new_thread(Origin::signed(1), texts));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ThreadPosted(1, vec![1, 2, 3])));
+
+		assert_eq!(Twitter::tweets(1).unwrap().thread_prev, None);
+		assert_eq!(Twitter::tweets(2).unwrap().thread_prev, Some(1));
+		assert_eq!(Twitter::tweets(3).unwrap().thread_prev, Some(2));
+		assert_eq!(Twitter::tweets(3).unwrap().text, b"3/3".to_vec());
+		assert_eq!(Twitter::accounts(&1), vec![1, 2, 3]);
+		assert_eq!(Twitter::hashtag_tweets(b"story"), vec![1]);
+		assert_eq!(Twitter::tweet_count_of(&1), 3);
+		assert_eq!(Twitter::tweet_deposit(3), 10);
+		assert_eq!(Balances::reserved_balance(1), 30);
+		assert_eq!(Twitter::next_tweet_id(), 4);
+		assert_eq!(Twitter::tweet_rate(&1), (0, 3));
+	});
+}
+
+#[test]
+fn new_thread_is_atomic() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::new_thread(Origin::signed(1), vec![]), Error::<Test>::EmptyThread);
+		let four = vec![b"a".to_vec(); 4];
+		assert_noop!(Twitter::new_thread(Origin::signed(1), four), Error::<Test>::ThreadTooLong);
+
+		let texts = vec![b"first".to_vec(), vec![b'a'; 141], b"third".to_vec()];
+		assert_noop!(Twitter::new_thread(Origin::signed(1), texts), Error::<Test>::TweetTooLong);
+		assert_eq!(Twitter::next_tweet_id(), 0);
+		assert_eq!(Twitter::tweet_count(), 0);
+
+		// Account 1 can afford 9 deposits of 10, but keeps 1 to stay alive.
+		Balances::make_free_balance_be(&1, 25);
+		let texts = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+		assert_noop!(Twitter::new_thread(Origin::signed(1), texts), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn new_thread_counts_every_tweet_against_the_rate_limit() {
+	ExtBuilder::default().max_tweets_per_period(4).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_thread(Origin::signed(1), vec![b"a".to_vec(), b"b".to_vec()]));
+		assert_noop!(
+			Twitter::new_thread(Origin::signed(1), vec![b"c".to_vec(), b"d".to_vec()]),
+			Error::<Test>::RateLimited,
+		);
+		assert_ok!(Twitter::new_thread(Origin::signed(1), vec![b"c".to_vec()]));
+	});
+}
+
+#[test]
+fn migrate_from_v10_adds_thread_prev() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV7::<u64, u64> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![b"cid".to_vec()],
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V10);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.thread_prev, None);
+		assert_eq!(tweet.media, vec![MediaCid::try_from(b"cid".to_vec()).unwrap()]);
+		assert_eq!(tweet.text, b"old".to_vec());
+	});
+}
+
+#[test]
+fn interactions_notify_the_author() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::like(Origin::signed(3), 0));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+		assert_eq!(Twitter::notifications(1), vec![
+			Notification::CommentedOn(2, 1),
+			Notification::Liked(3, 0),
+			Notification::Retweeted(2, 2),
+		]);
+
+		// The inbox is full, the oldest notification is dropped.
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+		assert_eq!(Twitter::notifications(1), vec![
+			Notification::Liked(3, 0),
+			Notification::Retweeted(2, 2),
+			Notification::Retweeted(3, 3),
+		]);
+		assert_eq!(Twitter::notification_count(1), 4);
+	});
+}
+
+#[test]
+fn self_actions_do_not_notify() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"reply".to_vec(), 0, None));
+		assert_ok!(Twitter::like(Origin::signed(1), 0));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 0, b"quote".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(1), 0));
+
+		assert!(Twitter::notifications(1).is_empty());
+		assert_eq!(Twitter::notification_count(1), 0);
+	});
+}
+
+#[test]
+fn blocked_accounts_do_not_notify() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::block_account(Origin::signed(1), 2));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quote".to_vec(), vec![], None));
+
+		assert!(Twitter::notifications(1).is_empty());
+	});
+}
+
+#[test]
+fn mark_notifications_read_works() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..2 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![2], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_eq!(Twitter::unread_count(&2), 2);
+
+		assert_ok!(Twitter::mark_notifications_read(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::NotificationsRead(2, 0)));
+		assert_eq!(Twitter::unread_count(&2), 1);
+		assert_noop!(Twitter::mark_notifications_read(Origin::signed(2), 2), Error::<Test>::NotificationNotFound);
+
+		// Marking less than already read keeps the cursor.
+		assert_ok!(Twitter::mark_notifications_read(Origin::signed(2), 1));
+		assert_ok!(Twitter::mark_notifications_read(Origin::signed(2), 0));
+		assert_eq!(Twitter::notifications_read_up_to(2), 2);
+		assert_eq!(Twitter::unread_count(&2), 0);
+
+		// Notifications keep their index when older ones are dropped.
+		for _ in 0..4 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![2], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_eq!(Twitter::notification_count(2), 6);
+		assert_eq!(Twitter::unread_count(&2), 3);
+		assert_ok!(Twitter::mark_notifications_read(Origin::signed(2), 4));
+		assert_eq!(Twitter::unread_count(&2), 1);
+
+		assert_ok!(Twitter::clear_notifications(Origin::signed(2)));
+		assert_eq!(Twitter::unread_count(&2), 0);
+	});
+}
+
+#[test]
+fn migrate_from_v11_converts_mentions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hi".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(3), b"hey".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		unhashed::put(&<Notifications<Test>>::hashed_key_for(2), &vec![0u128, 7, 1]);
+		<StorageVersion>::put(Releases::V11);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::notifications(2), vec![Notification::Mentioned(1, 0), Notification::Mentioned(3, 1)]);
+		assert_eq!(Twitter::notification_count(2), 2);
+		assert_eq!(Twitter::unread_count(&2), 2);
+	});
+}
+
+#[test]
+fn migrate_from_v12_ranks_liked_tweets() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..5 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 4));
+		for &(id, likes) in &[(0, 1), (1, 3), (2, 1), (3, 2), (4, 5)] {
+			<LikeCount>::insert(id, likes);
+		}
+		<TopTweets<Test>>::kill();
+		<StorageVersion>::put(Releases::V12);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::top_tweets(), vec![(1, 3), (3, 2), (0, 1)]);
+	});
+}
+
+#[test]
+fn purge_account_removes_everything() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hey".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for _ in 0..2 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::like(Origin::signed(1), 0));
+		assert_ok!(Twitter::like(Origin::signed(2), 1));
+		assert_ok!(Twitter::follow(Origin::signed(1), 2));
+		assert_ok!(Twitter::block_account(Origin::signed(1), 3));
+		assert_ok!(Twitter::bookmark(Origin::signed(1), 0));
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(1), b"rust".to_vec()));
+		assert_ok!(Twitter::set_profile(Origin::signed(1), b"Alice".to_vec(), b"hi".to_vec(), None));
+		assert_ok!(Twitter::claim_handle(Origin::signed(1), b"alice".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 75);
+
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeComplete(1)));
+
+		assert!(Twitter::accounts(&1).is_empty());
+		assert_eq!(Twitter::account_tweet_count(1), 0);
+		assert!(!Twitter::likes(0, 1));
+		assert_eq!(Twitter::like_count(0), 0);
+		assert_eq!(<AccountLikeCount<Test>>::get(1), 0);
+		assert!(!Twitter::likes(1, 2));
+		assert_eq!(Twitter::following(1, 2), None);
+		assert_eq!(Twitter::follower_count(2), 0);
+		assert_eq!(Twitter::blocked(1, 3), None);
+		assert_eq!(Twitter::bookmarks(1, 0), None);
+		assert!(Twitter::followed_hashtags(&1).is_empty());
+		assert_eq!(Twitter::followed_hashtag_count(1), 0);
+		assert_eq!(Twitter::profiles(1), None);
+		assert_eq!(Twitter::handle_of(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// The tweet of the other account is left alone.
+		assert_eq!(Twitter::accounts(&2), vec![0]);
+
+		// Purging an empty account is a no-op.
+		assert_ok!(Twitter::purge_account(Origin::signed(3), 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeComplete(3)));
+	});
+}
+
+#[test]
+fn purge_account_is_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hey".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for _ in 0..3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::like(Origin::signed(1), 0));
+		assert_ok!(Twitter::follow(Origin::signed(1), 2));
+		assert_ok!(Twitter::set_profile(Origin::signed(1), b"Alice".to_vec(), b"hi".to_vec(), None));
+
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeProgress(1, 3)));
+		// Newest tweets go first.
+		assert_eq!(Twitter::accounts(&1), vec![1]);
+		assert!(Twitter::profiles(1).is_some());
+
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeProgress(1, 1)));
+		assert!(Twitter::accounts(&1).is_empty());
+		assert_eq!(Twitter::like_count(0), 0);
+
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeProgress(1, 1)));
+
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeComplete(1)));
+		assert_eq!(Twitter::follower_count(2), 0);
+		assert_eq!(Twitter::profiles(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn deleting_a_tweet_leaves_the_like_index_to_purge() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::like(Origin::signed(3), 0));
+		assert_eq!(Twitter::account_likes(2, 0), Some(()));
+		assert_eq!(<AccountLikeCount<Test>>::get(2), 1);
+
+		assert_ok!(Twitter::unlike(Origin::signed(3), 0));
+		assert_eq!(Twitter::account_likes(3, 0), None);
+		assert_eq!(<AccountLikeCount<Test>>::get(3), 0);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert!(!Twitter::likes(0, 2));
+		assert_eq!(Twitter::like_count(0), 0);
+		assert_eq!(Twitter::account_likes(2, 0), Some(()));
+		assert_eq!(Twitter::export_account(&2, 0, 10).liked, Vec::<u128>::new());
+
+		// Dropped with the other likes of the account, each one an item.
+		assert_ok!(Twitter::purge_account(Origin::signed(2), 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeComplete(2)));
+		assert_eq!(Twitter::account_likes(2, 0), None);
+		assert_eq!(<AccountLikeCount<Test>>::get(2), 0);
+	});
+}
+
+#[test]
+fn migrate_from_v13_indexes_likes() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..2 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::like(Origin::signed(2), 1));
+		assert_ok!(Twitter::like(Origin::signed(3), 1));
+		for who in &[2, 3] {
+			<AccountLikes<Test>>::remove_prefix(who);
+			<AccountLikeCount<Test>>::remove(who);
+		}
+		<StorageVersion>::put(Releases::V13);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::account_likes(2, 0), Some(()));
+		assert_eq!(Twitter::account_likes(2, 1), Some(()));
+		assert_eq!(Twitter::account_likes(3, 1), Some(()));
+		assert_eq!(<AccountLikeCount<Test>>::get(2), 2);
+		assert_eq!(<AccountLikeCount<Test>>::get(3), 1);
+	});
+}
+
+#[test]
+fn add_note_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::add_note(Origin::signed(1), 0, b"context".to_vec()), Error::<Test>::TweetNotFound);
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::add_note(Origin::signed(1), 0, vec![b'a'; 141]), Error::<Test>::TweetTooLong);
+
+		assert_ok!(Twitter::add_note(Origin::signed(1), 0, b"context".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::NoteAdded(1, 0, 0)));
+		assert_ok!(Twitter::add_note(Origin::signed(3), 0, b"more context".to_vec()));
+		assert_eq!(Twitter::note_count(0), 2);
+		let note = Twitter::notes(0, 1).unwrap();
+		assert_eq!(note.author, 3);
+		assert_eq!(note.text.to_vec(), b"more context".to_vec());
+		assert_eq!((note.up, note.down, note.shown), (0, 0, false));
+
+		// At most `MaxNotesPerTweet` notes.
+		assert_noop!(Twitter::add_note(Origin::signed(1), 0, b"again".to_vec()), Error::<Test>::TooManyNotes);
+
+		assert_ok!(Twitter::rate_note(Origin::signed(2), 0, 0, true));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 0));
+		assert_eq!(Twitter::notes(0, 0), None);
+		assert_eq!(Twitter::note_count(0), 0);
+		assert_eq!(Twitter::note_rating(0, (0, 2)), None);
+		assert_noop!(Twitter::add_note(Origin::signed(1), 0, b"context".to_vec()), Error::<Test>::TweetDeleted);
+	});
+}
+
+#[test]
+fn rated_notes_are_shown() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::add_note(Origin::signed(1), 0, b"context".to_vec()));
+		assert_noop!(Twitter::rate_note(Origin::signed(2), 0, 1, true), Error::<Test>::NoteNotFound);
+		assert_noop!(Twitter::rate_note(Origin::signed(1), 0, 0, true), Error::<Test>::CannotRateOwnNote);
+
+		// Not shown before `MinNoteRatings` ratings.
+		assert_ok!(Twitter::rate_note(Origin::signed(2), 0, 0, true));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::NoteRated(2, 0, 0, true)));
+		assert_eq!(Twitter::note_rating(0, (0, 2)), Some(true));
+		assert!(!Twitter::notes(0, 0).unwrap().shown);
+		assert_noop!(Twitter::rate_note(Origin::signed(2), 0, 0, false), Error::<Test>::AlreadyRated);
+
+		// Half of the ratings are helpful, short of `NoteShowThreshold`.
+		assert_ok!(Twitter::rate_note(Origin::signed(3), 0, 0, false));
+		assert!(!Twitter::notes(0, 0).unwrap().shown);
+
+		assert_ok!(Twitter::rate_note(Origin::signed(4), 0, 0, true));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::NoteShown(0, 0)));
+		let note = Twitter::notes(0, 0).unwrap();
+		assert_eq!((note.up, note.down, note.shown), (2, 1, true));
+
+		// A shown note stays shown.
+		assert_ok!(Twitter::rate_note(Origin::signed(5), 0, 0, false));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::NoteRated(5, 0, 0, false)));
+		assert!(Twitter::notes(0, 0).unwrap().shown);
+	});
+}
+
+#[test]
+fn follow_hashtag_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(1), b"#Rust".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::HashtagFollowed(1, b"rust".to_vec())));
+		assert_eq!(Twitter::followed_hashtags(&1), vec![b"rust".to_vec()]);
+		assert_noop!(Twitter::follow_hashtag(Origin::signed(1), b"rust".to_vec()), Error::<Test>::AlreadyFollowingHashtag);
+
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(1), b"polkadot".to_vec()));
+		assert_eq!(Twitter::followed_hashtag_count(1), 2);
+		assert_noop!(Twitter::follow_hashtag(Origin::signed(1), b"kusama".to_vec()), Error::<Test>::TooManyFollowedHashtags);
+
+		assert_ok!(Twitter::unfollow_hashtag(Origin::signed(1), b"#RUST".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::HashtagUnfollowed(1, b"rust".to_vec())));
+		assert_eq!(Twitter::followed_hashtags(&1), vec![b"polkadot".to_vec()]);
+		assert_noop!(Twitter::unfollow_hashtag(Origin::signed(1), b"rust".to_vec()), Error::<Test>::NotFollowingHashtag);
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(1), b"kusama".to_vec()));
+	});
+}
+
+#[test]
+fn followed_hashtags_must_be_valid() {
+	new_test_ext().execute_with(|| {
+		for tag in &[&b""[..], b"#", b"##rust", b"rust lang", b"r-s"] {
+			assert_noop!(Twitter::follow_hashtag(Origin::signed(1), tag.to_vec()), Error::<Test>::InvalidHashtag);
+		}
+		let long = vec![b'a'; crate::MaxFollowedHashtagLen::get() as usize + 1];
+		assert_noop!(Twitter::follow_hashtag(Origin::signed(1), long), Error::<Test>::InvalidHashtag);
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(1), b"web_3".to_vec()));
+	});
+}
+
+#[test]
+fn hashtag_followers_are_notified() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(1), b"rust".to_vec()));
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(2), b"rust".to_vec()));
+		assert_ok!(Twitter::follow_hashtag(Origin::signed(2), b"polkadot".to_vec()));
+
+		// Followers of several of the hashtags are notified once, the author not at all.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#Rust on #polkadot".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::notifications(2), vec![Notification::HashtagTweeted(1, 0)]);
+		assert!(Twitter::notifications(1).is_empty());
+
+		assert_ok!(Twitter::comment(Origin::signed(3), b"#polkadot too".to_vec(), 0, None));
+		assert_eq!(Twitter::notifications(2), vec![Notification::HashtagTweeted(1, 0), Notification::HashtagTweeted(3, 1)]);
+		assert_ok!(Twitter::new_tweet(Origin::signed(3), b"no tags".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::notifications(2).len(), 2);
+	});
+}
+
+#[test]
+fn hashtag_fanout_is_capped() {
+	new_test_ext().execute_with(|| {
+		for follower in 2..=4 {
+			assert_ok!(Twitter::follow_hashtag(Origin::signed(follower), b"rust".to_vec()));
+		}
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"#rust".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		let notified = (2..=4).filter(|follower| !Twitter::notifications(follower).is_empty()).count();
+		assert_eq!(notified as u32, MaxHashtagFanout::get());
+		// Everyone can still read the tweet from the hashtag index.
+		assert_eq!(Twitter::hashtag_tweets(b"rust"), vec![0]);
+	});
+}
+
+#[test]
+fn mute_word_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::mute_word(Origin::signed(1), b"Spoiler".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::WordMuted(1, b"spoiler".to_vec())));
+		assert_noop!(Twitter::mute_word(Origin::signed(1), b"SPOILER".to_vec()), Error::<Test>::AlreadyMuted);
+		assert_noop!(Twitter::mute_word(Origin::signed(1), vec![]), Error::<Test>::InvalidMutedWord);
+		assert_noop!(Twitter::mute_word(Origin::signed(1), vec![b'a'; 33]), Error::<Test>::InvalidMutedWord);
+		assert_ok!(Twitter::mute_word(Origin::signed(1), vec![b'a'; 32]));
+
+		// At most `MaxMutedWords` words.
+		assert_noop!(Twitter::mute_word(Origin::signed(1), b"crypto".to_vec()), Error::<Test>::TooManyMutedWords);
+		assert_eq!(Twitter::muted_words(1).len(), 2);
+
+		assert_ok!(Twitter::unmute_word(Origin::signed(1), b"spoILer".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::WordUnmuted(1, b"spoiler".to_vec())));
+		assert_noop!(Twitter::unmute_word(Origin::signed(1), b"spoiler".to_vec()), Error::<Test>::NotMuted);
+		assert_ok!(Twitter::mute_word(Origin::signed(1), b"crypto".to_vec()));
+		assert_eq!(Twitter::muted_words(1).iter().map(|word| word.to_vec()).collect::<Vec<_>>(), vec![vec![b'a'; 32], b"crypto".to_vec()]);
+	});
+}
+
+#[test]
+fn comments_with_muted_words_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::mute_word(Origin::signed(1), b"Spam".to_vec()));
+
+		assert_noop!(Twitter::comment(Origin::signed(2), b"buy SPAM now".to_vec(), 0, None), Error::<Test>::ContainsMutedWord);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"#sPaMmy".to_vec(), 0, None), Error::<Test>::ContainsMutedWord);
+		assert_ok!(Twitter::comment(Origin::signed(2), b"sp am".to_vec(), 0, None));
+
+		// Only the words of the author of the parent count.
+		assert_ok!(Twitter::mute_word(Origin::signed(2), b"hi".to_vec()));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"hi".to_vec(), 0, None));
+		assert_noop!(Twitter::comment(Origin::signed(3), b"hi".to_vec(), 1, None), Error::<Test>::ContainsMutedWord);
+
+		assert_ok!(Twitter::unmute_word(Origin::signed(1), b"spam".to_vec()));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"buy SPAM now".to_vec(), 0, None));
+	});
+}
+
+#[test]
+fn tweets_can_be_tagged_with_a_language() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], Some(*b"en"), ReplyPolicy::Everyone));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Tweeted(1, 0, Some(*b"en"), true)));
+		assert_eq!(Twitter::tweets(0).unwrap().lang(), Some(*b"en"));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"salut".to_vec(), vec![], Some(*b"fr")));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Retweeted(2, 1, 0, Some(*b"fr"))));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"hi".to_vec(), 0, Some(*b"en")));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Commented(3, 2, 0, Some(*b"en"))));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"untagged".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(3).unwrap().lang(), None);
+
+		assert_eq!(Twitter::tweets_in_lang(*b"en"), vec![0, 2]);
+		assert_eq!(Twitter::tweets_in_lang(*b"fr"), vec![1]);
+		assert!(Twitter::tweets_in_lang(*b"de").is_empty());
+
+		// Deleted tweets leave the index.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::tweets(0).unwrap().lang(), None);
+		assert_ok!(Twitter::undo_retweet(Origin::signed(2), 1));
+		assert_eq!(Twitter::tweets_in_lang(*b"en"), vec![2]);
+		assert!(Twitter::tweets_in_lang(*b"fr").is_empty());
+	});
+}
+
+#[test]
+fn language_codes_are_two_lowercase_letters() {
+	new_test_ext().execute_with(|| {
+		for &lang in &[*b"EN", *b"e1", *b"e ", [0xc3, 0xa9]] {
+			assert_noop!(
+				Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], Some(lang), ReplyPolicy::Everyone),
+				Error::<Test>::InvalidLanguageCode
+			);
+		}
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"hi".to_vec(), vec![], Some(*b"En")), Error::<Test>::InvalidLanguageCode);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, Some(*b"eN")), Error::<Test>::InvalidLanguageCode);
+	});
+}
+
+#[test]
+fn migrate_from_v14_adds_lang() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV8::<u64, u64, Vec<u8>, Vec<Vec<u8>>> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![b"cid".to_vec()],
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V14);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.lang(), None);
+		assert_eq!(tweet.text, b"old".to_vec());
+		assert_eq!(tweet.media, vec![MediaCid::try_from(b"cid".to_vec()).unwrap()]);
+	});
+}
+
+#[test]
+fn duplicate_tweets_are_rejected_within_the_window() {
+	ExtBuilder::default().dedup_window(10).build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"buy now".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), b"buy now".to_vec(), vec![], false, Visibility::FollowersOnly, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::DuplicateTweet
+		);
+
+		// Other texts, other authors, retweets and comments are fine.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"buy now!".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"buy now".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(1), 2, b"buy now".to_vec(), vec![], None));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"buy now".to_vec(), 0, None));
+
+		// The previous window still counts.
+		System::set_block_number(19);
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), b"buy now".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::DuplicateTweet
+		);
+
+		System::set_block_number(20);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"buy now".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), b"buy now".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::DuplicateTweet
+		);
+	});
+}
+
+#[test]
+fn old_content_hashes_are_pruned() {
+	ExtBuilder::default().dedup_window(10).build().execute_with(|| {
+		for i in 0..4u8 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), vec![b'a' + i], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		System::set_block_number(12);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"e".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		// The previous window is kept.
+		Twitter::on_initialize(19);
+		assert_eq!(<RecentContent<Test>>::iter_prefix(0).count(), 4);
+
+		// At most `MaxContentPrunesPerBlock` hashes are removed in a block.
+		Twitter::on_initialize(20);
+		assert_eq!(<RecentContent<Test>>::iter_prefix(0).count(), 1);
+		Twitter::on_initialize(21);
+		assert_eq!(<RecentContent<Test>>::iter_prefix(0).count(), 0);
+		assert_eq!(<RecentContent<Test>>::iter_prefix(1).map(|(_, id)| id).collect::<Vec<_>>(), vec![4]);
+	});
+}
+
+#[test]
+fn delegates_can_tweet_as_their_owner() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::tweet_as(Origin::signed(2), 1, b"hello".to_vec()), Error::<Test>::NotDelegate);
+		assert_noop!(Twitter::add_delegate(Origin::signed(1), 1), Error::<Test>::CannotDelegateSelf);
+		assert_ok!(Twitter::add_delegate(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::DelegateAdded(1, 2)));
+		assert_noop!(Twitter::add_delegate(Origin::signed(1), 2), Error::<Test>::AlreadyDelegate);
+
+		assert_ok!(Twitter::tweet_as(Origin::signed(2), 1, b"hello".to_vec()));
+		let events = System::events();
+		assert_eq!(events[events.len() - 2].event, TestEvent::twitter(RawEvent::Tweeted(1, 0, None, true)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetedVia(1, 2, 0)));
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.author, 1);
+		assert_eq!(Twitter::accounts(&1), vec![0]);
+		assert!(Twitter::accounts(&2).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// Delegation goes one way.
+		assert_noop!(Twitter::tweet_as(Origin::signed(1), 2, b"hello".to_vec()), Error::<Test>::NotDelegate);
+		// The tweet is the owner's to delete.
+		assert_noop!(Twitter::delete_tweet(Origin::signed(2), 0), Error::<Test>::NotAuthor);
+	});
+}
+
+#[test]
+fn removed_delegates_cannot_tweet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::add_delegate(Origin::signed(1), 2));
+		assert_ok!(Twitter::add_delegate(Origin::signed(1), 3));
+
+		// At most `MaxDelegates` delegates.
+		assert_noop!(Twitter::add_delegate(Origin::signed(1), 4), Error::<Test>::TooManyDelegates);
+		assert_eq!(Twitter::delegate_count(1), 2);
+
+		assert_ok!(Twitter::remove_delegate(Origin::signed(1), 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::DelegateRemoved(1, 2)));
+		assert_noop!(Twitter::remove_delegate(Origin::signed(1), 2), Error::<Test>::NotDelegate);
+		assert_noop!(Twitter::tweet_as(Origin::signed(2), 1, b"hello".to_vec()), Error::<Test>::NotDelegate);
+		assert_ok!(Twitter::tweet_as(Origin::signed(3), 1, b"hello".to_vec()));
+
+		assert_ok!(Twitter::add_delegate(Origin::signed(1), 4));
+		assert_eq!(Twitter::delegate_count(1), 2);
+	});
+}
+
+#[test]
+fn prune_old_tweets_requires_prune_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::prune_old_tweets(Origin::signed(1), 10, 10), BadOrigin);
+	});
+}
+
+#[test]
+fn prune_old_tweets_removes_old_tombstones() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"old #news".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"old but kept".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(2);
+		assert_ok!(Twitter::comment(Origin::signed(3), b"reply".to_vec(), 0, None));
+		System::set_block_number(3);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"recent".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for id in &[0, 2, 3] {
+			assert_ok!(Twitter::delete_tweet(Origin::signed(Twitter::tweets(id).unwrap().author), *id));
+		}
+
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 3, 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(2, 1)));
+		assert!(Twitter::tweets(0).is_none());
+		assert!(Twitter::tweets(2).is_none());
+		assert!(!Twitter::is_deleted(0));
+		assert_eq!(Twitter::comments_of(0), Vec::<u128>::new());
+		// The tweet holding a deposit and the tweet of block 3 are kept.
+		assert!(Twitter::tweets(1).is_some());
+		assert!(Twitter::is_deleted(3));
+		assert_eq!(Balances::reserved_balance(2), Twitter::tweet_deposit(1));
+		assert_eq!(Twitter::tweets_in_range(1, 2, None, 10), (vec![1], None));
+
+		// Skipped tweets are not considered again.
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 3, 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(0, 0)));
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 4, 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(1, 0)));
+		assert!(Twitter::tweets(3).is_none());
+	});
+}
+
+#[test]
+fn prune_old_tweets_is_bounded_by_limit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(who), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+			assert_ok!(Twitter::delete_tweet(Origin::signed(who), who as u128 - 1));
+		}
+		System::set_block_number(5);
+		let declared = Call::<Test>::prune_old_tweets(5, 2).get_dispatch_info().weight;
+
+		// Blocks 0 and 1 are scanned, the third tweet is left for the next call.
+		let info = Twitter::prune_old_tweets(Origin::root(), 5, 2).unwrap();
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(2, 0)));
+		assert_eq!(info.actual_weight, Some(declared));
+		let info = Twitter::prune_old_tweets(Origin::root(), 5, 2).unwrap();
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(1, 0)));
+		assert!(info.actual_weight.unwrap() < declared);
+		assert_eq!(Twitter::tweet_count(), 0);
+
+		// The empty blocks left count toward the limit.
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 5, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(0, 0)));
+	});
+}
+
+#[test]
+fn prune_old_tweets_skips_pinned_tweets() {
+	ExtBuilder::default().tweets(vec![(1, b"pinned".to_vec()), (1, b"genesis".to_vec())]).build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Twitter::pin_tweet(Origin::signed(1), 0));
+
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 1, 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(1, 1)));
+		assert!(Twitter::tweets(0).is_some());
+		assert!(Twitter::tweets(1).is_none());
+		assert_eq!(Twitter::accounts(&1), vec![0]);
+		assert_eq!(Twitter::tweets_in_range(0, 1, None, 10), (vec![0], None));
+	});
+}
+
+#[test]
+fn prune_old_tweets_moves_past_kept_tweets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(who), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+		System::set_block_number(2);
+
+		// The tweets holding a deposit are kept, each one once.
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 2, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(0, 2)));
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 2, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(0, 1)));
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 2, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(0, 0)));
+
+		assert_eq!(Twitter::tweets_in_range(1, 2, None, 10), (vec![0, 1, 2], None));
+	});
+}
+
+#[test]
+fn migrate_from_v15_indexes_tweets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		<TweetsByBlock<Test>>::remove_prefix(2);
+		<StorageVersion>::put(Releases::V15);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_ok!(Twitter::prune_old_tweets(Origin::root(), 3, 10));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Pruned(1, 0)));
+		assert!(Twitter::tweets(0).is_none());
+	});
+}
+
+#[test]
+fn reply_policy_is_enforced() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::follow(Origin::signed(2), 1));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"followers".to_vec(), vec![3], true, Visibility::Public, vec![], None, ReplyPolicy::Followers));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"mentioned".to_vec(), vec![3], true, Visibility::Public, vec![], None, ReplyPolicy::Mentioned));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"everyone".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(0).unwrap().reply_policy(), ReplyPolicy::Followers);
+
+		// A follower, a mentioned account and the author can reply under `Followers`.
+		assert_ok!(Twitter::comment(Origin::signed(2), b"follower".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"mentioned".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"author".to_vec(), 0, None));
+		assert_noop!(Twitter::comment(Origin::signed(4), b"stranger".to_vec(), 0, None), Error::<Test>::ReplyNotAllowed);
+
+		// Only the mentioned account and the author can reply under `Mentioned`.
+		assert_ok!(Twitter::comment(Origin::signed(3), b"mentioned".to_vec(), 1, None));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"author".to_vec(), 1, None));
+		assert_noop!(Twitter::comment(Origin::signed(2), b"follower".to_vec(), 1, None), Error::<Test>::ReplyNotAllowed);
+		assert_noop!(Twitter::comment(Origin::signed(4), b"stranger".to_vec(), 1, None), Error::<Test>::ReplyNotAllowed);
+
+		// Anyone can reply under `Everyone`.
+		assert_ok!(Twitter::comment(Origin::signed(2), b"follower".to_vec(), 2, None));
+		assert_ok!(Twitter::comment(Origin::signed(3), b"stranger".to_vec(), 2, None));
+		assert_eq!(Twitter::comment_count(2), 2);
+	});
+}
+
+#[test]
+fn reply_policy_can_only_be_relaxed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![3], true, Visibility::Public, vec![], None, ReplyPolicy::Mentioned));
+
+		assert_noop!(Twitter::set_reply_policy(Origin::signed(2), 0, ReplyPolicy::Everyone), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::set_reply_policy(Origin::signed(1), 42, ReplyPolicy::Everyone), Error::<Test>::TweetNotFound);
+
+		assert_ok!(Twitter::set_reply_policy(Origin::signed(1), 0, ReplyPolicy::Followers));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::ReplyPolicySet(0, ReplyPolicy::Followers)));
+		assert_noop!(Twitter::set_reply_policy(Origin::signed(1), 0, ReplyPolicy::Mentioned), Error::<Test>::ReplyPolicyTightened);
+		assert_ok!(Twitter::comment(Origin::signed(3), b"mentioned".to_vec(), 0, None));
+		assert_noop!(Twitter::comment(Origin::signed(2), b"stranger".to_vec(), 0, None), Error::<Test>::ReplyNotAllowed);
+
+		assert_ok!(Twitter::set_reply_policy(Origin::signed(1), 0, ReplyPolicy::Everyone));
+		assert!(<TweetMentions<Test>>::iter_prefix(0).next().is_none());
+		assert_noop!(Twitter::set_reply_policy(Origin::signed(1), 0, ReplyPolicy::Followers), Error::<Test>::ReplyPolicyTightened);
+		assert_ok!(Twitter::comment(Origin::signed(2), b"stranger".to_vec(), 0, None));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_noop!(Twitter::set_reply_policy(Origin::signed(1), 0, ReplyPolicy::Everyone), Error::<Test>::TweetDeleted);
+	});
+}
+
+#[test]
+fn migrate_from_v16_lets_everyone_reply() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV9::<u64, u64, Vec<u8>, Vec<Vec<u8>>> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![],
+			lang: Some(*b"en"),
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V16);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.reply_policy(), ReplyPolicy::Everyone);
+		assert_eq!(tweet.lang(), Some(*b"en"));
+		assert_eq!(tweet.text, b"old".to_vec());
+	});
+}
+
+#[test]
+fn reported_tweets_are_hidden_at_the_threshold() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"spam".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		// The report of the author does not count.
+		assert_ok!(Twitter::report_tweet(Origin::signed(1), 0, Reason::Spam));
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 0, Reason::Spam));
+		assert_ok!(Twitter::report_tweet(Origin::signed(3), 0, Reason::Spam));
+		assert_eq!(Twitter::report_count(0), 2);
+		assert!(!Twitter::tweets(0).unwrap().is_hidden());
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+
+		assert_ok!(Twitter::report_tweet(Origin::signed(4), 0, Reason::Spam));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetAutoHidden(0)));
+		assert!(Twitter::tweets(0).unwrap().is_hidden());
+		assert_noop!(Twitter::like(Origin::signed(3), 0), Error::<Test>::TweetHidden);
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, b"look".to_vec(), vec![], None), Error::<Test>::TweetHidden);
+		assert_noop!(Twitter::repost(Origin::signed(2), 0), Error::<Test>::TweetHidden);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, None), Error::<Test>::TweetHidden);
+
+		// Dismissing the reports shows the tweet again.
+		assert_ok!(Twitter::resolve_report(Origin::root(), 0, ModerationAction::Dismiss));
+		assert!(!Twitter::tweets(0).unwrap().is_hidden());
+		assert_eq!(Twitter::report_count(0), 0);
+		assert_ok!(Twitter::like(Origin::signed(3), 0));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, None));
+	});
+}
+
+#[test]
+fn hidden_tweets_can_be_removed_by_moderation() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"spam".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for who in 2..=4 {
+			assert_ok!(Twitter::report_tweet(Origin::signed(who), 0, Reason::Spam));
+		}
+		assert!(Twitter::tweets(0).unwrap().is_hidden());
+
+		assert_ok!(Twitter::resolve_report(Origin::root(), 0, ModerationAction::Remove));
+		assert!(Twitter::is_deleted(0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn reports_of_young_accounts_do_not_count() {
+	ExtBuilder::default().min_reporter_age(5).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::first_tweet_at(2), Some(1));
+		System::set_block_number(5);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"spam".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		// Account 2 first tweeted 4 blocks ago, account 3 never did.
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 1, Reason::Spam));
+		assert_ok!(Twitter::report_tweet(Origin::signed(3), 1, Reason::Spam));
+		assert_eq!(Twitter::report_count(1), 0);
+		assert_eq!(Twitter::reports(1, 3), Some(Reason::Spam));
+
+		// Reports that do not count can still be dismissed.
+		assert_ok!(Twitter::resolve_report(Origin::root(), 1, ModerationAction::Dismiss));
+		assert_eq!(Twitter::reports(1, 3), None);
+		assert_noop!(Twitter::resolve_report(Origin::root(), 1, ModerationAction::Dismiss), Error::<Test>::NoReports);
+
+		// Once old enough, the reports of account 2 count.
+		System::set_block_number(6);
+		<Reports<Test>>::remove(1, 2);
+		assert_ok!(Twitter::report_tweet(Origin::signed(2), 1, Reason::Spam));
+		assert_eq!(Twitter::report_count(1), 1);
+	});
+}
+
+#[test]
+fn migrate_from_v17_adds_hidden() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV10::<u64, u64, Vec<u8>, Vec<Vec<u8>>> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![],
+			lang: None,
+			reply_policy: ReplyPolicy::Mentioned,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V17);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert!(!tweet.is_hidden());
+		assert_eq!(tweet.reply_policy(), ReplyPolicy::Mentioned);
+	});
+}
+
+#[test]
+fn migrate_from_v18_records_first_tweets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		System::set_block_number(5);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"second".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for who in &[1, 2] {
+			<FirstTweetAt<Test>>::remove(who);
+		}
+		<StorageVersion>::put(Releases::V18);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::first_tweet_at(1), Some(3));
+		assert_eq!(Twitter::first_tweet_at(2), Some(5));
+		assert_eq!(Twitter::first_tweet_at(3), None);
+	});
+}
+
+#[test]
+fn migrate_from_v19_fills_previews() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"quoted".to_vec(), vec![], None));
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+		assert_ok!(Twitter::repost(Origin::signed(3), 0));
+		<LikePreview<Test>>::remove(0);
+		<RetweetPreview<Test>>::remove(0);
+		<StorageVersion>::put(Releases::V19);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::like_preview(0), vec![2]);
+		let mut retweeters = Twitter::retweet_preview(0).into_inner();
+		retweeters.sort_unstable();
+		assert_eq!(retweeters, vec![2, 3]);
+	});
+}
+
+#[test]
+fn migrate_from_v21_adds_revision() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV12::<u64, u64, Vec<u8>, Vec<Vec<u8>>> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![],
+			lang: None,
+			reply_policy: ReplyPolicy::Everyone,
+			hidden: true,
+			conversation_id: 0,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V21);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.revision(), 0);
+		assert!(tweet.is_hidden());
+		assert_eq!(Twitter::edit_history(0), vec![]);
+	});
+}
+
+#[test]
+fn migrate_from_v22_adds_community() {
+	new_test_ext().execute_with(|| {
+		let old = TweetV13::<u64, u64, Vec<u8>, Vec<Vec<u8>>> {
+			id: 0,
+			create_at: 1,
+			quote_tweet_id: None,
+			parent_id: None,
+			thread_prev: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![],
+			lang: None,
+			reply_policy: ReplyPolicy::Everyone,
+			hidden: false,
+			conversation_id: 0,
+			revision: 2,
+		};
+		unhashed::put(&<Tweets<Test>>::hashed_key_for(0), &old);
+		<NextTweetId>::put(1);
+		<StorageVersion>::put(Releases::V22);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(tweet.community(), None);
+		assert_eq!(tweet.revision(), 2);
+		assert_eq!(tweet.text(), b"old");
+	});
+}
+
+#[test]
+fn migrate_from_v20_records_conversations() {
+	new_test_ext().execute_with(|| {
+		let old = |id, create_at, parent_id, quote_tweet_id| TweetV11::<u64, u64, Vec<u8>, Vec<Vec<u8>>> {
+			id,
+			create_at,
+			quote_tweet_id,
+			parent_id,
+			thread_prev: None,
+			text: b"old".to_vec(),
+			author: 1,
+			comments_enabled: true,
+			visibility: Visibility::Public,
+			media: vec![],
+			lang: None,
+			reply_policy: ReplyPolicy::Everyone,
+			hidden: false,
+		};
+		// A three level reply tree under tweet 0, a retweet of it and a comment on a tweet that
+		// was removed.
+		let tweets = vec![
+			old(0, 1, None, None),
+			old(1, 3, Some(0), None),
+			old(2, 2, Some(1), None),
+			old(3, 4, None, Some(0)),
+			old(4, 4, Some(9), None),
+		];
+		for tweet in &tweets {
+			unhashed::put(&<Tweets<Test>>::hashed_key_for(tweet.id), tweet);
+		}
+		<NextTweetId>::put(10);
+		<StorageVersion>::put(Releases::V20);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let conversation_of = |id| Twitter::tweets(id).unwrap().conversation_id();
+		assert_eq!((0..5).map(conversation_of).collect::<Vec<_>>(), vec![0, 0, 0, 3, 9]);
+		// Ordered by creation block rather than by id.
+		let ids = |tweets: Vec<crate::TweetOf<Test>>| tweets.into_iter().map(|tweet| tweet.id).collect::<Vec<_>>();
+		assert_eq!(ids(Twitter::conversation(0, 0, 10)), vec![0, 2, 1]);
+		assert_eq!(ids(Twitter::conversation(9, 0, 10)), vec![4]);
+	});
+}
+
+#[test]
+fn create_community_makes_the_owner_a_member() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::create_community(Origin::signed(1), b"rustaceans".to_vec()));
+
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommunityCreated(1, 0)));
+		assert_eq!(Twitter::communities(0), Some(Community { id: 0, name: CommunityName::try_from(b"rustaceans".to_vec()).unwrap(), owner: 1 }));
+		assert!(Twitter::members(0, 1).is_some());
+		assert_eq!(Twitter::member_count(0), 1);
+
+		assert_noop!(Twitter::create_community(Origin::signed(1), vec![]), Error::<Test>::InvalidCommunityName);
+		assert_noop!(Twitter::create_community(Origin::signed(1), vec![b'a'; 33]), Error::<Test>::InvalidCommunityName);
+		assert_ok!(Twitter::create_community(Origin::signed(2), b"rustaceans".to_vec()));
+		assert_noop!(Twitter::create_community(Origin::signed(3), b"third".to_vec()), Error::<Test>::TooManyCommunities);
+	});
+}
+
+#[test]
+fn only_members_post_to_a_community() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::create_community(Origin::signed(1), b"rustaceans".to_vec()));
+		assert_noop!(Twitter::post_to_community(Origin::signed(2), 0, b"hi".to_vec()), Error::<Test>::NotAMember);
+		assert_noop!(Twitter::post_to_community(Origin::signed(1), 1, b"hi".to_vec()), Error::<Test>::CommunityNotFound);
+
+		assert_ok!(Twitter::join_community(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommunityJoined(2, 0)));
+		assert_noop!(Twitter::join_community(Origin::signed(2), 0), Error::<Test>::AlreadyMember);
+		assert_ok!(Twitter::post_to_community(Origin::signed(2), 0, b"hi".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommunityPosted(2, 0, 0)));
+		assert_ok!(Twitter::post_to_community(Origin::signed(1), 0, b"welcome".to_vec()));
+		assert_eq!(Twitter::tweets(0).unwrap().community(), Some(0));
+		assert_eq!(Twitter::community_feed(0, 0, 10), vec![1, 0]);
+		assert_eq!(Twitter::community_feed(0, 1, 10), vec![0]);
+
+		assert_ok!(Twitter::leave_community(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommunityLeft(2, 0)));
+		assert_eq!(Twitter::member_count(0), 1);
+		assert_noop!(Twitter::post_to_community(Origin::signed(2), 0, b"again".to_vec()), Error::<Test>::NotAMember);
+		assert_noop!(Twitter::leave_community(Origin::signed(2), 0), Error::<Test>::NotAMember);
+		assert_noop!(Twitter::leave_community(Origin::signed(1), 0), Error::<Test>::OwnerCannotLeave);
+		// Tweets posted before leaving stay in the feed.
+		assert_eq!(Twitter::community_feed(0, 0, 10), vec![1, 0]);
+	});
+}
+
+#[test]
+fn community_owner_removes_members() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::create_community(Origin::signed(1), b"rustaceans".to_vec()));
+		assert_ok!(Twitter::join_community(Origin::signed(2), 0));
+		assert_ok!(Twitter::join_community(Origin::signed(3), 0));
+		// At most `MaxMembersTracked` members, the owner included.
+		assert_noop!(Twitter::join_community(Origin::signed(4), 0), Error::<Test>::TooManyMembers);
+
+		assert_noop!(Twitter::remove_member(Origin::signed(2), 0, 3), Error::<Test>::NotCommunityOwner);
+		assert_noop!(Twitter::remove_member(Origin::signed(1), 0, 1), Error::<Test>::OwnerCannotLeave);
+		assert_ok!(Twitter::remove_member(Origin::signed(1), 0, 3));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommunityLeft(3, 0)));
+		assert!(Twitter::members(0, 3).is_none());
+		assert_noop!(Twitter::post_to_community(Origin::signed(3), 0, b"hi".to_vec()), Error::<Test>::NotAMember);
+		assert_ok!(Twitter::join_community(Origin::signed(4), 0));
+	});
+}
+
+#[test]
+fn deleted_tweets_leave_the_community_feed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::create_community(Origin::signed(1), b"rustaceans".to_vec()));
+		assert_ok!(Twitter::post_to_community(Origin::signed(1), 0, b"one".to_vec()));
+		assert_ok!(Twitter::post_to_community(Origin::signed(1), 0, b"two".to_vec()));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+
+		assert_eq!(Twitter::community_feed(0, 0, 10), vec![1]);
+		assert_eq!(Twitter::tweets(0).unwrap().community(), None);
+	});
+}
+
+fn commitment(text: &[u8], salt: [u8; 32]) -> [u8; 32] {
+	sp_io::hashing::blake2_256(&[text, &salt[..]].concat())
+}
+
+#[test]
+fn reveal_tweet_posts_it_anonymously() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let hash = commitment(b"secret", [7; 32]);
+		assert_ok!(Twitter::commit_tweet(Origin::signed(1), hash));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetCommitted(1, hash)));
+		assert_eq!(Balances::reserved_balance(1), CommitDeposit::get());
+		assert_noop!(Twitter::commit_tweet(Origin::signed(2), hash), Error::<Test>::AlreadyCommitted);
+
+		System::set_block_number(2 + RevealWindow::get());
+		assert_ok!(Twitter::reveal_tweet(Origin::signed(2), b"secret".to_vec(), [7; 32]));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetRevealed(0, hash)));
+		let tweet = Twitter::tweets(0).unwrap();
+		assert_eq!(*tweet.author(), AnonymousAccount::get());
+		assert_eq!(tweet.text(), b"secret");
+		assert_eq!(Twitter::tweet_commitment(0), Some((hash, 2)));
+		assert_eq!(Twitter::revealed_tweet(hash), Some(0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(Twitter::commitments(hash).is_none());
+	});
+}
+
+#[test]
+fn anonymous_tweets_are_not_capped() {
+	ExtBuilder::default().max_tweets_per_account(1).max_tweets_per_period(1).build().execute_with(|| {
+		for salt in 0..2 {
+			let hash = commitment(b"secret", [salt; 32]);
+			assert_ok!(Twitter::commit_tweet(Origin::signed(1), hash));
+			assert_ok!(Twitter::reveal_tweet(Origin::signed(2), b"secret".to_vec(), [salt; 32]));
+		}
+
+		assert_eq!(Twitter::tweet_count_of(AnonymousAccount::get()), 2);
+		assert_eq!(Twitter::tweet_deposit(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn reveal_tweet_rejects_a_wrong_salt() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::commit_tweet(Origin::signed(1), commitment(b"secret", [7; 32])));
+
+		assert_noop!(Twitter::reveal_tweet(Origin::signed(1), b"secret".to_vec(), [8; 32]), Error::<Test>::CommitmentNotFound);
+		assert_noop!(Twitter::reveal_tweet(Origin::signed(1), b"secrets".to_vec(), [7; 32]), Error::<Test>::CommitmentNotFound);
+	});
+}
+
+#[test]
+fn expired_commitments_forfeit_their_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let hash = commitment(b"secret", [7; 32]);
+		assert_ok!(Twitter::commit_tweet(Origin::signed(1), hash));
+
+		System::set_block_number(3 + RevealWindow::get());
+		assert_noop!(Twitter::reveal_tweet(Origin::signed(2), b"secret".to_vec(), [7; 32]), Error::<Test>::RevealWindowExpired);
+
+		Twitter::on_initialize(3 + RevealWindow::get());
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommitmentExpired(hash)));
+		assert!(Twitter::commitments(hash).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100 - CommitDeposit::get());
+		assert_noop!(Twitter::reveal_tweet(Origin::signed(2), b"secret".to_vec(), [7; 32]), Error::<Test>::CommitmentNotFound);
+	});
+}
+
+#[test]
+fn commitments_are_revealed_once() {
+	new_test_ext().execute_with(|| {
+		let hash = commitment(b"secret", [7; 32]);
+		assert_ok!(Twitter::commit_tweet(Origin::signed(1), hash));
+		assert_ok!(Twitter::reveal_tweet(Origin::signed(1), b"secret".to_vec(), [7; 32]));
+
+		assert_noop!(Twitter::reveal_tweet(Origin::signed(2), b"secret".to_vec(), [7; 32]), Error::<Test>::AlreadyRevealed);
+		assert_noop!(Twitter::commit_tweet(Origin::signed(1), hash), Error::<Test>::AlreadyCommitted);
+		assert_eq!(Twitter::next_tweet_id(), 1);
+	});
+}
+
+#[test]
+fn default_reply_policy_applies_to_new_tweets_and_comments() {
+	new_test_ext().execute_with(|| {
+		let settings = AccountSettings { reply_policy: ReplyPolicy::Mentioned, ..Default::default() };
+		assert_ok!(Twitter::update_settings(Origin::signed(1), settings.clone()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::SettingsUpdated(1)));
+		assert_eq!(Twitter::settings(1), settings);
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![3], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(0).unwrap().reply_policy(), ReplyPolicy::Mentioned);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, None), Error::<Test>::ReplyNotAllowed);
+		assert_ok!(Twitter::comment(Origin::signed(3), b"hi".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(1), b"thanks".to_vec(), 0, None));
+		assert_eq!(Twitter::tweets(2).unwrap().reply_policy(), ReplyPolicy::Mentioned);
+
+		assert_ok!(Twitter::update_settings(Origin::signed(1), AccountSettings::default()));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"again".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::tweets(3).unwrap().reply_policy(), ReplyPolicy::Everyone);
+		assert!(!Settings::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn mentions_from_non_followers_can_be_muted() {
+	new_test_ext().execute_with(|| {
+		let settings = AccountSettings { mentions_from_non_followers: false, ..Default::default() };
+		assert_ok!(Twitter::update_settings(Origin::signed(2), settings));
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hey".to_vec(), vec![2], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert!(Twitter::notifications(2).is_empty());
+
+		assert_ok!(Twitter::follow(Origin::signed(1), 2));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hey again".to_vec(), vec![2], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::notifications(2), vec![Notification::Mentioned(1, 1)]);
+	});
+}
+
+#[test]
+fn tips_can_be_turned_off() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::tip(Origin::signed(2), 0, 5));
+
+		let settings = AccountSettings { accepts_tips: false, ..Default::default() };
+		assert_ok!(Twitter::update_settings(Origin::signed(1), settings));
+		assert_noop!(Twitter::tip(Origin::signed(2), 0, 5), Error::<Test>::TipsDisabled);
+	});
+}
+
+#[test]
+fn export_account_round_trips_through_json() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"first".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"second".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(2), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::set_profile(Origin::signed(1), b"Alice".to_vec(), b"hi".to_vec(), None));
+		assert_ok!(Twitter::claim_handle(Origin::signed(1), b"alice".to_vec()));
+		assert_ok!(Twitter::like(Origin::signed(1), 2));
+		assert_ok!(Twitter::bookmark(Origin::signed(1), 0));
+		assert_ok!(Twitter::follow(Origin::signed(1), 3));
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::bookmark(Origin::signed(2), 1));
+		assert_ok!(Twitter::follow(Origin::signed(2), 1));
+
+		let first = Twitter::export_account(&1, 0, 1);
+		assert_eq!(first.tweets, vec![Twitter::tweets(0).unwrap()]);
+		assert_eq!(first.liked, vec![2]);
+		assert_eq!(first.bookmarked, vec![0]);
+		assert_eq!(first.following, vec![3]);
+		assert_eq!(first.profile, Twitter::profiles(1));
+		assert_eq!(first.handle.clone().map(|handle| handle.into_inner()), Some(b"alice".to_vec()));
+		assert_eq!(first.settings, Some(Default::default()));
+		assert_eq!(first.next_cursor, Some(1));
+
+		let json = serde_json::to_string(&first).unwrap();
+		assert_eq!(serde_json::from_str::<AccountExportOf<Test>>(&json).unwrap(), first);
+
+		let second = Twitter::export_account(&1, 1, 1);
+		assert_eq!(second.tweets, vec![Twitter::tweets(1).unwrap()]);
+		assert!(second.liked.is_empty() && second.bookmarked.is_empty() && second.following.is_empty());
+		assert_eq!((second.profile, second.handle, second.settings), (None, None, None));
+		assert_eq!(second.next_cursor, None);
+	});
+}
+
+#[test]
+fn tweets_in_range_includes_from_and_excludes_to() {
+	new_test_ext().execute_with(|| {
+		for block in 1..=4 {
+			System::set_block_number(block);
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), vec![b'a'; block as usize], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+			assert_ok!(Twitter::new_tweet(Origin::signed(2), vec![b'b'; block as usize], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+
+		assert_eq!(Twitter::tweets_in_range(2, 4, None, 10), (vec![2, 3, 4, 5], None));
+		assert_eq!(Twitter::tweets_in_range(4, 4, None, 10), (vec![], None));
+		assert_eq!(Twitter::tweets_in_range(0, 10, None, 10), ((0..8).collect::<Vec<_>>(), None));
+
+		// A page can end within a block.
+		assert_eq!(Twitter::tweets_in_range(2, 4, None, 3), (vec![2, 3, 4], Some((3, Some(4)))));
+		assert_eq!(Twitter::tweets_in_range(3, 4, Some(4), 3), (vec![5], None));
+		assert_eq!(Twitter::tweets_in_range(2, 4, None, 2), (vec![2, 3], Some((3, None))));
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 2));
+		assert_eq!(Twitter::tweets_in_range(2, 4, None, 10), (vec![3, 4, 5], None));
+	});
+}
+
+#[test]
+fn tweets_in_range_scans_a_bounded_number_of_blocks() {
+	new_test_ext().execute_with(|| {
+		let end = crate::MaxRangeBlocks::get() as u64 + 10;
+		System::set_block_number(end - 1);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"late".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		let (ids, cursor) = Twitter::tweets_in_range(0, end, None, 10);
+		assert!(ids.is_empty());
+		assert_eq!(cursor, Some((crate::MaxRangeBlocks::get() as u64, None)));
+		assert_eq!(Twitter::tweets_in_range(crate::MaxRangeBlocks::get() as u64, end, None, 10), (vec![0], None));
+	});
+}
+
+#[test]
+fn quotes_of_edited_tweets_are_stale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"untouched".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 0, b"agreed".to_vec(), vec![], None));
+		assert_ok!(Twitter::retweet(Origin::signed(2), 1, b"nice".to_vec(), vec![], None));
+		assert_eq!(Twitter::quote_is_stale(2), Some(false));
+
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"goodbye".to_vec()));
+
+		assert_eq!(Twitter::quote_is_stale(2), Some(true));
+		assert!(<Twitter as TweetProvider<_, _>>::get(2).unwrap().quote_edited);
+		assert_eq!(Twitter::quote_is_stale(3), Some(false));
+		assert!(!<Twitter as TweetProvider<_, _>>::get(3).unwrap().quote_edited);
+		assert_eq!(Twitter::quote_is_stale(0), None);
+	});
+}
+
+#[test]
+fn comments_are_removed_by_their_author_or_the_tweet_author() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"other".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"second".to_vec(), 0, None));
+		assert_eq!(Twitter::comment_count(0), 2);
+
+		// A third party.
+		assert_noop!(Twitter::remove_comment(Origin::signed(3), 0, 2), Error::<Test>::NotAuthor);
+		assert_noop!(Twitter::remove_comment(Origin::signed(1), 1, 2), Error::<Test>::NotAComment);
+		assert_noop!(Twitter::remove_comment(Origin::signed(1), 0, 1), Error::<Test>::NotAComment);
+
+		// The author of the comment.
+		assert_ok!(Twitter::remove_comment(Origin::signed(2), 0, 2));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommentRemoved(0, 2, 2)));
+		assert!(Twitter::is_deleted(2));
+		assert_eq!(Twitter::comment_count(0), 1);
+		assert_eq!(Twitter::comments_page(0, 0, 10), vec![3]);
+		assert_noop!(Twitter::remove_comment(Origin::signed(2), 0, 2), Error::<Test>::NotAComment);
+
+		// The author of the tweet.
+		assert_ok!(Twitter::remove_comment(Origin::signed(1), 0, 3));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::CommentRemoved(0, 3, 1)));
+		assert_eq!(Twitter::comment_count(0), 0);
+		assert!(Twitter::comments_page(0, 0, 10).is_empty());
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn validate_text_rejects_control_bidi_and_zero_width_padding() {
+	for text in [
+		&b"null\0byte"[..],
+		b"tab\there",
+		b"carriage\r\nreturn",
+		b"escape\x1b[31m",
+		b"delete\x7f",
+		"c1\u{85}control".as_bytes(),
+		"right-to-left \u{202E}override".as_bytes(),
+		"isolate \u{2067}text\u{2069}".as_bytes(),
+		"padding\u{200B}\u{200B}".as_bytes(),
+		"joiners\u{200D}\u{FEFF}".as_bytes(),
+	].iter() {
+		assert_eq!(Twitter::validate_text(text), Err(Error::<Test>::InvalidCharacters), "{:?}", text);
+	}
+	assert_eq!(Twitter::validate_text(&[0xff]), Err(Error::<Test>::InvalidUtf8));
+
+	for text in [
+		"two\nlines\n\nand a gap".as_bytes(),
+		"family \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}".as_bytes(),
+		"flag \u{1F3F3}\u{FE0F}\u{200D}\u{1F308} and caf\u{E9}".as_bytes(),
+		"\u{05E9}\u{05DC}\u{05D5}\u{05DD} mixed with latin".as_bytes(),
+	].iter() {
+		assert_eq!(Twitter::validate_text(text), Ok(()), "{:?}", text);
+	}
+}
+
+#[test]
+fn posting_rejects_invalid_characters() {
+	new_test_ext().execute_with(|| {
+		let text = "hidden\u{202E}txt".as_bytes().to_vec();
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), text.clone(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::InvalidCharacters
+		);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"line one\nline two".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::comment(Origin::signed(2), text.clone(), 0, None), Error::<Test>::InvalidCharacters);
+		assert_noop!(Twitter::edit_tweet(Origin::signed(1), 0, b"\0".to_vec()), Error::<Test>::InvalidCharacters);
+	});
+}
+
+#[test]
+fn empty_tweets_retweets_and_comments_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Twitter::new_tweet(Origin::signed(1), vec![], vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone),
+			Error::<Test>::EmptyTweet
+		);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::retweet(Origin::signed(2), 0, vec![], vec![], None), Error::<Test>::EmptyTweet);
+		assert_noop!(Twitter::comment(Origin::signed(2), vec![], 0, None), Error::<Test>::EmptyTweet);
+		// Reposts carry no text of their own.
+		assert_ok!(Twitter::repost(Origin::signed(2), 0));
+	});
+}
+
+#[test]
+fn no_tweet_can_be_emptied() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_noop!(Twitter::edit_tweet(Origin::signed(1), 0, vec![]), Error::<Test>::EmptyTweet);
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"hello".to_vec());
+
+		assert_noop!(Twitter::new_thread(Origin::signed(1), vec![b"one".to_vec(), vec![]]), Error::<Test>::EmptyTweet);
+		assert_noop!(Twitter::new_ephemeral_tweet(Origin::signed(1), vec![], 5), Error::<Test>::EmptyTweet);
+		assert_noop!(Twitter::schedule_tweet(Origin::signed(1), vec![], 5), Error::<Test>::EmptyTweet);
+		assert_noop!(Twitter::new_poll_tweet(Origin::signed(1), vec![], vec![b"a".to_vec(), b"b".to_vec()], 5), Error::<Test>::EmptyTweet);
+	});
+	ExtBuilder::default().first_user_tweet_id(10).build().execute_with(|| {
+		assert_noop!(Twitter::force_create_tweet(Origin::root(), 0, 1, vec![]), Error::<Test>::EmptyTweet);
+	});
+}
+
+#[test]
+fn tweets_cannot_refer_to_themselves() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, None));
+
+		assert_noop!(Twitter::remove_comment(Origin::signed(2), 1, 1), Error::<Test>::SelfReferenceNotAllowed);
+		assert_noop!(Twitter::remove_comment(Origin::signed(1), 0, 0), Error::<Test>::SelfReferenceNotAllowed);
+	});
+}
+
+#[test]
+fn force_create_tweet_uses_reserved_ids() {
+	ExtBuilder::default().first_user_tweet_id(10).build().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::accounts(&1), vec![10]);
+
+		assert_noop!(Twitter::force_create_tweet(Origin::signed(1), 3, 2, b"welcome".to_vec()), BadOrigin);
+		assert_ok!(Twitter::force_create_tweet(Origin::root(), 3, 2, b"welcome #launch".to_vec()));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::SystemTweetCreated(2, 3)));
+		let tweet = Twitter::tweets(3).unwrap();
+		assert_eq!(tweet.author, 2);
+		assert_eq!(tweet.create_at, 1);
+		assert_eq!(Twitter::hashtag_tweets(b"launch"), vec![3]);
+		assert_eq!(Twitter::tweet_deposit(3), 0);
+
+		assert_noop!(Twitter::force_create_tweet(Origin::root(), 3, 2, b"again".to_vec()), Error::<Test>::IdAlreadyUsed);
+		assert_noop!(Twitter::force_create_tweet(Origin::root(), 10, 2, b"taken".to_vec()), Error::<Test>::IdNotReserved);
+		assert_noop!(Twitter::force_create_tweet(Origin::root(), 12, 2, b"free".to_vec()), Error::<Test>::IdNotReserved);
+
+		// Accounts keep getting ids past the reserved range.
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"world".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::accounts(&1), vec![10, 11]);
+	});
+}
+
+#[test]
+fn genesis_seeds_system_tweets() {
+	ExtBuilder::default()
+		.first_user_tweet_id(10)
+		.system_tweets(vec![(0, 1, b"welcome #launch".to_vec()), (5, 1, b"rules".to_vec())])
+		.tweets(vec![(2, b"first".to_vec())])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Twitter::accounts(&1), vec![0, 5]);
+			assert_eq!(Twitter::accounts(&2), vec![10]);
+			assert_eq!(Twitter::hashtag_tweets(b"launch"), vec![0]);
+			assert_eq!(Twitter::next_tweet_id(), 11);
+		});
+}
+
+#[test]
+#[should_panic(expected = "genesis system tweet id is not reserved")]
+fn genesis_rejects_unreserved_system_tweets() {
+	ExtBuilder::default().first_user_tweet_id(10).system_tweets(vec![(10, 1, b"welcome".to_vec())]).build();
+}
+
+#[test]
+fn migrate_from_v23_skips_reserved_ids() {
+	ExtBuilder::default().first_user_tweet_id(10).build().execute_with(|| {
+		<NextTweetId>::put(3);
+		<StorageVersion>::put(Releases::V23);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::next_tweet_id(), 10);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_eq!(Twitter::accounts(&1), vec![10]);
+
+		// Ids already past the reserved range are kept.
+		<NextTweetId>::put(20);
+		<StorageVersion>::put(Releases::V23);
+		Twitter::on_runtime_upgrade();
+		assert_eq!(Twitter::next_tweet_id(), 20);
+	});
+}
+
+#[test]
+fn comments_keep_their_order_across_adds_and_removes() {
+	new_test_ext().execute_with(|| {
+		let mut seed: u64 = 42;
+		let mut next_random = move || {
+			seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			(seed >> 33) as usize
+		};
+		for _ in 0..8 {
+			let parent = Twitter::next_tweet_id();
+			assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+			// `(index, id)` of the comments that were not removed, oldest first.
+			let mut surviving: Vec<(u32, TweetId)> = vec![];
+			let mut added = 0;
+			while added < MaxCommentsPerTweet::get() {
+				if !surviving.is_empty() && next_random() % 3 == 0 {
+					let (_, id) = surviving.remove(next_random() % surviving.len());
+					let by = if next_random() % 2 == 0 { 1 } else { Twitter::tweets(id).unwrap().author };
+					assert_ok!(Twitter::remove_comment(Origin::signed(by), parent, id));
+					assert_eq!(Twitter::comment_index(id), None);
+				} else {
+					let id = Twitter::next_tweet_id();
+					let author = 2 + next_random() as u64 % 2;
+					assert_ok!(Twitter::comment(Origin::signed(author), b"reply".to_vec(), parent, None));
+					surviving.push((added, id));
+					added += 1;
+				}
+
+				let ids: Vec<TweetId> = surviving.iter().map(|(_, id)| *id).collect();
+				assert_eq!(Twitter::comments_of(parent), ids);
+				assert_eq!(Twitter::comments_page(parent, 0, MaxCommentsPerTweet::get()), ids);
+				assert_eq!(Twitter::comment_count(parent), ids.len() as u32);
+				for (index, id) in &surviving {
+					assert_eq!(Twitter::comment_index(id), Some(*index));
+					assert_eq!(Twitter::comments_page(parent, *index, 1), vec![*id]);
+					// Pages starting at a comment are the same whatever was removed before it.
+					let later: Vec<TweetId> = surviving.iter().filter(|(i, _)| i >= index).map(|(_, id)| *id).collect();
+					assert_eq!(Twitter::comments_page(parent, *index, MaxCommentsPerTweet::get()), later);
+				}
+			}
+			assert_noop!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), parent, None), Error::<Test>::TooManyComments);
+			for (_, id) in surviving {
+				assert_ok!(Twitter::remove_comment(Origin::signed(1), parent, id));
+			}
+			assert!(Twitter::comments_of(parent).is_empty());
+		}
+	});
+}
+
+#[test]
+fn migrate_from_v24_indexes_comments() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		for _ in 0..3 {
+			assert_ok!(Twitter::comment(Origin::signed(2), b"reply".to_vec(), 0, None));
+		}
+		for id in 1..4 {
+			<CommentIndex>::remove(id);
+		}
+		// A comment removed before `remove_tweet` dropped its entry in `Comments`.
+		<Tweets<Test>>::remove(2);
+		<StorageVersion>::put(Releases::V24);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		assert_eq!(Twitter::comment_index(1), Some(0));
+		assert_eq!(Twitter::comment_index(2), None);
+		assert_eq!(Twitter::comment_index(3), Some(2));
+		assert_eq!(Twitter::comments(0, 1), None);
+		assert_eq!(Twitter::comments_page(0, 0, 10), vec![1, 3]);
+	});
+}
+
+#[test]
+fn followers_are_paged_by_key() {
+	ExtBuilder::default()
+		.follows((100..125).map(|follower| (follower, 1)).collect())
+		.build()
+		.execute_with(|| {
+			assert_eq!(Twitter::follower_count(&1), 25);
+			let mut followers = vec![];
+			let mut sizes = vec![];
+			let mut start = None;
+			loop {
+				let page = Twitter::followers_page(&1, start, 10);
+				sizes.push(page.len());
+				if page.len() < 10 {
+					followers.extend(page);
+					break;
+				}
+				start = page.last().copied();
+				followers.extend(page);
+			}
+			assert_eq!(sizes, vec![10, 10, 5]);
+			followers.sort();
+			assert_eq!(followers, (100..125).collect::<Vec<u64>>());
+			assert!(Twitter::followers_page(&2, None, 10).is_empty());
+
+			assert_ok!(Twitter::follow(Origin::signed(2), 1));
+			assert!(Twitter::follower(&1, &2).is_some());
+			assert_ok!(Twitter::unfollow(Origin::signed(100), 1));
+			assert!(Twitter::follower(&1, &100).is_none());
+			let mut followers = Twitter::followers_page(&1, None, 30);
+			followers.sort();
+			let mut expected = vec![2];
+			expected.extend(101..125);
+			assert_eq!(followers, expected);
+		});
+}
+
+#[test]
+fn migrate_from_v25_records_followers() {
+	ExtBuilder::default().follows(vec![(2, 1), (3, 1), (1, 2)]).build().execute_with(|| {
+		for (target, follower) in vec![(1, 2), (1, 3), (2, 1)] {
+			<Followers<Test>>::remove(target, follower);
+		}
+		<StorageVersion>::put(Releases::V25);
+
+		Twitter::on_runtime_upgrade();
+
+		assert_eq!(Twitter::storage_version(), Releases::V27);
+		let mut followers = Twitter::followers_page(&1, None, 10);
+		followers.sort();
+		assert_eq!(followers, vec![2, 3]);
+		assert_eq!(Twitter::followers_page(&2, None, 10), vec![1]);
+	});
+}
+
+#[test]
+fn follower_milestones_are_announced() {
+	ExtBuilder::default()
+		.follows((100..109).map(|follower| (follower, 1)).collect())
+		.build()
+		.execute_with(|| {
+			assert_ok!(Twitter::follow(Origin::signed(2), 1));
+			assert_eq!(Twitter::follower_count(&1), 10);
+			let events: Vec<TestEvent> = System::events().into_iter().map(|record| record.event).collect();
+			assert_eq!(events, vec![
+				TestEvent::twitter(RawEvent::Followed(2, 1)),
+				TestEvent::twitter(RawEvent::FollowerMilestone(1, 10)),
+			]);
+
+			assert_ok!(Twitter::follow(Origin::signed(3), 1));
+			assert_eq!(last_event(), TestEvent::twitter(RawEvent::Followed(3, 1)));
+			assert_ok!(Twitter::unfollow(Origin::signed(3), 1));
+			assert_eq!(last_event(), TestEvent::twitter(RawEvent::Unfollowed(3, 1)));
+			assert_ok!(Twitter::unfollow(Origin::signed(2), 1));
+			assert_ok!(Twitter::follow(Origin::signed(2), 1));
+			assert_eq!(last_event(), TestEvent::twitter(RawEvent::FollowerMilestone(1, 10)));
+
+			for &count in [1, 9, 11, 20, 99, 101, 110, 1_001].iter() {
+				assert!(!Twitter::is_follower_milestone(count));
+			}
+			for &count in [10, 100, 1_000, 1_000_000_000].iter() {
+				assert!(Twitter::is_follower_milestone(count));
+			}
+		});
+}
+
+#[test]
+fn tips_rank_authors_by_earnings() {
+	new_test_ext().execute_with(|| {
+		for author in 1..=3 {
+			assert_ok!(Twitter::new_tweet(Origin::signed(author), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		}
+
+		assert_ok!(Twitter::tip(Origin::signed(2), 0, 5));
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(1, 5)]);
+		assert_ok!(Twitter::tip(Origin::signed(1), 1, 3));
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(1, 5), (2, 3)]);
+		// Account 1 reached 5 first.
+		assert_ok!(Twitter::tip(Origin::signed(1), 2, 5));
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(1, 5), (3, 5), (2, 3)]);
+		assert_ok!(Twitter::tip(Origin::signed(3), 1, 4));
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(2, 7), (1, 5), (3, 5)]);
+		assert_ok!(Twitter::tip(Origin::signed(2), 2, 1));
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(2, 7), (3, 6), (1, 5)]);
+		assert_eq!(Twitter::earnings_of(&2), 7);
+
+		// Earnings are kept when the tipped tweets go away.
+		assert_ok!(Twitter::delete_tweet(Origin::signed(2), 1));
+		assert_ok!(Twitter::report_tweet(Origin::signed(1), 2, Reason::Spam));
+		assert_ok!(Twitter::resolve_report(Origin::root(), 2, ModerationAction::Remove));
+		assert_eq!(Twitter::tip_total(2), 6);
+		assert_eq!(Twitter::earnings_of(&2), 7);
+		assert_eq!(Twitter::earnings_of(&3), 6);
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(2, 7), (3, 6), (1, 5)]);
+	});
+}
+
+#[test]
+fn top_earners_keeps_the_most_earning_accounts() {
+	new_test_ext().execute_with(|| {
+		for i in 0..25 {
+			Twitter::rank_earner(&(100 + i), 1 + i);
+		}
+		let board = Twitter::top_earners().into_inner();
+		assert_eq!(board.len(), crate::MaxTopEarners::get() as usize);
+		assert_eq!(board[0], (124, 25));
+		assert_eq!(board[19], (105, 6));
+
+		// An account earning the least leaves the full board.
+		Twitter::rank_earner(&124, 2);
+		assert_eq!(Twitter::top_earners()[19], (124, 2));
+		Twitter::rank_earner(&200, 3);
+		assert_eq!(Twitter::top_earners()[19], (200, 3));
+		assert!(Twitter::top_earners().iter().all(|(account, _)| *account != 124));
+		Twitter::rank_earner(&100, u64::max_value());
+		assert_eq!(Twitter::top_earners()[0], (100, u64::max_value()));
+	});
+}
+
+#[test]
+fn purge_account_keeps_earnings() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::tip(Origin::signed(2), 0, 5));
+
+		assert_ok!(Twitter::purge_account(Origin::signed(1), 100));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeComplete(1)));
+		assert!(Twitter::is_deleted(0));
+		assert_eq!(Twitter::earnings_of(&1), 5);
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(1, 5)]);
+	});
+}
+
+#[test]
+fn force_purge_account_takes_the_account_off_top_earners() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::tip(Origin::signed(2), 0, 5));
+		assert_eq!(Twitter::top_earners().into_inner(), vec![(1, 5)]);
+
+		assert_noop!(Twitter::force_purge_account(Origin::signed(2), 1, 100), BadOrigin);
+		assert_ok!(Twitter::force_purge_account(Origin::root(), 1, 100));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::PurgeComplete(1)));
+		assert!(Twitter::is_deleted(0));
+		assert!(Twitter::top_earners().is_empty());
+		assert_eq!(Twitter::earnings_of(&1), 0);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn gated_tweets_are_unlocked_by_paying_the_author() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
 		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
+			Twitter::new_gated_tweet(Origin::signed(1), b"teaser".to_vec(), b"full".to_vec(), 0),
+			Error::<Test>::ZeroPrice
+		);
+		assert_noop!(
+			Twitter::new_gated_tweet(Origin::signed(1), b"teaser".to_vec(), vec![], 5),
+			Error::<Test>::EmptyTweet
 		);
+		assert_ok!(Twitter::new_gated_tweet(Origin::signed(1), b"teaser #news".to_vec(), b"full story".to_vec(), 5));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::GatedTweetCreated(1, 0, 5)));
+		assert_eq!(Twitter::tweets(0).unwrap().text, b"teaser #news".to_vec());
+		assert_eq!(Twitter::hashtag_tweets(b"news"), vec![0]);
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"open".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+
+		assert_eq!(Twitter::gated_text(0, &1).unwrap().into_inner(), b"full story".to_vec());
+		assert_eq!(Twitter::gated_text(0, &2), None);
+		assert_eq!(Twitter::gated_text(1, &1), None);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None), Error::<Test>::NotUnlocked);
+		assert_ok!(Twitter::comment(Origin::signed(1), b"by the author".to_vec(), 0, None));
+		assert_noop!(Twitter::unlock(Origin::signed(2), 1), Error::<Test>::NotGated);
+
+		assert_ok!(Twitter::unlock(Origin::signed(2), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetUnlocked(2, 0, 5)));
+		assert_eq!(Balances::free_balance(2), 95);
+		assert_eq!(Twitter::gated_text(0, &2).unwrap().into_inner(), b"full story".to_vec());
+		assert_ok!(Twitter::comment(Origin::signed(2), b"first".to_vec(), 0, None));
+		assert_noop!(Twitter::unlock(Origin::signed(2), 0), Error::<Test>::AlreadyUnlocked);
+
+		// The author unlocks for free.
+		let balance = Balances::free_balance(1);
+		assert_ok!(Twitter::unlock(Origin::signed(1), 0));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::TweetUnlocked(1, 0, 0)));
+		assert_eq!(Balances::free_balance(1), balance);
+		assert_noop!(Twitter::unlock(Origin::signed(1), 0), Error::<Test>::AlreadyUnlocked);
+
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 0));
+		assert_eq!(Twitter::gated_content(0), None);
+		assert_eq!(Twitter::gate_price(0), None);
+		assert!(Twitter::unlocked(0, &2).is_none());
+		assert_noop!(Twitter::unlock(Origin::signed(3), 0), Error::<Test>::TweetDeleted);
+	});
+}
+
+#[test]
+fn force_remove_tweet_drops_gated_text() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Twitter::new_gated_tweet(Origin::signed(1), b"teaser".to_vec(), b"illegal".to_vec(), 5));
+		assert_ok!(Twitter::unlock(Origin::signed(2), 0));
+
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 0));
+		assert_eq!(Twitter::gated_text(0, &2), None);
+		assert_eq!(Twitter::gated_content(0), None);
+		assert_eq!(Twitter::gate_price(0), None);
+		assert!(Twitter::unlocked(0, &2).is_none());
+	});
+}
+
+#[test]
+fn subscriptions_are_paid_and_expire() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Twitter::subscribe(Origin::signed(2), 1), Error::<Test>::NoSubscriptionPrice);
+		assert_noop!(Twitter::set_subscription_price(Origin::signed(1), Some(0)), Error::<Test>::ZeroPrice);
+		assert_ok!(Twitter::set_subscription_price(Origin::signed(1), Some(5)));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::SubscriptionPriceSet(1, Some(5))));
+		assert_noop!(Twitter::subscribe(Origin::signed(1), 1), Error::<Test>::CannotSubscribeSelf);
+		assert_noop!(Twitter::renew(Origin::signed(2), 1), Error::<Test>::NotSubscribed);
+
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"for followers".to_vec(), vec![], true, Visibility::FollowersOnly, vec![], None, ReplyPolicy::Followers));
+		assert_noop!(Twitter::like(Origin::signed(2), 0), Error::<Test>::NotVisible);
+
+		assert_ok!(Twitter::subscribe(Origin::signed(2), 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::Subscribed(2, 1, 11)));
+		assert_eq!(Balances::free_balance(2), 95);
+		assert_noop!(Twitter::subscribe(Origin::signed(2), 1), Error::<Test>::AlreadySubscribed);
+		assert!(Twitter::is_subscriber(&1, &2, 10));
+		assert!(!Twitter::is_subscriber(&1, &2, 11));
+
+		// Subscribers see and reply to followers-only tweets until the expiry block.
+		System::set_block_number(10);
+		assert_ok!(Twitter::like(Origin::signed(2), 0));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"thanks".to_vec(), 0, None));
+		System::set_block_number(11);
+		assert_noop!(Twitter::comment(Origin::signed(2), b"again".to_vec(), 0, None), Error::<Test>::NotVisible);
+
+		// An expired subscription can be taken again or renewed from now.
+		assert_ok!(Twitter::renew(Origin::signed(2), 1));
+		assert_eq!(last_event(), TestEvent::twitter(RawEvent::SubscriptionRenewed(2, 1, 21)));
+		assert_eq!(Balances::free_balance(2), 80);
+		// A running one is extended from its expiry.
+		System::set_block_number(15);
+		assert_ok!(Twitter::renew(Origin::signed(2), 1));
+		assert_eq!(Twitter::subscription(&1, &2), Some(31));
+		System::set_block_number(31);
+		assert!(!Twitter::is_subscriber(&1, &2, 31));
+		assert_ok!(Twitter::subscribe(Origin::signed(2), 1));
+		assert_eq!(Twitter::subscription(&1, &2), Some(41));
+
+		assert_ok!(Twitter::set_subscription_price(Origin::signed(1), None));
+		assert_noop!(Twitter::renew(Origin::signed(2), 1), Error::<Test>::NoSubscriptionPrice);
+		assert!(Twitter::is_subscriber(&1, &2, 40));
+	});
+}
+
+#[test]
+fn posted_tweets_are_indexed_offchain() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"hello".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::comment(Origin::signed(2), b"hi".to_vec(), 0, None));
+		assert_ok!(Twitter::new_tweet(Origin::signed(1), b"bye".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::delete_tweet(Origin::signed(1), 2));
+		System::set_block_number(2);
+		assert_ok!(Twitter::edit_tweet(Origin::signed(1), 0, b"hello world".to_vec()));
+		assert_ok!(Twitter::new_tweet(Origin::signed(3), b"illegal".to_vec(), vec![], true, Visibility::Public, vec![], None, ReplyPolicy::Everyone));
+		assert_ok!(Twitter::force_remove_tweet(Origin::root(), 3));
+	});
+	ext.persist_offchain_overlay();
+
+	let db = ext.offchain_db();
+	let indexed = |id| db.get(b"", &Twitter::offchain_key(id))
+		.map(|raw| IndexedTweet::<u64, u64>::decode(&mut &raw[..]).unwrap());
+	assert_eq!(indexed(0), Some(IndexedTweet { author: 1, block: 2, text: b"hello world".to_vec() }));
+	assert_eq!(indexed(1), Some(IndexedTweet { author: 2, block: 1, text: b"hi".to_vec() }));
+	assert_eq!(indexed(2), None);
+	assert_eq!(indexed(3), None);
+
+	// The offchain worker of block 1 finds the entries of the tweets still posted in it.
+	let (offchain, _state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.execute_with(|| {
+		let entries = vec![indexed(0).unwrap(), indexed(1).unwrap()];
+		assert_eq!(Twitter::indexed_digest(1), (2, sp_io::hashing::blake2_256(&entries.encode())));
+		assert_eq!(Twitter::indexed_digest(2).0, 0);
+		Twitter::offchain_worker(1);
 	});
 }
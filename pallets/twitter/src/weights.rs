@@ -0,0 +1,1018 @@
+//! Weights for pallet_twitter.
+//!
+//! The figures below are estimates following the shape of the benchmarks in `benchmarking.rs`,
+//! not measurements. Replace this file with the output of `scripts/benchmark.sh`, run on
+//! reference hardware, before relying on them.
+
+use sp_std::marker::PhantomData;
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+
+/// Weight functions needed for pallet_twitter.
+pub trait WeightInfo {
+	fn new_tweet(t: u32, h: u32, m: u32, a: u32) -> Weight;
+	fn tweet_as(t: u32, h: u32) -> Weight;
+	fn new_thread(s: u32, h: u32) -> Weight;
+	fn new_ephemeral_tweet(t: u32, h: u32) -> Weight;
+	fn expire_tweets(e: u32) -> Weight;
+	fn prune_hashtag_usage(p: u32) -> Weight;
+	fn prune_recent_content(p: u32) -> Weight;
+	fn schedule_tweet(t: u32, h: u32) -> Weight;
+	fn cancel_scheduled_tweet() -> Weight;
+	fn publish_scheduled(s: u32) -> Weight;
+	fn retweet(t: u32, h: u32, a: u32, d: u32) -> Weight;
+	fn repost(d: u32) -> Weight;
+	fn comment(t: u32, h: u32) -> Weight;
+	fn delete_tweet(h: u32) -> Weight;
+	fn undo_retweet(h: u32) -> Weight;
+	fn edit_tweet(t: u32, h: u32) -> Weight;
+	fn set_comments_enabled() -> Weight;
+	fn set_reply_policy() -> Weight;
+	fn bookmark() -> Weight;
+	fn remove_bookmark() -> Weight;
+	fn pin_tweet() -> Weight;
+	fn unpin_tweet() -> Weight;
+	fn mark_notifications_read() -> Weight;
+	fn clear_notifications() -> Weight;
+	fn set_profile() -> Weight;
+	fn clear_profile() -> Weight;
+	fn claim_handle() -> Weight;
+	fn release_handle() -> Weight;
+	fn subscribe_premium() -> Weight;
+	fn cancel_premium() -> Weight;
+	fn block_account() -> Weight;
+	fn unblock_account() -> Weight;
+	fn like() -> Weight;
+	fn unlike() -> Weight;
+	fn react() -> Weight;
+	fn remove_reaction() -> Weight;
+	fn follow() -> Weight;
+	fn unfollow() -> Weight;
+	fn tip() -> Weight;
+	fn new_poll_tweet(t: u32, h: u32) -> Weight;
+	fn vote() -> Weight;
+	fn close_poll() -> Weight;
+	fn mint_tweet() -> Weight;
+	fn transfer_tweet() -> Weight;
+	fn force_remove_tweet(h: u32) -> Weight;
+	fn set_global_announcement() -> Weight;
+	fn set_text_limit() -> Weight;
+	fn set_verified() -> Weight;
+	fn report_tweet() -> Weight;
+	fn resolve_report(h: u32) -> Weight;
+	fn purge_account(l: u32) -> Weight;
+	fn add_note() -> Weight;
+	fn rate_note() -> Weight;
+	fn mute_word() -> Weight;
+	fn unmute_word() -> Weight;
+	fn add_delegate() -> Weight;
+	fn remove_delegate() -> Weight;
+	fn follow_hashtag() -> Weight;
+	fn unfollow_hashtag() -> Weight;
+	fn notify_hashtag_followers(f: u32) -> Weight;
+	fn prune_old_tweets(n: u32) -> Weight;
+	fn create_community() -> Weight;
+	fn join_community() -> Weight;
+	fn leave_community() -> Weight;
+	fn remove_member() -> Weight;
+	fn post_to_community(t: u32, h: u32) -> Weight;
+	fn commit_tweet() -> Weight;
+	fn reveal_tweet(t: u32, h: u32) -> Weight;
+	fn expire_commitments(n: u32) -> Weight;
+	fn update_settings() -> Weight;
+	fn remove_comment(h: u32) -> Weight;
+	fn force_create_tweet(t: u32, h: u32) -> Weight;
+	fn new_gated_tweet(t: u32, h: u32) -> Weight;
+	fn unlock() -> Weight;
+	fn set_subscription_price() -> Weight;
+	fn subscribe() -> Weight;
+	fn renew() -> Weight;
+}
+
+/// Weights for pallet_twitter using the runtime's database weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn new_tweet(t: u32, h: u32, m: u32, a: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((3_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((400_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(m as Weight)))
+	}
+	fn tweet_as(t: u32, h: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn new_thread(s: u32, h: u32) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().reads((s as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 * s as Weight).saturating_mul(h as Weight)))
+	}
+	fn new_ephemeral_tweet(t: u32, h: u32) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	// Each tweet is removed like `delete_tweet` with the maximum number of hashtags.
+	fn expire_tweets(e: u32) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((70_000_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((16 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((58 as Weight).saturating_mul(e as Weight)))
+	}
+	fn prune_hashtag_usage(p: u32) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn prune_recent_content(p: u32) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn schedule_tweet(t: u32, h: u32) -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((1_000_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_scheduled_tweet() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Each tweet is published with the maximum number of hashtags.
+	fn publish_scheduled(s: u32) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((16 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((32 as Weight).saturating_mul(s as Weight)))
+	}
+	fn retweet(t: u32, h: u32, a: u32, d: u32) -> Weight {
+		(53_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((400_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add((1_500_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(18 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn repost(d: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((1_500_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(19 as Weight))
+	}
+	// The number of existing comments on the parent does not affect the weight.
+	fn comment(t: u32, h: u32) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(22 as Weight))
+			.saturating_add(T::DbWeight::get().writes(20 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn delete_tweet(h: u32) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(48 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn undo_retweet(h: u32) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(48 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn edit_tweet(t: u32, h: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn set_comments_enabled() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_reply_policy() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn bookmark() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_bookmark() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn pin_tweet() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unpin_tweet() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn mark_notifications_read() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn clear_notifications() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_profile() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn clear_profile() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn claim_handle() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn release_handle() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn subscribe_premium() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_premium() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn block_account() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn unblock_account() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn like() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn unlike() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn react() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_reaction() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn follow() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn unfollow() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn tip() -> Weight {
+		(65_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn new_poll_tweet(t: u32, h: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn vote() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn close_poll() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn mint_tweet() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_tweet() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn force_remove_tweet(h: u32) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn set_global_announcement() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_text_limit() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_verified() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn report_tweet() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn resolve_report(h: u32) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(48 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	// Each item is removed like a tweet by `delete_tweet` with the maximum number of hashtags.
+	fn purge_account(l: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((70_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(T::DbWeight::get().writes(26 as Weight))
+			.saturating_add(T::DbWeight::get().writes((60 as Weight).saturating_mul(l as Weight)))
+	}
+	fn add_note() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn rate_note() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn mute_word() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn unmute_word() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_delegate() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_delegate() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn follow_hashtag() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn unfollow_hashtag() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn notify_hashtag_followers(f: u32) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((16_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(f as Weight)))
+	}
+	fn prune_old_tweets(n: u32) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((72_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((18 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((48 as Weight).saturating_mul(n as Weight)))
+	}
+	fn create_community() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn join_community() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn leave_community() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_member() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn post_to_community(t: u32, h: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn commit_tweet() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn reveal_tweet(t: u32, h: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	// Each expired commitment has its deposit slashed.
+	fn expire_commitments(n: u32) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn update_settings() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_comment(h: u32) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(20 as Weight))
+			.saturating_add(T::DbWeight::get().writes(48 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn force_create_tweet(t: u32, h: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn new_gated_tweet(t: u32, h: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn unlock() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_subscription_price() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn subscribe() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn renew() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn new_tweet(t: u32, h: u32, m: u32, a: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((3_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((400_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(m as Weight)))
+	}
+	fn tweet_as(t: u32, h: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn new_thread(s: u32, h: u32) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((38_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((s as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 * s as Weight).saturating_mul(h as Weight)))
+	}
+	fn new_ephemeral_tweet(t: u32, h: u32) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	// Each tweet is removed like `delete_tweet` with the maximum number of hashtags.
+	fn expire_tweets(e: u32) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((70_000_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((16 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((58 as Weight).saturating_mul(e as Weight)))
+	}
+	fn prune_hashtag_usage(p: u32) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn prune_recent_content(p: u32) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn schedule_tweet(t: u32, h: u32) -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((1_000_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_scheduled_tweet() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Each tweet is published with the maximum number of hashtags.
+	fn publish_scheduled(s: u32) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((16 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((32 as Weight).saturating_mul(s as Weight)))
+	}
+	fn retweet(t: u32, h: u32, a: u32, d: u32) -> Weight {
+		(53_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add((400_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add((1_500_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(18 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(21 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn repost(d: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((1_500_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(19 as Weight))
+	}
+	fn comment(t: u32, h: u32) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(22 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(20 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn delete_tweet(h: u32) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(48 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn undo_retweet(h: u32) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(48 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn edit_tweet(t: u32, h: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn set_comments_enabled() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_reply_policy() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn bookmark() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_bookmark() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn pin_tweet() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unpin_tweet() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn mark_notifications_read() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn clear_notifications() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_profile() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn clear_profile() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn claim_handle() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn release_handle() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn subscribe_premium() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_premium() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn block_account() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn unblock_account() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn like() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn unlike() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn react() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_reaction() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn follow() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn unfollow() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn tip() -> Weight {
+		(65_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn new_poll_tweet(t: u32, h: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn vote() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn close_poll() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn mint_tweet() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_tweet() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn force_remove_tweet(h: u32) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn set_global_announcement() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_text_limit() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_verified() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn report_tweet() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn resolve_report(h: u32) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(48 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	// Each item is removed like a tweet by `delete_tweet` with the maximum number of hashtags.
+	fn purge_account(l: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((70_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((17 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(26 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((60 as Weight).saturating_mul(l as Weight)))
+	}
+	fn add_note() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn rate_note() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn mute_word() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unmute_word() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_delegate() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_delegate() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn follow_hashtag() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn unfollow_hashtag() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn notify_hashtag_followers(f: u32) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((16_000_000 as Weight).saturating_mul(f as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(f as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(f as Weight)))
+	}
+	fn prune_old_tweets(n: u32) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((72_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((18 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((48 as Weight).saturating_mul(n as Weight)))
+	}
+	fn create_community() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn join_community() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn leave_community() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_member() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn post_to_community(t: u32, h: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn commit_tweet() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn reveal_tweet(t: u32, h: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	// Each expired commitment has its deposit slashed.
+	fn expire_commitments(n: u32) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((30_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn update_settings() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_comment(h: u32) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(20 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(48 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(h as Weight)))
+	}
+	fn force_create_tweet(t: u32, h: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn new_gated_tweet(t: u32, h: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(h as Weight)))
+	}
+	fn unlock() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_subscription_price() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn subscribe() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn renew() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
@@ -9,11 +9,11 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature, ModuleId,
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -95,10 +95,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 1,
+	spec_version: 26,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -261,9 +261,135 @@ impl pallet_sudo::Trait for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const MaxTextLen: u32 = 140;
+	pub const MaxTextLenPremium: u32 = 560;
+	pub const MaxTextBytes: u32 = 4 * MaxTextLenPremium::get();
+	pub const EditWindow: BlockNumber = 10 * MINUTES;
+	pub const MaxEdits: u32 = 5;
+	pub const MaxHashtagsPerTweet: u32 = 10;
+	pub const MaxMentions: u32 = 10;
+	pub const MaxAttachments: u32 = 4;
+	pub const RateLimitPeriod: BlockNumber = HOURS;
+	pub const MaxTweetsPerPeriod: u32 = 100;
+	pub const FreeTweetsPerDay: u32 = 10;
+	pub const DayLength: BlockNumber = DAYS;
+	pub const MinBalanceForFreeTweets: Balance = 100 * ExistentialDeposit::get();
+	pub const MaxSponsoredPerBlock: u32 = 10;
+	pub const MaxTweetsPerAccount: u32 = 100_000;
+	pub const MaxNotifications: u32 = 100;
+	pub const RecentBufferSize: u32 = 50;
+	pub const RecentSkipsComments: bool = true;
+	pub const MaxBlocked: u32 = 1000;
+	pub const MaxMutedWords: u32 = 100;
+	pub const MaxDelegates: u32 = 10;
+	pub const MaxBookmarks: u32 = 1000;
+	pub const MaxThreadLen: u32 = 25;
+	pub const MaxExpirationsPerBlock: u32 = 50;
+	pub const MaxScheduledPerBlock: u32 = 50;
+	pub const MaxQuoteDepth: u32 = 10;
+	pub const MaxLeaderboardSize: u32 = 20;
+	pub const TrendingWindow: BlockNumber = HOURS;
+	pub const KeepWindows: u32 = 24;
+	pub const MaxHashtagPrunesPerBlock: u32 = 100;
+	pub const DedupWindow: BlockNumber = HOURS;
+	pub const MaxContentPrunesPerBlock: u32 = 100;
+	pub const MaxCommentsPerTweet: u32 = 10_000;
+	pub const MaxFollowedHashtags: u32 = 100;
+	pub const MaxHashtagFanout: u32 = 50;
+	pub const MaxNotesPerTweet: u32 = 10;
+	pub const MaxCommunities: u32 = 1_000;
+	pub const MaxMembersTracked: u32 = 10_000;
+	pub const RevealWindow: BlockNumber = DAYS;
+	pub const MaxCommitsPerBlock: u32 = 50;
+	pub AnonymousAccount: AccountId = ModuleId(*b"tw/anony").into_account();
+	pub const FirstUserTweetId: pallet_twitter::TweetId = 1_000;
+	pub const MinNoteRatings: u32 = 5;
+	pub const NoteShowThreshold: Perbill = Perbill::from_percent(70);
+	pub const TweetDepositBase: Balance = 10 * ExistentialDeposit::get();
+	pub const TweetDepositPerByte: Balance = ExistentialDeposit::get() / 10;
+	pub const ProfileDeposit: Balance = 20 * ExistentialDeposit::get();
+	pub const HandleDeposit: Balance = 20 * ExistentialDeposit::get();
+	pub const PremiumFee: Balance = 100 * ExistentialDeposit::get();
+	pub const PremiumPeriod: BlockNumber = 30 * DAYS;
+	pub const SubscriptionPeriod: BlockNumber = 30 * DAYS;
+	pub const CommitDeposit: Balance = 10 * ExistentialDeposit::get();
+	pub const AutoHideThreshold: u32 = 10;
+	pub const MinReporterAge: BlockNumber = 7 * DAYS;
+	pub const SlashCensoredDeposit: bool = true;
+	pub const QuotesJoinConversation: bool = false;
+}
+
 /// Configure the template pallet in pallets/template.
 impl pallet_twitter::Trait for Runtime {
 	type Event = Event;
+	type MaxTextLen = MaxTextLen;
+	type MaxTextBytes = MaxTextBytes;
+	type EditWindow = EditWindow;
+	type MaxEdits = MaxEdits;
+	type MaxHashtagsPerTweet = MaxHashtagsPerTweet;
+	type MaxMentions = MaxMentions;
+	type MaxAttachments = MaxAttachments;
+	type RateLimitPeriod = RateLimitPeriod;
+	type MaxTweetsPerPeriod = MaxTweetsPerPeriod;
+	type FreeTweetsPerDay = FreeTweetsPerDay;
+	type DayLength = DayLength;
+	type MinBalanceForFreeTweets = MinBalanceForFreeTweets;
+	type MaxSponsoredPerBlock = MaxSponsoredPerBlock;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	type MaxTweetsPerAccount = MaxTweetsPerAccount;
+	type MaxNotifications = MaxNotifications;
+	type RecentBufferSize = RecentBufferSize;
+	type RecentSkipsComments = RecentSkipsComments;
+	type MaxBlocked = MaxBlocked;
+	type MaxMutedWords = MaxMutedWords;
+	type MaxDelegates = MaxDelegates;
+	type MaxBookmarks = MaxBookmarks;
+	type MaxThreadLen = MaxThreadLen;
+	type MaxExpirationsPerBlock = MaxExpirationsPerBlock;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type MaxQuoteDepth = MaxQuoteDepth;
+	type MaxLeaderboardSize = MaxLeaderboardSize;
+	type TrendingWindow = TrendingWindow;
+	type KeepWindows = KeepWindows;
+	type MaxHashtagPrunesPerBlock = MaxHashtagPrunesPerBlock;
+	type DedupWindow = DedupWindow;
+	type MaxContentPrunesPerBlock = MaxContentPrunesPerBlock;
+	type MaxCommentsPerTweet = MaxCommentsPerTweet;
+	type MaxFollowedHashtags = MaxFollowedHashtags;
+	type MaxHashtagFanout = MaxHashtagFanout;
+	type MaxNotesPerTweet = MaxNotesPerTweet;
+	type MaxCommunities = MaxCommunities;
+	type MaxMembersTracked = MaxMembersTracked;
+	type RevealWindow = RevealWindow;
+	type MaxCommitsPerBlock = MaxCommitsPerBlock;
+	type AnonymousAccount = AnonymousAccount;
+	type FirstUserTweetId = FirstUserTweetId;
+	type MinNoteRatings = MinNoteRatings;
+	type NoteShowThreshold = NoteShowThreshold;
+	type Currency = Balances;
+	type TweetDepositBase = TweetDepositBase;
+	type TweetDepositPerByte = TweetDepositPerByte;
+	type ProfileDeposit = ProfileDeposit;
+	type HandleDeposit = HandleDeposit;
+	type PremiumFee = PremiumFee;
+	type PremiumPeriod = PremiumPeriod;
+	type SubscriptionPeriod = SubscriptionPeriod;
+	type CommitDeposit = CommitDeposit;
+	type MaxTextLenPremium = MaxTextLenPremium;
+	type AutoHideThreshold = AutoHideThreshold;
+	type MinReporterAge = MinReporterAge;
+	type ModerationOrigin = frame_system::EnsureRoot<AccountId>;
+	type ForceRemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type AnnouncementOrigin = frame_system::EnsureRoot<AccountId>;
+	type SlashCensoredDeposit = SlashCensoredDeposit;
+	type QuotesJoinConversation = QuotesJoinConversation;
+	type VerifyOrigin = frame_system::EnsureRoot<AccountId>;
+	type PruneOrigin = frame_system::EnsureRoot<AccountId>;
+	type TweetHooks = ();
+	type Identity = ();
+	type WeightInfo = pallet_twitter::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -282,7 +408,7 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
-		Twitter: pallet_twitter::{Module, Call, Storage, Event<T>},
+		Twitter: pallet_twitter::{Module, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -434,6 +560,57 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_twitter_runtime_api::TwitterApi<
+		Block,
+		AccountId,
+		pallet_twitter::TweetOf<Runtime>,
+		pallet_twitter::AccountExportOf<Runtime>,
+	> for Runtime {
+		fn tweets_of(account: AccountId, offset: u32, limit: u32) -> Vec<pallet_twitter::TweetOf<Runtime>> {
+			Twitter::tweets_page_of(&account, offset, limit)
+		}
+
+		fn tweet(id: pallet_twitter::TweetId) -> Option<pallet_twitter::TweetOf<Runtime>> {
+			Twitter::tweets(id)
+		}
+
+		fn comments_of(id: pallet_twitter::TweetId, offset: u32, limit: u32) -> Vec<pallet_twitter::TweetOf<Runtime>> {
+			Twitter::comments_page_of(id, offset, limit)
+		}
+
+		fn timeline_of(account: AccountId, offset: u32, limit: u32) -> Vec<pallet_twitter::TweetOf<Runtime>> {
+			Twitter::account_timeline(&account, offset, limit)
+		}
+
+		fn tweets(ids: Vec<pallet_twitter::TweetId>) -> Vec<Option<pallet_twitter::TweetOf<Runtime>>> {
+			Twitter::tweets_by_ids(&ids)
+		}
+
+		fn recent(limit: u32) -> Vec<pallet_twitter::TweetOf<Runtime>> {
+			Twitter::recent(limit)
+		}
+
+		fn conversation(id: pallet_twitter::TweetId, offset: u32, limit: u32) -> Vec<pallet_twitter::TweetOf<Runtime>> {
+			Twitter::conversation(id, offset, limit)
+		}
+
+		fn comments_page(id: pallet_twitter::TweetId, start: u32, len: u32) -> Vec<pallet_twitter::TweetId> {
+			Twitter::comments_page(id, start, len)
+		}
+
+		fn export_account(account: AccountId, cursor: u64, limit: u32) -> pallet_twitter::AccountExportOf<Runtime> {
+			Twitter::export_account(&account, cursor, limit)
+		}
+
+		fn quote_is_stale(id: pallet_twitter::TweetId) -> Option<bool> {
+			Twitter::quote_is_stale(id)
+		}
+
+		fn followers_page(account: AccountId, start: Option<AccountId>, limit: u32) -> Vec<AccountId> {
+			Twitter::followers_page(&account, start, limit)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -472,6 +649,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_twitter, Twitter);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+
+set -e
+
+echo "*** Benchmarking pallet_twitter"
+
+cd "$(dirname "$0")/.."
+
+cargo build --release --features runtime-benchmarks
+
+./target/release/node-template benchmark \
+	--chain dev \
+	--execution wasm \
+	--wasm-execution compiled \
+	--pallet pallet_twitter \
+	--extrinsic '*' \
+	--steps 50 \
+	--repeat 20 \
+	--output
+
+mv pallet_twitter.rs pallets/twitter/src/weights.rs